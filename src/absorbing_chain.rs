@@ -0,0 +1,144 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains the analysis of absorbing Markov chains through their fundamental matrix
+use crate::linear_solver::LinearSolver;
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+
+/// Holds the analysis of an absorbing Markov chain with a row-stochastic transition matrix P. Reordering the states
+/// so the transient ones come first puts P in the canonical form [Q R; 0 I], and the fundamental matrix is
+/// N = (I - Q)^-1. Rows follow the transient states in increasing order, and the columns of the absorption
+/// probabilities follow the absorbing states in the order they were given
+#[derive(Debug, Clone)]
+pub struct AbsorbingChain<T>
+where
+    T: MatrixCompatible,
+{
+    transient_states: Vec<usize>,
+    absorbing_states: Vec<usize>,
+    fundamental_matrix: Matrix<T>,
+    absorption_probabilities: Matrix<T>,
+}
+
+impl<T> AbsorbingChain<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// Analyzes the chain with the given transition matrix and absorbing states. Errors if the matrix isn't
+    /// row-stochastic to within the square root of the machine epsilon, if a listed state is out of range, repeated,
+    /// or doesn't return to itself with probability 1, or if some transient state can never be absorbed
+    pub fn new(transition: &Matrix<T>, absorbing_states: &[usize]) -> Result<Self, MatrixError> {
+        if transition.rows() != transition.columns() {
+            return Err(MatrixError::NotSquare {
+                rows: transition.rows(),
+                columns: transition.columns(),
+            });
+        }
+
+        let size: usize = transition.rows();
+        let tolerance: T = T::epsilon().sqrt();
+        for row in 0..size {
+            let mut sum: T = T::zero();
+            for column in 0..size {
+                if transition[row][column] < T::zero() {
+                    return Err(MatrixError::NegativeEntry { row, column });
+                }
+                sum += transition[row][column];
+            }
+            if (sum - T::one()).abs() > tolerance {
+                return Err(MatrixError::NotStochastic { row });
+            }
+        }
+
+        let mut is_absorbing: Vec<bool> = vec![false; size];
+        for (position, state) in absorbing_states.iter().enumerate() {
+            if *state >= size {
+                return Err(MatrixError::IndexOutOfRange {
+                    axis: "state",
+                    position,
+                    index: *state,
+                    bound: size,
+                });
+            }
+            if is_absorbing[*state] {
+                return Err(MatrixError::InvalidArgument {
+                    name: "absorbing states",
+                    requirement: "must be distinct",
+                });
+            }
+            if (transition[*state][*state] - T::one()).abs() > tolerance {
+                return Err(MatrixError::NotAbsorbing { state: *state });
+            }
+            is_absorbing[*state] = true;
+        }
+        let transient_states: Vec<usize> =
+            (0..size).filter(|state| !is_absorbing[*state]).collect();
+
+        // I - Q and R from the canonical form
+        let mut transient_part: Matrix<T> = Matrix::identity_matrix(transient_states.len());
+        let mut absorbing_part: Matrix<T> =
+            Matrix::new(transient_states.len(), absorbing_states.len());
+        for (row, from) in transient_states.iter().enumerate() {
+            for (column, to) in transient_states.iter().enumerate() {
+                let value: T = transient_part[row][column] - transition[*from][*to];
+                transient_part.set_value(row, column, value);
+            }
+            for (column, to) in absorbing_states.iter().enumerate() {
+                absorbing_part.set_value(row, column, transition[*from][*to]);
+            }
+        }
+
+        let solver: LinearSolver<T> =
+            LinearSolver::new(&transient_part).map_err(|_| MatrixError::NeverAbsorbed)?;
+        let absorption_probabilities: Matrix<T> = solver.solve_matrix(&absorbing_part)?;
+
+        Ok(Self {
+            transient_states,
+            absorbing_states: absorbing_states.to_vec(),
+            fundamental_matrix: solver.inverse(),
+            absorption_probabilities,
+        })
+    }
+
+    /// Returns the transient states, in the order of the rows of the results
+    pub fn transient_states(&self) -> &[usize] {
+        &self.transient_states
+    }
+
+    /// Returns the absorbing states, in the order of the columns of the absorption probabilities
+    pub fn absorbing_states(&self) -> &[usize] {
+        &self.absorbing_states
+    }
+
+    /// Returns the fundamental matrix N, whose entry (i, j) is the expected number of visits to transient state j
+    /// before absorption when starting from transient state i
+    pub fn fundamental_matrix(&self) -> &Matrix<T> {
+        &self.fundamental_matrix
+    }
+
+    /// Returns the expected number of steps before absorption from each transient state, which is N times a vector of
+    /// ones
+    pub fn expected_steps(&self) -> Vec<T> {
+        self.fundamental_matrix.row_sums()
+    }
+
+    /// Returns the matrix NR, whose entry (i, j) is the probability of ending in absorbing state j when starting from
+    /// transient state i
+    pub fn absorption_probabilities(&self) -> &Matrix<T> {
+        &self.absorption_probabilities
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// Analyzes the absorbing Markov chain with this transition matrix and the given absorbing states
+    pub fn absorbing_chain_analysis(
+        &self,
+        absorbing_states: &[usize],
+    ) -> Result<AbsorbingChain<T>, MatrixError> {
+        AbsorbingChain::new(self, absorbing_states)
+    }
+}
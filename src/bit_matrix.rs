@@ -0,0 +1,502 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains a bit-packed matrix over GF(2), the integers mod 2, which also serves as the mask type for element-wise
+/// comparisons
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+use std::ops;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Represents a matrix over GF(2), zero-indexed. Each row is packed into u64 words, so addition is XOR and row
+/// operations work on 64 columns at a time. Bits past the last column are always zero
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    matrix: Vec<Vec<u64>>,
+    rows: usize,
+    columns: usize,
+}
+
+impl BitMatrix {
+    // -----CONSTRUCTORS-----
+
+    /// Creates a new zero matrix with the given size parameters
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            matrix: vec![vec![0; columns.div_ceil(WORD_BITS)]; rows],
+            rows,
+            columns,
+        }
+    }
+
+    /// Creates a new identity matrix with the given size
+    pub fn identity_matrix(size: usize) -> Self {
+        let mut output: Self = Self::new(size, size);
+        for i in 0..size {
+            output.set_value(i, i, true);
+        }
+
+        output
+    }
+
+    /// Creates a matrix from the given rows of bits. No rows gives a 0x0 matrix
+    pub fn from_bool_rows(bits: &[Vec<bool>]) -> Self {
+        let rows: usize = bits.len();
+        let columns: usize = bits.first().map_or(0, |row| row.len());
+
+        let mut output: Self = Self::new(rows, columns);
+        for (row, values) in bits.iter().enumerate() {
+            if columns != values.len() {
+                panic!("This matrix doesn't have equal column sizes!")
+            }
+            for (column, value) in values.iter().enumerate() {
+                output.set_value(row, column, *value);
+            }
+        }
+
+        output
+    }
+
+    /// Creates a matrix with a one wherever the given matrix is non-zero
+    pub fn from_matrix<T>(matrix: &Matrix<T>) -> Self
+    where
+        T: MatrixCompatible,
+    {
+        let mut output: Self = Self::new(matrix.rows(), matrix.columns());
+        for row in 0..matrix.rows() {
+            for column in 0..matrix.columns() {
+                output.set_value(row, column, !matrix[row][column].is_zero());
+            }
+        }
+
+        output
+    }
+
+    /// Converts this matrix to a dense matrix of zeros and ones
+    pub fn to_matrix(&self) -> Matrix<i32> {
+        let mut output: Matrix<i32> = Matrix::new(self.rows, self.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if self.get_value(row, column) {
+                    output.set_value(row, column, 1);
+                }
+            }
+        }
+
+        output
+    }
+
+    // -----GETTERS AND SETTERS-----
+
+    /// Returns the number of rows in the matrix
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns in the matrix
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the bit at the given position
+    pub fn get_value(&self, row: usize, column: usize) -> bool {
+        if column >= self.columns {
+            panic!("The column is out of bounds");
+        }
+
+        self.matrix[row][column / WORD_BITS] >> (column % WORD_BITS) & 1 == 1
+    }
+
+    /// Sets the bit at the given position
+    pub fn set_value(&mut self, row: usize, column: usize, value: bool) {
+        if column >= self.columns {
+            panic!("The column is out of bounds");
+        }
+
+        let mask: u64 = 1 << (column % WORD_BITS);
+        if value {
+            self.matrix[row][column / WORD_BITS] |= mask;
+        } else {
+            self.matrix[row][column / WORD_BITS] &= !mask;
+        }
+    }
+
+    // -----OPERATIONS-----
+
+    /// Adds two matrices together, which over GF(2) is XOR, or errors if their sizes differ
+    pub fn try_add(&self, rhs: &Self) -> Result<Self, &'static str> {
+        if self.rows != rhs.rows || self.columns != rhs.columns {
+            return Err("Addition requires both matrices to have the same size");
+        }
+
+        let mut output: Self = self.clone();
+        for (output_row, rhs_row) in output.matrix.iter_mut().zip(&rhs.matrix) {
+            Self::xor_words(output_row, rhs_row, 0);
+        }
+
+        Ok(output)
+    }
+
+    /// Multiplies two matrices together over GF(2), or errors if the left hand columns don't equal the right hand rows.
+    /// Each output row is the XOR of the rows of rhs selected by the set bits of the matching row of this matrix
+    pub fn try_mul(&self, rhs: &Self) -> Result<Self, &'static str> {
+        if self.columns != rhs.rows {
+            return Err(
+                "Multiplication requires the left hand columns to equal the right hand rows",
+            );
+        }
+
+        let mut output: Self = Self::new(self.rows, rhs.columns);
+        for row in 0..self.rows {
+            for k in 0..self.columns {
+                if self.get_value(row, k) {
+                    Self::xor_words(&mut output.matrix[row], &rhs.matrix[k], 0);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Returns the reduced row echelon form over GF(2)
+    pub fn reduced_echelon_form(&self) -> Self {
+        let mut output: Self = self.clone();
+        output.reduce();
+        output
+    }
+
+    /// Returns the rank over GF(2)
+    pub fn rank_gf2(&self) -> usize {
+        self.clone().reduce().len()
+    }
+
+    /// Solves Ax = b over GF(2), setting every free variable to zero. Errors if the system is inconsistent
+    pub fn solve_gf2(&self, b: &[bool]) -> Result<Vec<bool>, &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+
+        let mut augmented: Self = Self::new(self.rows, self.columns + 1);
+        for (row, value) in b.iter().enumerate() {
+            augmented.matrix[row][..self.matrix[row].len()].copy_from_slice(&self.matrix[row]);
+            augmented.set_value(row, self.columns, *value);
+        }
+
+        let pivot_columns: Vec<usize> = augmented.reduce();
+        if pivot_columns.contains(&self.columns) {
+            return Err("The system was inconsistent and there is no solution for b.");
+        }
+
+        let mut solution: Vec<bool> = vec![false; self.columns];
+        for (row, column) in pivot_columns.iter().enumerate() {
+            solution[*column] = augmented.get_value(row, self.columns);
+        }
+
+        Ok(solution)
+    }
+
+    /// Returns a basis for the null space over GF(2), with one vector for each free column
+    pub fn nullspace_gf2(&self) -> Vec<Vec<bool>> {
+        let mut reduced: Self = self.clone();
+        let pivot_columns: Vec<usize> = reduced.reduce();
+
+        let mut null_space: Vec<Vec<bool>> = Vec::new();
+        for free_column in (0..self.columns).filter(|column| !pivot_columns.contains(column)) {
+            let mut null_vector: Vec<bool> = vec![false; self.columns];
+            null_vector[free_column] = true;
+            for (row, column) in pivot_columns.iter().enumerate() {
+                null_vector[*column] = reduced.get_value(row, free_column);
+            }
+            null_space.push(null_vector);
+        }
+
+        null_space
+    }
+
+    /// Returns the number of set bits
+    pub fn count_true(&self) -> usize {
+        self.matrix
+            .iter()
+            .flatten()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns true if any bit is set
+    pub fn any_true(&self) -> bool {
+        self.matrix.iter().flatten().any(|word| *word != 0)
+    }
+
+    /// Returns true if every bit is set, which is vacuously true for an empty matrix
+    pub fn all_true(&self) -> bool {
+        self.count_true() == self.rows * self.columns
+    }
+
+    // -----PRIVATE HELPERS-----
+
+    /// Reduces this matrix to reduced row echelon form in place, returning the pivot column of each non-zero row
+    fn reduce(&mut self) -> Vec<usize> {
+        let mut pivot_columns: Vec<usize> = Vec::new();
+
+        for column in 0..self.columns {
+            let pivot_row: usize = pivot_columns.len();
+            if pivot_row == self.rows {
+                break;
+            }
+
+            let word: usize = column / WORD_BITS;
+            let mask: u64 = 1 << (column % WORD_BITS);
+            let Some(found) =
+                (pivot_row..self.rows).find(|row| self.matrix[*row][word] & mask != 0)
+            else {
+                continue;
+            };
+            self.matrix.swap(pivot_row, found);
+
+            // Rows at or below the pivot are zero before this column, so the words before it can be skipped
+            let pivot: Vec<u64> = self.matrix[pivot_row].clone();
+            for (row, values) in self.matrix.iter_mut().enumerate() {
+                if row != pivot_row && values[word] & mask != 0 {
+                    Self::xor_words(values, &pivot, word);
+                }
+            }
+            pivot_columns.push(column);
+        }
+
+        pivot_columns
+    }
+
+    /// XORs rhs into target, starting at the given word
+    fn xor_words(target: &mut [u64], rhs: &[u64], start: usize) {
+        for (target_word, rhs_word) in target[start..].iter_mut().zip(&rhs[start..]) {
+            *target_word ^= *rhs_word;
+        }
+    }
+}
+
+impl ops::Add for BitMatrix {
+    type Output = Self;
+
+    /// Adds two matrices together over GF(2)
+    fn add(self, rhs: Self) -> Self {
+        match self.try_add(&rhs) {
+            Ok(output) => output,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl ops::Mul for BitMatrix {
+    type Output = Self;
+
+    /// Multiplies two matrices together over GF(2)
+    fn mul(self, rhs: Self) -> Self {
+        match self.try_mul(&rhs) {
+            Ok(output) => output,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + num_traits::PrimInt,
+{
+    /// Returns the rank with every entry taken modulo 2
+    pub fn rank_mod2(&self) -> usize {
+        self.parity_bits().rank_gf2()
+    }
+
+    /// Returns the reduced row echelon form with every entry taken modulo 2, as a matrix of zeros and ones
+    pub fn reduced_echelon_mod2(&self) -> Self {
+        let reduced: BitMatrix = self.parity_bits().reduced_echelon_form();
+
+        let mut output: Self = Self::new(self.rows(), self.columns());
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                if reduced.get_value(row, column) {
+                    output.set_value(row, column, T::one());
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Solves Ax = b with every entry of A and b taken modulo 2, returning a solution of zeros and ones with every free
+    /// variable set to zero. Errors if b doesn't have an entry for each row or the system is inconsistent
+    pub fn solve_mod2(&self, b: &[u8]) -> Result<Vec<u8>, MatrixError> {
+        if b.len() != self.rows() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "solve_mod2",
+                left: (self.rows(), self.columns()),
+                right: (b.len(), 1),
+            });
+        }
+
+        let parities: Vec<bool> = b.iter().map(|value| value % 2 == 1).collect();
+        // The length is checked, so the only failure left is inconsistency
+        let solution: Vec<bool> = self
+            .parity_bits()
+            .solve_gf2(&parities)
+            .map_err(|_| MatrixError::Inconsistent)?;
+        Ok(solution.into_iter().map(u8::from).collect())
+    }
+
+    /// Packs the parity of each entry into a BitMatrix
+    fn parity_bits(&self) -> BitMatrix {
+        let two: T = T::one() + T::one();
+
+        let mut output: BitMatrix = BitMatrix::new(self.rows(), self.columns());
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                output.set_value(row, column, !(self[row][column] % two).is_zero());
+            }
+        }
+
+        output
+    }
+}
+
+/// An element-wise comparison. Eq and Ne carry a tolerance, so that two values are equal when they differ by at most it.
+/// Pass zero for exact equality. Ne is always the negation of Eq, so a NaN on either side is never equal and always not
+/// equal. The tolerance should not be negative: with one, Eq never holds and Ne always does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmpOp<T> {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq(T),
+    Ne(T),
+}
+
+impl<T> CmpOp<T>
+where
+    T: MatrixCompatible + PartialOrd,
+{
+    /// Applies the comparison to a pair of values
+    fn holds(&self, a: T, b: T) -> bool {
+        match self {
+            Self::Lt => a < b,
+            Self::Le => a <= b,
+            Self::Gt => a > b,
+            Self::Ge => a >= b,
+            Self::Eq(tolerance) => (a - b).abs() <= *tolerance,
+            Self::Ne(tolerance) => !Self::Eq(*tolerance).holds(a, b),
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + PartialOrd,
+{
+    /// Returns a mask with a one wherever the entry compared against the scalar satisfies op
+    pub fn compare_scalar(&self, op: CmpOp<T>, scalar: T) -> BitMatrix {
+        let mut output: BitMatrix = BitMatrix::new(self.rows(), self.columns());
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                output.set_value(row, column, op.holds(self[row][column], scalar));
+            }
+        }
+
+        output
+    }
+
+    /// Returns a mask with a one wherever the entry of this matrix compared against the matching entry of other
+    /// satisfies op. Errors if their sizes differ
+    pub fn compare(&self, other: &Self, op: CmpOp<T>) -> Result<BitMatrix, MatrixError> {
+        if self.rows() != other.rows() || self.columns() != other.columns() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "compare",
+                left: (self.rows(), self.columns()),
+                right: (other.rows(), other.columns()),
+            });
+        }
+
+        let mut output: BitMatrix = BitMatrix::new(self.rows(), self.columns());
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                output.set_value(row, column, op.holds(self[row][column], other[row][column]));
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Returns the entries where the mask is set, in row-major order. Errors if the mask's size differs from this
+    /// matrix
+    pub fn select(&self, mask: &BitMatrix) -> Result<Vec<T>, MatrixError> {
+        self.validate_mask(mask, "select")?;
+
+        let mut output: Vec<T> = Vec::with_capacity(mask.count_true());
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                if mask.get_value(row, column) {
+                    output.push(self[row][column]);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Sets every entry where the mask is set to the given value, leaving the rest untouched. Errors if the mask's size
+    /// differs from this matrix
+    pub fn set_where(&mut self, mask: &BitMatrix, value: T) -> Result<(), MatrixError> {
+        self.validate_mask(mask, "set_where")?;
+
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                if mask.get_value(row, column) {
+                    self.set_value(row, column, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies the entries of source where the mask is set, leaving the rest untouched. Errors if the size of the mask
+    /// or source differs from this matrix
+    pub fn assign_where(&mut self, mask: &BitMatrix, source: &Self) -> Result<(), MatrixError> {
+        self.validate_mask(mask, "assign_where")?;
+        if source.rows() != self.rows() || source.columns() != self.columns() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "assign_where",
+                left: (self.rows(), self.columns()),
+                right: (source.rows(), source.columns()),
+            });
+        }
+
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                if mask.get_value(row, column) {
+                    self.set_value(row, column, source[row][column]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Errors as a dimension mismatch in op if the mask's size differs from this matrix
+    fn validate_mask(&self, mask: &BitMatrix, op: &'static str) -> Result<(), MatrixError> {
+        if mask.rows() != self.rows() || mask.columns() != self.columns() {
+            return Err(MatrixError::DimensionMismatch {
+                op,
+                left: (self.rows(), self.columns()),
+                right: (mask.rows(), mask.columns()),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -2,11 +2,30 @@
 /// https://github.com/BraydenJonsson/rust-matrix
 ///
 /// Contains tests for the matrix library
-mod matrix;
+pub mod absorbing_chain;
+pub mod bit_matrix;
+pub mod linear_solver;
+pub mod matrix;
+pub mod matrix_assert;
+pub mod matrix_builder;
+pub mod matrix_display;
+pub mod matrix_error;
+pub mod pivoted_cholesky;
+pub mod pivoted_qr;
+pub mod semiring;
+
+// Re-exported so that the assertion macros work in crates that don't depend on num_traits
+#[doc(hidden)]
+pub use num_traits;
 
 #[cfg(test)]
+#[allow(clippy::excessive_precision)]
 mod f64tests {
-    use crate::matrix::Matrix;
+    use crate::matrix::{ConvolutionMode, EchelonForm, EulerOrder, Matrix, Padding, SolveReport};
+    use crate::matrix_builder::MatrixBuilder;
+    use crate::matrix_error::MatrixError;
+    use crate::{assert_matrix_approx_eq, assert_matrix_eq};
+    use std::cmp::Ordering;
 
     const COMPARISON_TOLERANCE: f64 = 0.000000001;
     const STANDARD_MATRIX_A: &[f64] = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
@@ -51,8 +70,8 @@ mod f64tests {
 
     #[test]
     fn square_addition() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let mut solution_list: Vec<f64> = Vec::with_capacity(9);
         for index in 0..9 {
@@ -64,8 +83,8 @@ mod f64tests {
 
     #[test]
     fn square_subtraction() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let mut solution_list: Vec<f64> = Vec::with_capacity(9);
         for index in 0..9 {
@@ -77,11 +96,11 @@ mod f64tests {
 
     #[test]
     fn square_scalar() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
         let mut solution_list: Vec<f64> = Vec::with_capacity(9);
-        for index in 0..9 {
-            solution_list.push(STANDARD_MATRIX_A[index] * 3.7);
+        for value in STANDARD_MATRIX_A {
+            solution_list.push(value * 3.7);
         }
         let solution_matrix: Matrix<f64> = Matrix::square_matrix_from_list(&solution_list);
         assert!(solution_matrix.equals(&(a * 3.7), COMPARISON_TOLERANCE));
@@ -89,44 +108,43 @@ mod f64tests {
 
     #[test]
     fn square_multiplication() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let solution_matrix: Matrix<f64> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_MULTIPLICATION_SOLUTION.to_vec());
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_MULTIPLICATION_SOLUTION);
         assert!(solution_matrix.equals(&(a * b), COMPARISON_TOLERANCE));
     }
 
     #[test]
     fn square_reverse_multiplication() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
-        let solution_matrix: Matrix<f64> = Matrix::square_matrix_from_list(
-            &STANDARD_MATRIX_REVERSE_MULTIPLICATION_SOLUTION.to_vec(),
-        );
+        let solution_matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_REVERSE_MULTIPLICATION_SOLUTION);
         assert!(solution_matrix.equals(&(b * a), COMPARISON_TOLERANCE));
     }
 
     #[test]
     fn a_inverse() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
         assert_eq!(a.inverse().unwrap_err(), "Matrix is not invertible");
     }
 
     #[test]
     fn b_inverse() {
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let solution_matrix: Matrix<f64> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_B_INVERSE_SOLUTION.to_vec());
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_B_INVERSE_SOLUTION);
         assert!(solution_matrix.equals(&(b.inverse()).unwrap(), COMPARISON_TOLERANCE));
     }
 
     #[test]
     fn a_determinant() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
         let determinant: f64 = a.determinant();
 
@@ -135,7 +153,7 @@ mod f64tests {
 
     #[test]
     fn b_determinant() {
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let determinant: f64 = b.determinant();
 
@@ -144,51 +162,49 @@ mod f64tests {
 
     #[test]
     fn a_ref() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
-        let solution_matrix: Matrix<f64> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_A_REF.to_vec());
+        let solution_matrix: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A_REF);
         assert!(solution_matrix.equals(&(a.reduced_echelon_form()), COMPARISON_TOLERANCE));
     }
 
     #[test]
     fn b_ref() {
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
-        let solution_matrix: Matrix<f64> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_B_REF.to_vec());
+        let solution_matrix: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B_REF);
         assert!(solution_matrix.equals(&(b.reduced_echelon_form()), COMPARISON_TOLERANCE));
     }
 
     #[test]
     fn a_transpose() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
         let solution_matrix: Matrix<f64> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_A_TRANSPOSE.to_vec());
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_A_TRANSPOSE);
         assert!(solution_matrix.equals(&(a.transpose()), COMPARISON_TOLERANCE));
     }
 
     #[test]
     fn b_transpose() {
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let solution_matrix: Matrix<f64> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_B_TRANSPOSE.to_vec());
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_B_TRANSPOSE);
         assert!(solution_matrix.equals(&(b.transpose()), COMPARISON_TOLERANCE));
     }
 
     #[test]
     #[should_panic]
     fn wrong_length_b_vector() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
-        a.solve(WRONG_LENGTH_B_VECTOR.to_vec());
+        let _ = a.solve(WRONG_LENGTH_B_VECTOR.to_vec());
     }
 
     #[test]
     fn solve_a() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
         assert_eq!(
             a.solve(B_VECTOR.to_vec()).unwrap_err(),
@@ -198,7 +214,7 @@ mod f64tests {
 
     #[test]
     fn solve_b() {
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let solution_vector: Vec<f64> = STANDARD_MATRIX_B_SOLUTION.to_vec();
 
@@ -211,7 +227,7 @@ mod f64tests {
 
     #[test]
     fn least_squares_a() {
-        let a: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
 
         assert_eq!(
             a.least_squares_solution(B_VECTOR.to_vec()).unwrap_err(),
@@ -221,7 +237,7 @@ mod f64tests {
 
     #[test]
     fn least_squares_b() {
-        let b: Matrix<f64> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
         let solution_vector: Vec<f64> = STANDARD_MATRIX_B_SOLUTION.to_vec();
 
@@ -231,239 +247,5548 @@ mod f64tests {
             assert!((solution_vector[i] - b_solution[i]).abs() < COMPARISON_TOLERANCE);
         }
     }
-}
 
-// Runs the exact same tests* as f64tests, but with f32 instead
-// least_squares_a is different because it can actually solve the system consistently
-mod f32tests {
-    use crate::matrix::Matrix;
+    #[test]
+    fn set_submatrix() {
+        let mut matrix: Matrix<f64> = Matrix::square_matrix(4);
+        let block: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, 4.0]);
 
-    const COMPARISON_TOLERANCE: f32 = 0.001;
-    const STANDARD_MATRIX_A: &[f32] = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
-    const STANDARD_MATRIX_B: &[f32] = &[5.7, 1.2, 0.0, 4.9, -7.1, -2.1, 77.1, 0.0, 9.1];
-    // These matrices were calculated using reshish.com
-    const STANDARD_MATRIX_MULTIPLICATION_SOLUTION: &[f32] =
-        &[246.8, -13.0, 23.1, 509.9, -30.7, 44.1, 773.0, -48.4, 65.1];
-    const STANDARD_MATRIX_REVERSE_MULTIPLICATION_SOLUTION: &[f32] =
-        &[10.5, 17.4, 24.3, -38.2, -42.5, -46.8, 140.8, 227.0, 313.2];
-    const STANDARD_MATRIX_B_INVERSE_SOLUTION: &[f32] = &[
-        0.10487325447955369214,
-        0.017725057095135835292,
-        0.0040903977911851927602,
-        0.33518537455545329559,
-        -0.084194021201895217641,
-        -0.019429389508129665611,
-        -0.88854152971138347965,
-        -0.15017603319065636276,
-        0.075234102230727652557,
-    ];
+        matrix.set_submatrix(0, 0, &block).unwrap();
 
-    const STANDARD_MATRIX_A_DETERMINANT: f32 = 0.0;
-    const STANDARD_MATRIX_B_DETERMINANT: f32 = -616.077;
+        assert_eq!(matrix[0][0], 1.0);
+        assert_eq!(matrix[0][1], 2.0);
+        assert_eq!(matrix[1][0], 3.0);
+        assert_eq!(matrix[1][1], 4.0);
+        assert_eq!(matrix[2][2], 0.0);
+        assert!(matrix.submatrix(0, 0, 2, 2).unwrap() == block);
+        assert!(matrix.set_submatrix(3, 3, &block).is_err());
+        assert_eq!(
+            matrix.set_submatrix(usize::MAX, 0, &block).unwrap_err(),
+            "The submatrix exceeds the bounds of this matrix"
+        );
+    }
 
-    const STANDARD_MATRIX_A_REF: &[f32] = &[1.0, 0.0, -1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0];
+    #[test]
+    fn submatrix_overflowing_bounds() {
+        let matrix: Matrix<f64> = Matrix::square_matrix(4);
 
-    const STANDARD_MATRIX_B_REF: &[f32] = &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        assert_eq!(
+            matrix.submatrix(2, 0, usize::MAX, 1).unwrap_err(),
+            "The submatrix exceeds the bounds of this matrix"
+        );
+        assert_eq!(
+            matrix.submatrix(0, usize::MAX, 1, 2).unwrap_err(),
+            "The submatrix exceeds the bounds of this matrix"
+        );
+    }
 
-    const STANDARD_MATRIX_A_TRANSPOSE: &[f32] = &[1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0];
+    #[test]
+    fn builder_from_filtered_iterator() {
+        let rows: Vec<Vec<f64>> = vec![
+            vec![1.0, 2.0],
+            vec![-1.0, 0.0],
+            vec![3.0, 4.0],
+            vec![-5.0, 1.0],
+        ];
 
-    const STANDARD_MATRIX_B_TRANSPOSE: &[f32] = &[5.7, 4.9, 77.1, 1.2, -7.1, 0.0, 0.0, -2.1, 9.1];
+        let matrix: Matrix<f64> =
+            Matrix::from_row_iter(rows.into_iter().filter(|row| row[0] > 0.0)).unwrap();
 
-    const B_VECTOR: &[f32] = &[3.9, 7.2, -1.0];
+        assert_matrix_eq!(
+            matrix,
+            Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0], 2, 2)
+        );
+    }
 
-    const WRONG_LENGTH_B_VECTOR: &[f32] = &[-2.0, 1.0, 9.8, -0.1];
+    #[test]
+    fn builder_mismatched_row() {
+        let mut builder: MatrixBuilder<f64> = MatrixBuilder::new();
 
-    const STANDARD_MATRIX_B_SOLUTION: &[f32] = &[
-        0.53253570576405222074,
-        0.72045539762075195146,
-        -4.6218135070778490354,
-    ];
+        let result = builder.push_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0],
+            vec![9.0, 10.0, 11.0],
+        ]);
 
-    const LEAST_SQUARES_A_SOLUTION: &[f32] = &[-7.45, 6.6333333333333332766, 0.0];
+        assert_eq!(
+            result.unwrap_err(),
+            MatrixError::RowLengthMismatch {
+                row: 2,
+                expected: 3,
+                actual: 2
+            }
+        );
+        // The rejected row isn't added, and push_rows stops there
+        assert_eq!(builder.row_count(), 2);
+
+        assert_eq!(
+            builder.push_row(vec![1.0, 2.0, 3.0, 4.0]).unwrap_err(),
+            MatrixError::RowLengthMismatch {
+                row: 2,
+                expected: 3,
+                actual: 4
+            }
+        );
+        builder.push_row(vec![7.0, 8.0, 9.0]).unwrap();
+        assert_matrix_eq!(
+            builder.build().unwrap(),
+            Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], 3, 3)
+        );
+
+        // from_row_iter keeps the index of the mismatched row too
+        assert_eq!(
+            Matrix::from_row_iter(vec![vec![1.0], vec![2.0], vec![3.0, 4.0]]).unwrap_err(),
+            MatrixError::RowLengthMismatch {
+                row: 2,
+                expected: 1,
+                actual: 2
+            }
+        );
+    }
 
     #[test]
-    fn square_addition() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn builder_empty() {
+        let builder: MatrixBuilder<f64> = MatrixBuilder::new();
 
-        let mut solution_list: Vec<f32> = Vec::with_capacity(9);
-        for index in 0..9 {
-            solution_list.push(STANDARD_MATRIX_A[index] + STANDARD_MATRIX_B[index]);
-        }
-        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(&solution_list);
-        assert!(solution_matrix.equals(&(a + b), COMPARISON_TOLERANCE));
+        assert_eq!(builder.build().unwrap_err(), MatrixError::NoRows);
+        assert_eq!(
+            Matrix::<f64>::from_row_iter(Vec::new()).unwrap_err(),
+            MatrixError::NoRows
+        );
     }
 
     #[test]
-    fn square_subtraction() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn b_independence() {
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
 
-        let mut solution_list: Vec<f32> = Vec::with_capacity(9);
-        for index in 0..9 {
-            solution_list.push(STANDARD_MATRIX_A[index] - STANDARD_MATRIX_B[index]);
+        assert_eq!(b.rank(), 3);
+        assert!(b.rows_independent());
+        assert!(b.columns_independent());
+    }
+
+    #[test]
+    fn dependent_rows() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 2.0, 4.0, 1.0, 0.0], 3, 2);
+
+        assert_eq!(matrix.rank(), 2);
+        assert!(!matrix.rows_independent());
+        assert!(matrix.columns_independent());
+    }
+
+    #[test]
+    fn tile() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+
+        let tiled: Matrix<f64> = matrix.tile(2, 2);
+
+        assert_eq!(tiled.rows(), 4);
+        assert_eq!(tiled.columns(), 6);
+        assert_eq!(tiled[0][0], 1.0);
+        assert_eq!(tiled[0][5], 3.0);
+        assert_eq!(tiled[3][0], 4.0);
+        assert_eq!(tiled[3][5], 6.0);
+        assert_eq!(tiled[2][3], 1.0);
+    }
+
+    #[test]
+    fn repeat_elements() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+        let ones: Matrix<f64> = Matrix::matrix_from_list(&[1.0; 6], 3, 2);
+
+        assert!(matrix.repeat_elements(3, 2) == matrix.kronecker(&ones));
+    }
+
+    #[test]
+    fn zero_repetitions() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let tiled: Matrix<f64> = matrix.tile(0, 2);
+        let repeated: Matrix<f64> = matrix.repeat_elements(2, 0);
+
+        assert_eq!((tiled.rows(), tiled.columns()), (0, 6));
+        assert_eq!((repeated.rows(), repeated.columns()), (6, 0));
+    }
+
+    #[test]
+    fn row_and_column_sums() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        assert_eq!(a.row_sums(), vec![6.0, 15.0, 24.0]);
+        assert_eq!(a.column_sums(), vec![12.0, 15.0, 18.0]);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_sums() {
+        let list: Vec<f64> = (0..40000).map(|value| (value % 97) as f64).collect();
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&list);
+
+        assert_eq!(matrix.par_row_sums(), matrix.row_sums());
+        assert_eq!(matrix.par_column_sums(), matrix.column_sums());
+    }
+
+    #[test]
+    fn hadamard_pow() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, -2.0, 3.0, 0.5]);
+
+        let solution_matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 4.0, 9.0, 0.25]);
+        assert!(solution_matrix.equals(&matrix.hadamard_pow(2), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn checked_arithmetic_mismatches() {
+        let a: Matrix<f64> = Matrix::new(2, 3);
+        let b: Matrix<f64> = Matrix::new(3, 2);
+        let c: Matrix<f64> = Matrix::new(2, 2);
+
+        let mismatch = |op: &'static str, right: (usize, usize)| MatrixError::DimensionMismatch {
+            op,
+            left: (2, 3),
+            right,
+        };
+        assert_eq!(a.try_add(&b).unwrap_err(), mismatch("add", (3, 2)));
+        assert_eq!(a.try_sub(&b).unwrap_err(), mismatch("sub", (3, 2)));
+        assert_eq!(a.try_mul(&c).unwrap_err(), mismatch("mul", (2, 2)));
+        assert_eq!(
+            a.try_mul_vec(&[1.0, 2.0]).unwrap_err(),
+            mismatch("mul_vec", (2, 1))
+        );
+        assert_eq!(a.try_combine(&b).unwrap_err(), mismatch("hstack", (3, 2)));
+        assert_eq!(a.try_hstack(&b).unwrap_err(), mismatch("hstack", (3, 2)));
+        assert_eq!(
+            a.try_add(&b).unwrap_err().to_string(),
+            "Dimension mismatch in add: the left is 2x3 but the right is 3x2"
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic_agrees_with_operators() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        assert_matrix_eq!(a.try_add(&b).unwrap(), a.clone() + b.clone());
+        assert_matrix_eq!(a.try_sub(&b).unwrap(), a.clone() - b.clone());
+        assert_matrix_eq!(a.try_mul(&b).unwrap(), a.clone() * b.clone());
+        assert_eq!(
+            a.try_mul_vec(&[1.0, 0.0, -1.0]).unwrap(),
+            vec![-2.0, -2.0, -2.0]
+        );
+        assert_matrix_eq!(a.try_combine(&b).unwrap(), a.try_hstack(&b).unwrap());
+        assert_matrix_eq!(
+            a.try_hstack(&b).unwrap(),
+            Matrix::matrix_from_list(
+                &[
+                    1.0, 2.0, 3.0, 5.7, 1.2, 0.0, 4.0, 5.0, 6.0, 4.9, -7.1, -2.1, 7.0, 8.0, 9.0,
+                    77.1, 0.0, 9.1
+                ],
+                3,
+                6
+            )
+        );
+    }
+
+    #[test]
+    fn solve_general() {
+        let matrix: Matrix<f64> =
+            Matrix::matrix_from_list(&[1.0, 2.0, 0.0, 1.0, 0.0, 0.0, 1.0, 3.0], 2, 4);
+        let b: Vec<f64> = vec![4.0, 5.0];
+
+        let (particular_solution, null_space) = matrix.solve_general(&b).unwrap();
+
+        assert_eq!(null_space.len(), 2);
+        let product: Vec<f64> = matrix.try_mul_vec(&particular_solution).unwrap();
+        for i in 0..b.len() {
+            assert!((product[i] - b[i]).abs() < COMPARISON_TOLERANCE);
+        }
+        for null_vector in null_space {
+            for value in matrix.try_mul_vec(&null_vector).unwrap() {
+                assert!(value.abs() < COMPARISON_TOLERANCE);
+            }
         }
-        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(&solution_list);
-        assert!(solution_matrix.equals(&(a - b), COMPARISON_TOLERANCE));
     }
 
     #[test]
-    fn square_scalar() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn factorized_solves() {
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let solver = b.factorize().unwrap();
 
-        let mut solution_list: Vec<f32> = Vec::with_capacity(9);
-        for index in 0..9 {
-            solution_list.push(STANDARD_MATRIX_A[index] * 3.7);
+        for b_vector in [B_VECTOR, &[1.0, 0.0, 0.0], &[-4.0, 2.5, 8.0]] {
+            let expected: Vec<f64> = b.solve(b_vector.to_vec()).unwrap();
+            let actual: Vec<f64> = solver.solve(b_vector).unwrap();
+            for i in 0..expected.len() {
+                assert!((expected[i] - actual[i]).abs() < COMPARISON_TOLERANCE);
+            }
         }
-        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(&solution_list);
-        assert!(solution_matrix.equals(&(a * 3.7), COMPARISON_TOLERANCE));
     }
 
     #[test]
-    fn square_multiplication() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn factorized_determinant_and_inverse() {
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let solver = b.factorize().unwrap();
 
-        let solution_matrix: Matrix<f32> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_MULTIPLICATION_SOLUTION.to_vec());
-        assert!(solution_matrix.equals(&(a * b), COMPARISON_TOLERANCE));
+        assert!(
+            (solver.determinant() - STANDARD_MATRIX_B_DETERMINANT).abs() < COMPARISON_TOLERANCE
+        );
+        assert!(solver
+            .inverse()
+            .equals(&b.inverse().unwrap(), COMPARISON_TOLERANCE));
     }
 
     #[test]
-    fn square_reverse_multiplication() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn factorize_singular() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0]);
 
-        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(
-            &STANDARD_MATRIX_REVERSE_MULTIPLICATION_SOLUTION.to_vec(),
+        assert_eq!(matrix.factorize().unwrap_err(), MatrixError::Singular);
+        assert_eq!(
+            Matrix::<f64>::new(2, 3).factorize().unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3
+            }
+        );
+
+        let solver = Matrix::<f64>::identity_matrix(2).factorize().unwrap();
+        assert_eq!(
+            solver.solve(&[1.0]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve",
+                left: (2, 2),
+                right: (1, 1)
+            }
+        );
+        assert_eq!(
+            solver.solve_matrix(&Matrix::new(3, 2)).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve_matrix",
+                left: (2, 2),
+                right: (3, 2)
+            }
         );
-        assert!(solution_matrix.equals(&(b * a), COMPARISON_TOLERANCE));
     }
 
     #[test]
-    fn a_inverse() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn solve_with_free_variable() {
+        // x + z = 1 and y + z = 1, where z is free. This used to index past the last row of the reduced matrix
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 0.0, 1.0, 0.0, 1.0, 1.0], 2, 3);
 
-        assert_eq!(a.inverse().unwrap_err(), "Matrix is not invertible");
+        assert_eq!(matrix.solve(vec![1.0, 1.0]).unwrap(), vec![1.0, 1.0, 0.0]);
     }
 
     #[test]
-    fn b_inverse() {
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn sqrt_identity() {
+        let identity: Matrix<f64> = Matrix::identity_matrix(3);
 
-        let solution_matrix: Matrix<f32> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_B_INVERSE_SOLUTION.to_vec());
-        assert!(solution_matrix.equals(&(b.inverse()).unwrap(), COMPARISON_TOLERANCE));
+        assert_matrix_approx_eq!(
+            identity.sqrt(COMPARISON_TOLERANCE).unwrap(),
+            identity,
+            tol = COMPARISON_TOLERANCE
+        );
     }
 
     #[test]
-    fn a_determinant() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn sqrt_spd() {
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[4.0, 1.0, 0.5, 1.0, 3.0, 0.2, 0.5, 0.2, 2.0]);
 
-        let determinant: f32 = a.determinant();
+        let root: Matrix<f64> = matrix.sqrt(COMPARISON_TOLERANCE).unwrap();
+        assert_matrix_approx_eq!(root.clone() * root, matrix, tol = COMPARISON_TOLERANCE);
 
-        assert!((determinant - STANDARD_MATRIX_A_DETERMINANT).abs() < COMPARISON_TOLERANCE);
+        let root: Matrix<f64> = matrix.sqrt_denman_beavers(1e-12, 50).unwrap();
+        assert_matrix_approx_eq!(root.clone() * root, matrix, tol = COMPARISON_TOLERANCE);
     }
 
     #[test]
-    fn b_determinant() {
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn sqrt_clamps_tiny_negative_eigenvalues() {
+        // Eigenvalues of 2 and -1e-15 with eigenvectors (1, 1) and (1, -1)
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            1.0 - 0.5e-15,
+            1.0 + 0.5e-15,
+            1.0 + 0.5e-15,
+            1.0 - 0.5e-15,
+        ]);
 
-        let determinant: f32 = b.determinant();
+        let root: Matrix<f64> = matrix.sqrt(1e-12).unwrap();
+        assert_matrix_approx_eq!(root.clone() * root, matrix, tol = COMPARISON_TOLERANCE);
+    }
 
-        assert!((determinant - STANDARD_MATRIX_B_DETERMINANT).abs() < COMPARISON_TOLERANCE);
+    #[test]
+    fn sqrt_tolerance_only_clamps() {
+        // A loose clamp tolerance neither stops the eigendecomposition early nor excuses an asymmetric matrix
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[4.0, 0.3, 0.3, 1.0]);
+        let root: Matrix<f64> = matrix.sqrt(0.5).unwrap();
+        assert_matrix_approx_eq!(root.clone() * root, matrix, tol = COMPARISON_TOLERANCE);
+
+        let asymmetric: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 0.1, 0.0, 1.0]);
+        assert_eq!(asymmetric.sqrt(0.5).unwrap_err(), MatrixError::NotSymmetric);
     }
 
     #[test]
-    fn a_ref() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn sqrt_indefinite() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 1.0]);
 
-        let solution_matrix: Matrix<f32> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_A_REF.to_vec());
-        assert!(solution_matrix.equals(&(a.reduced_echelon_form()), COMPARISON_TOLERANCE));
+        assert_eq!(
+            matrix.sqrt(COMPARISON_TOLERANCE).unwrap_err(),
+            MatrixError::NotPositiveSemiDefinite
+        );
+        assert_eq!(
+            Matrix::<f64>::new(2, 3).sqrt(0.0).unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3
+            }
+        );
     }
 
     #[test]
-    fn b_ref() {
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn sqrt_large_spd() {
+        // X^T * X + 1e-3 * I for a fixed pseudo-random 30x30 X, at a few scales
+        let size: usize = 30;
+        let mut state: u64 = 12345;
+        let mut random = Matrix::<f64>::new(size, size);
+        for row in 0..size {
+            for column in 0..size {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                random.set_value(
+                    row,
+                    column,
+                    (state >> 33) as f64 / (1u64 << 31) as f64 - 0.5,
+                );
+            }
+        }
 
-        let solution_matrix: Matrix<f32> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_B_REF.to_vec());
-        assert!(solution_matrix.equals(&(b.reduced_echelon_form()), COMPARISON_TOLERANCE));
+        for scale in [1e-8, 1.0, 1e8] {
+            let scaled: Matrix<f64> = random.clone() * scale;
+            let matrix: Matrix<f64> = scaled.transpose() * scaled
+                + Matrix::identity_matrix(size) * (1e-3 * scale * scale);
+
+            for root in [matrix.sqrt(0.0).unwrap(), matrix.sqrtm_spd().unwrap()] {
+                let residual: f64 = (root.clone() * root - matrix.clone()).frobenius_norm();
+                assert!(residual <= 1e-12 * matrix.frobenius_norm());
+            }
+        }
     }
 
     #[test]
-    fn a_transpose() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn matrix_log_2x2() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[3.0, 1.0, 0.5, 2.0]);
 
-        let solution_matrix: Matrix<f32> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_A_TRANSPOSE.to_vec());
-        assert!(solution_matrix.equals(&(a.transpose()), COMPARISON_TOLERANCE));
+        let logarithm: Matrix<f64> = matrix.matrix_log_2x2().unwrap();
+
+        assert_matrix_approx_eq!(
+            logarithm.matrix_exp().unwrap(),
+            matrix,
+            tol = COMPARISON_TOLERANCE
+        );
     }
 
     #[test]
-    fn b_transpose() {
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn matrix_log_2x2_errors() {
+        let negative: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 1.0]);
+        let rotation: Matrix<f64> = Matrix::square_matrix_from_list(&[0.0, -1.0, 1.0, 0.0]);
+        let jordan_block: Matrix<f64> = Matrix::square_matrix_from_list(&[2.0, 1.0, 0.0, 2.0]);
 
-        let solution_matrix: Matrix<f32> =
-            Matrix::square_matrix_from_list(&STANDARD_MATRIX_B_TRANSPOSE.to_vec());
-        assert!(solution_matrix.equals(&(b.transpose()), COMPARISON_TOLERANCE));
+        assert_eq!(
+            negative.matrix_log_2x2().unwrap_err(),
+            "The matrix does not have positive eigenvalues"
+        );
+        assert_eq!(
+            rotation.matrix_log_2x2().unwrap_err(),
+            "The matrix does not have real eigenvalues"
+        );
+        assert_eq!(
+            jordan_block.matrix_log_2x2().unwrap_err(),
+            "The matrix is not diagonalizable"
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn wrong_length_b_vector() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn spectral_norm() {
+        let diagonal: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[3.0, 0.0, 0.0, 0.0, -7.0, 0.0, 0.0, 0.0, 2.0]);
+        let angle: f64 = 0.7;
+        let rotation: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[angle.cos(), -angle.sin(), angle.sin(), angle.cos()]);
 
-        a.solve(WRONG_LENGTH_B_VECTOR.to_vec());
+        let diagonal_norm: f64 = diagonal.spectral_norm(COMPARISON_TOLERANCE, 1000).unwrap();
+        let rotation_norm: f64 = rotation.spectral_norm(COMPARISON_TOLERANCE, 1000).unwrap();
+
+        assert!((diagonal_norm - 7.0).abs() < 1e-6);
+        assert!((rotation_norm - 1.0).abs() < 1e-6);
+
+        // The tolerance applies to sigma, so a large norm doesn't demand an absurdly tight tolerance on sigma^2
+        let large: Matrix<f64> = Matrix::square_matrix_from_list(&[1000.0, 0.0, 0.0, 900.0]);
+        let large_norm: f64 = large.spectral_norm(1e-3, 40).unwrap();
+        assert!((large_norm - 1000.0).abs() < 1e-2);
     }
 
     #[test]
-    fn solve_a() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn spectral_radius() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[4.0, 1.0, 2.0, 3.0]);
+
+        let radius: f64 = matrix.spectral_radius(COMPARISON_TOLERANCE, 1000).unwrap();
+
+        assert!((radius - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spectral_radius_negative_dominant_eigenvalue() {
+        // The eigenvalues are -6, 3 and 1, so the radius is the magnitude of the negative one
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[-6.0, 2.0, 1.0, 0.0, 3.0, -1.0, 0.0, 0.0, 1.0]);
+
+        let radius: f64 = matrix.spectral_radius(COMPARISON_TOLERANCE, 1000).unwrap();
+
+        assert!((radius - 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spectral_radius_not_converged() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 0.0, 0.0, 0.99]);
 
         assert_eq!(
-            a.solve(B_VECTOR.to_vec()).unwrap_err(),
-            "The system was inconsistent and there is no solution for b."
+            matrix.spectral_radius(COMPARISON_TOLERANCE, 2).unwrap_err(),
+            MatrixError::NotConverged { iterations: 2 }
+        );
+        assert_eq!(
+            matrix.spectral_norm(COMPARISON_TOLERANCE, 2).unwrap_err(),
+            MatrixError::NotConverged { iterations: 2 }
+        );
+        assert_eq!(
+            Matrix::<f64>::new(2, 3)
+                .spectral_radius(COMPARISON_TOLERANCE, 1000)
+                .unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3
+            }
         );
     }
 
     #[test]
-    fn solve_b() {
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn sqrtm_spd() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[5.0, 2.0, 2.0, 2.0]);
+        let indefinite: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 1.0]);
 
-        let solution_vector: Vec<f32> = STANDARD_MATRIX_B_SOLUTION.to_vec();
+        let root: Matrix<f64> = matrix.sqrtm_spd().unwrap();
 
-        let b_solution: Vec<f32> = b.solve(B_VECTOR.to_vec()).unwrap();
+        assert_matrix_approx_eq!(root.clone() * root, matrix, tol = COMPARISON_TOLERANCE);
+        assert_eq!(
+            indefinite.sqrtm_spd().unwrap_err(),
+            "The matrix was not positive-definite"
+        );
+    }
 
-        for i in 0..solution_vector.len() {
-            assert!((solution_vector[i] - b_solution[i]).abs() < COMPARISON_TOLERANCE);
-        }
+    #[test]
+    fn cholesky() {
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[4.0, 2.0, -2.0, 2.0, 10.0, 2.0, -2.0, 2.0, 5.0]);
+
+        let lower: Matrix<f64> = matrix.cholesky().unwrap();
+
+        assert_eq!(lower[0][1], 0.0);
+        assert_matrix_approx_eq!(
+            lower.clone() * lower.transpose(),
+            matrix,
+            tol = COMPARISON_TOLERANCE
+        );
     }
 
     #[test]
-    fn least_squares_a() {
-        let a: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_A.to_vec());
+    fn nuclear_norm() {
+        let diagonal: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[3.0, 0.0, 0.0, 0.0, -7.0, 0.0, 0.0, 0.0, 2.0]);
+        // The outer product of u = (1, 2, 2) and v = (3, 4), so the norm is |u||v| = 3 * 5
+        let outer_product: Matrix<f64> =
+            Matrix::matrix_from_list(&[3.0, 4.0, 6.0, 8.0, 6.0, 8.0], 3, 2);
 
-        let solution_vector: Vec<f32> = LEAST_SQUARES_A_SOLUTION.to_vec();
+        let diagonal_norm: f64 = diagonal.nuclear_norm(COMPARISON_TOLERANCE).unwrap();
+        let outer_product_norm: f64 = outer_product.nuclear_norm(COMPARISON_TOLERANCE).unwrap();
 
-        let a_solution: Vec<f32> = a.least_squares_solution(B_VECTOR.to_vec()).unwrap();
+        assert!((diagonal_norm - 12.0).abs() < 1e-6);
+        assert!((outer_product_norm - 15.0).abs() < 1e-6);
+    }
 
-        for i in 0..solution_vector.len() {
-            assert!((solution_vector[i] - a_solution[i]).abs() < COMPARISON_TOLERANCE);
-        }
+    #[test]
+    fn schatten_norm() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 0.5, -3.0, 4.0, 1.0], 2, 3);
+
+        let two_norm: f64 = matrix.schatten_norm(2.0, COMPARISON_TOLERANCE).unwrap();
+        let infinity_norm: f64 = matrix
+            .schatten_norm(f64::INFINITY, COMPARISON_TOLERANCE)
+            .unwrap();
+        let spectral_norm: f64 = matrix.spectral_norm(COMPARISON_TOLERANCE, 1000).unwrap();
+
+        assert!((two_norm - matrix.frobenius_norm()).abs() < 1e-6);
+        assert!((infinity_norm - spectral_norm).abs() < 1e-6);
+        assert_eq!(
+            matrix.schatten_norm(0.5, COMPARISON_TOLERANCE).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "Schatten exponent",
+                requirement: "must be at least 1"
+            }
+        );
     }
 
     #[test]
-    fn least_squares_b() {
-        let b: Matrix<f32> = Matrix::square_matrix_from_list(&STANDARD_MATRIX_B.to_vec());
+    fn near_degenerate_determinant_sign() {
+        // The three points are collinear up to a 1e-12 perturbation of the last y coordinate
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[0.1, 0.3, 0.2, 0.6 + 1e-12]);
 
-        let solution_vector: Vec<f32> = STANDARD_MATRIX_B_SOLUTION.to_vec();
+        assert_eq!(matrix.determinant_sign(0.0).unwrap(), Ordering::Greater);
+        assert_eq!(matrix.determinant_sign(1e-9).unwrap(), Ordering::Equal);
+        assert!(Matrix::<f64>::new(2, 3).determinant_sign(0.0).is_err());
+    }
 
-        let b_solution: Vec<f32> = b.least_squares_solution(B_VECTOR.to_vec()).unwrap();
+    #[test]
+    fn solve_jacobi() {
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[10.0, -1.0, 2.0, -1.0, 11.0, -1.0, 2.0, -1.0, 10.0]);
+        let b: Vec<f64> = vec![6.0, 25.0, -11.0];
 
-        for i in 0..solution_vector.len() {
-            assert!((solution_vector[i] - b_solution[i]).abs() < COMPARISON_TOLERANCE);
+        let expected: Vec<f64> = matrix.solve(b.clone()).unwrap();
+        let actual: Vec<f64> = matrix.solve_jacobi(&b, 100, 1e-12).unwrap();
+
+        for i in 0..expected.len() {
+            assert!((expected[i] - actual[i]).abs() < COMPARISON_TOLERANCE);
         }
+        assert!(Matrix::square_matrix_from_list(STANDARD_MATRIX_A)
+            .solve_jacobi(&b, 100, 1e-12)
+            .is_err());
+    }
+
+    #[test]
+    fn solve_gauss_seidel() {
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[10.0, -1.0, 2.0, -1.0, 11.0, -1.0, 2.0, -1.0, 10.0]);
+        let b: Vec<f64> = vec![6.0, 25.0, -11.0];
+
+        // The fewest iterations each method needs to reach the tolerance
+        let jacobi_iterations: usize = (1..100)
+            .find(|iterations| matrix.solve_jacobi(&b, *iterations, 1e-12).is_ok())
+            .unwrap();
+        let gauss_seidel_iterations: usize = (1..100)
+            .find(|iterations| matrix.solve_gauss_seidel(&b, *iterations, 1e-12).is_ok())
+            .unwrap();
+        assert!(gauss_seidel_iterations < jacobi_iterations);
+
+        let expected: Vec<f64> = matrix.solve(b.clone()).unwrap();
+        let actual: Vec<f64> = matrix.solve_gauss_seidel(&b, 100, 1e-12).unwrap();
+        for i in 0..expected.len() {
+            assert!((expected[i] - actual[i]).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn inverse_triangular() {
+        let upper: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            2.0, -1.0, 0.5, 3.0, 0.0, 4.0, 1.5, -2.0, 0.0, 0.0, -0.5, 1.0, 0.0, 0.0, 0.0, 3.0,
+        ]);
+
+        let upper_inverse: Matrix<f64> = upper
+            .inverse_upper_triangular(COMPARISON_TOLERANCE)
+            .unwrap();
+        let lower_inverse: Matrix<f64> = upper
+            .transpose()
+            .inverse_lower_triangular(COMPARISON_TOLERANCE)
+            .unwrap();
+
+        assert_matrix_approx_eq!(
+            upper_inverse.clone() * upper,
+            Matrix::identity_matrix(4),
+            tol = COMPARISON_TOLERANCE
+        );
+        for row in 0..4 {
+            for column in 0..row {
+                assert_eq!(upper_inverse[row][column], 0.0);
+                assert_eq!(lower_inverse[column][row], 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_triangular_errors() {
+        let not_triangular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 0.1, 1.0]);
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 0.0, 1e-12]);
+
+        assert_eq!(
+            not_triangular
+                .inverse_upper_triangular(COMPARISON_TOLERANCE)
+                .unwrap_err(),
+            MatrixError::NotTriangular { row: 1, column: 0 }
+        );
+        assert_eq!(
+            not_triangular
+                .transpose()
+                .inverse_lower_triangular(COMPARISON_TOLERANCE)
+                .unwrap_err(),
+            MatrixError::NotTriangular { row: 0, column: 1 }
+        );
+        assert_eq!(
+            singular
+                .inverse_upper_triangular(COMPARISON_TOLERANCE)
+                .unwrap_err(),
+            MatrixError::SingularDiagonal { index: 1 }
+        );
+        assert_eq!(
+            Matrix::<f64>::new(2, 3)
+                .inverse_lower_triangular(COMPARISON_TOLERANCE)
+                .unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_by_trace() {
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let traceless: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, -1.0]);
+
+        let normalized: Matrix<f64> = b.normalize_by_trace().unwrap();
+
+        assert!((normalized.trace().unwrap() - 1.0).abs() < COMPARISON_TOLERANCE);
+        assert!((normalized[2][0] - 77.1 / 7.7).abs() < COMPARISON_TOLERANCE);
+        assert!(traceless.normalize_by_trace().is_err());
+        assert!(Matrix::<f64>::new(2, 3).normalize_by_trace().is_err());
+    }
+
+    #[test]
+    fn diagonal_fast_paths() {
+        let diagonal: Matrix<f64> = Matrix::from_diagonal(&[2.0, -4.0, 0.5]);
+
+        let expected: Vec<f64> = diagonal.solve(B_VECTOR.to_vec()).unwrap();
+        let actual: Vec<f64> = diagonal.solve_diagonal(B_VECTOR).unwrap();
+        for i in 0..expected.len() {
+            assert!((expected[i] - actual[i]).abs() < COMPARISON_TOLERANCE);
+        }
+        assert_matrix_approx_eq!(
+            diagonal.inverse_diagonal().unwrap(),
+            diagonal.inverse().unwrap(),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert!(!Matrix::square_matrix_from_list(STANDARD_MATRIX_A).is_diagonal());
+    }
+
+    #[test]
+    fn scale_rows_and_columns() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let factors: Vec<f64> = vec![2.0, -1.0, 0.5];
+
+        assert_matrix_approx_eq!(
+            a.scale_rows(&factors).unwrap(),
+            Matrix::from_diagonal(&factors) * a.clone(),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_matrix_approx_eq!(
+            a.scale_columns(&factors).unwrap(),
+            a.clone() * Matrix::from_diagonal(&factors),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            a.scale_rows(&[1.0]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "scale_rows",
+                left: (3, 3),
+                right: (1, 1)
+            }
+        );
+        assert_eq!(
+            a.scale_columns(&[1.0; 4]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "scale_columns",
+                left: (3, 3),
+                right: (4, 1)
+            }
+        );
+    }
+
+    #[test]
+    fn diagonal_zero_entry() {
+        let diagonal: Matrix<f64> = Matrix::from_diagonal(&[2.0, 0.0, 0.5]);
+
+        assert_eq!(
+            diagonal.solve_diagonal(B_VECTOR).unwrap_err(),
+            MatrixError::SingularDiagonal { index: 1 }
+        );
+        assert_eq!(
+            diagonal.inverse_diagonal().unwrap_err(),
+            MatrixError::SingularDiagonal { index: 1 }
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_A)
+                .solve_diagonal(B_VECTOR)
+                .unwrap_err(),
+            MatrixError::NotDiagonal { row: 0, column: 1 }
+        );
+        assert_eq!(
+            diagonal.solve_diagonal(&[1.0]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve_diagonal",
+                left: (3, 3),
+                right: (1, 1)
+            }
+        );
+    }
+
+    #[test]
+    fn stochastic_normalization() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let zero_row: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, -1.0, 2.0, 3.0]);
+
+        for sum in a.row_stochastic().unwrap().row_sums() {
+            assert!((sum - 1.0).abs() < COMPARISON_TOLERANCE);
+        }
+        for sum in a.column_stochastic().unwrap().column_sums() {
+            assert!((sum - 1.0).abs() < COMPARISON_TOLERANCE);
+        }
+        assert!(zero_row.row_stochastic().is_err());
+        assert!(zero_row.column_stochastic().is_ok());
+    }
+
+    #[test]
+    fn closed_form_agrees_with_elimination() {
+        // Diagonally heavy matrices with pseudo-random off-diagonal entries, so they are well-conditioned
+        for size in 2..=4 {
+            let list: Vec<f64> = (0..size * size)
+                .map(|i| {
+                    let noise: f64 = ((i * 37 + 11) % 17) as f64 / 17.0 - 0.5;
+                    if i % (size + 1) == 0 {
+                        5.0 + noise
+                    } else {
+                        noise
+                    }
+                })
+                .collect();
+            let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&list);
+
+            assert_matrix_approx_eq!(
+                matrix.inverse().unwrap(),
+                matrix.inverse_gauss_jordan().unwrap(),
+                tol = COMPARISON_TOLERANCE
+            );
+            let general_determinant: f64 = matrix.reduced_echelon_and_det().1.unwrap();
+            assert!((matrix.determinant() - general_determinant).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn closed_form_exact_inverse() {
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[2.0, 3.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 1.0]);
+
+        assert_matrix_approx_eq!(
+            matrix.inverse().unwrap(),
+            Matrix::square_matrix_from_list(&[1.0, -2.0, 1.0, 0.0, 1.0, -1.0, -1.0, 1.0, 1.0]),
+            tol = 0.0
+        );
+    }
+
+    #[test]
+    fn closed_form_singular() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let nearly_singular: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0 + 1e-12]);
+
+        assert_eq!(a.inverse().unwrap_err(), "Matrix is not invertible");
+        assert!(nearly_singular.inverse().is_ok());
+        assert_eq!(
+            nearly_singular.inverse_with_threshold(1e-9).unwrap_err(),
+            "Matrix is not invertible"
+        );
+    }
+
+    #[test]
+    fn affine_2d_composition_order() {
+        let translation: Matrix<f64> = Matrix::translation_2d(1.0, 0.0);
+        let rotation: Matrix<f64> =
+            Matrix::rotation_about_2d(std::f64::consts::FRAC_PI_2, 0.0, 0.0);
+
+        // The right-hand transform is applied first
+        let translate_then_rotate: [f64; 2] = (rotation.clone() * translation.clone())
+            .transform_point_2d([1.0, 0.0])
+            .unwrap();
+        let rotate_then_translate: [f64; 2] = (translation * rotation)
+            .transform_point_2d([1.0, 0.0])
+            .unwrap();
+
+        assert!((translate_then_rotate[0] - 0.0).abs() < COMPARISON_TOLERANCE);
+        assert!((translate_then_rotate[1] - 2.0).abs() < COMPARISON_TOLERANCE);
+        assert!((rotate_then_translate[0] - 1.0).abs() < COMPARISON_TOLERANCE);
+        assert!((rotate_then_translate[1] - 1.0).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn affine_2d_rotation_about_center() {
+        let rotation: Matrix<f64> = Matrix::rotation_about_2d(0.7, 3.0, -2.0);
+        let center: [f64; 2] = rotation.transform_point_2d([3.0, -2.0]).unwrap();
+        let quarter_turn: [f64; 2] =
+            Matrix::rotation_about_2d(std::f64::consts::FRAC_PI_2, 1.0, 1.0)
+                .transform_point_2d([2.0, 1.0])
+                .unwrap();
+
+        assert!((center[0] - 3.0).abs() < COMPARISON_TOLERANCE);
+        assert!((center[1] + 2.0).abs() < COMPARISON_TOLERANCE);
+        assert!((quarter_turn[0] - 1.0).abs() < COMPARISON_TOLERANCE);
+        assert!((quarter_turn[1] - 2.0).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn affine_2d_scaling_and_shear() {
+        let scaled: [f64; 2] = Matrix::scaling_2d(2.0, -3.0)
+            .transform_point_2d([1.5, 2.0])
+            .unwrap();
+        let sheared: [f64; 2] = Matrix::shear_2d(0.5, 2.0)
+            .transform_point_2d([2.0, 4.0])
+            .unwrap();
+
+        assert_eq!(scaled, [3.0, -6.0]);
+        assert_eq!(sheared, [4.0, 8.0]);
+    }
+
+    #[test]
+    fn affine_2d_homogeneous_divide_and_shape() {
+        let projective: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0]);
+        let vanishing: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, -1.0]);
+
+        assert_eq!(
+            projective.transform_point_2d([4.0, 6.0]).unwrap(),
+            [2.0, 3.0]
+        );
+        assert_eq!(
+            vanishing.transform_point_2d([1.0, 5.0]).unwrap_err(),
+            MatrixError::PointAtInfinity
+        );
+        assert_eq!(
+            Matrix::<f64>::identity_matrix(2)
+                .transform_point_2d([1.0, 1.0])
+                .unwrap_err(),
+            MatrixError::UnexpectedShape {
+                expected: (3, 3),
+                actual: (2, 2)
+            }
+        );
+    }
+
+    #[test]
+    fn stationary_distribution_two_state() {
+        // A two-state chain leaving state 0 with probability a and state 1 with probability b is stationary at
+        // [b / (a + b), a / (a + b)]
+        let chain: Matrix<f64> = Matrix::square_matrix_from_list(&[0.7, 0.3, 0.1, 0.9]);
+        let distribution: Vec<f64> = chain.stationary_distribution(1000, 1e-12).unwrap();
+
+        assert!((distribution[0] - 0.25).abs() < COMPARISON_TOLERANCE);
+        assert!((distribution[1] - 0.75).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn stationary_distribution_periodic_and_invalid() {
+        let periodic: Matrix<f64> = Matrix::square_matrix_from_list(&[0.0, 1.0, 1.0, 0.0]);
+        let distribution: Vec<f64> = periodic.stationary_distribution(1000, 1e-12).unwrap();
+
+        assert!((distribution[0] - 0.5).abs() < COMPARISON_TOLERANCE);
+        assert!((distribution[1] - 0.5).abs() < COMPARISON_TOLERANCE);
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.5, 0.6, 0.5, 0.5])
+                .stationary_distribution(1000, 1e-12)
+                .unwrap_err(),
+            "The matrix is not row-stochastic"
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[1.5, -0.5, 0.5, 0.5])
+                .stationary_distribution(1000, 1e-12)
+                .unwrap_err(),
+            "The matrix has a negative entry"
+        );
+
+        // A loose convergence tolerance doesn't loosen the row sum check
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.5, 0.49, 0.5, 0.5])
+                .stationary_distribution(1000, 0.1)
+                .unwrap_err(),
+            "The matrix is not row-stochastic"
+        );
+        // And a tight one doesn't reject rows whose sums are only off by rounding
+        let rounded: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.1,
+            0.2,
+            0.7, //
+            1.0 / 3.0,
+            1.0 / 3.0,
+            1.0 / 3.0, //
+            0.3,
+            0.3,
+            0.4,
+        ]);
+        assert!(rounded.stationary_distribution(1000, 1e-14).is_ok());
+    }
+
+    #[test]
+    fn perspective_projection() {
+        let projection: Matrix<f64> =
+            Matrix::perspective(std::f64::consts::FRAC_PI_2, 2.0, 1.0, 10.0).unwrap();
+
+        // The clip coordinates of the near plane center have z = -w, so the depth is -1 after the divide
+        let near_center: Vec<f64> = projection.try_mul_vec(&[0.0, 0.0, -1.0, 1.0]).unwrap();
+        let far_center: Vec<f64> = projection.try_mul_vec(&[0.0, 0.0, -10.0, 1.0]).unwrap();
+        let corner: Vec<f64> = projection.try_mul_vec(&[1.0, 1.0, -1.0, 1.0]).unwrap();
+
+        for (actual, expected) in near_center.iter().zip([0.0, 0.0, -1.0, 1.0]) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+        assert!((far_center[2] / far_center[3] - 1.0).abs() < COMPARISON_TOLERANCE);
+        // With a 90 degree field of view the focal length is 1, and x is squeezed by the aspect ratio
+        assert!((corner[0] / corner[3] - 0.5).abs() < COMPARISON_TOLERANCE);
+        assert!((corner[1] / corner[3] - 1.0).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn perspective_invalid_parameters() {
+        let fov: f64 = std::f64::consts::FRAC_PI_2;
+
+        assert_eq!(
+            Matrix::perspective(0.0, 1.0, 1.0, 10.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "field of view",
+                requirement: "must be between 0 and pi"
+            }
+        );
+        assert_eq!(
+            Matrix::perspective(fov, 0.0, 1.0, 10.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "aspect ratio",
+                requirement: "must be positive"
+            }
+        );
+        assert_eq!(
+            Matrix::perspective(fov, 1.0, 10.0, 1.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "clipping planes",
+                requirement: "must satisfy 0 < near < far"
+            }
+        );
+        assert_eq!(
+            Matrix::perspective(fov, 1.0, 0.0, 1.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "clipping planes",
+                requirement: "must satisfy 0 < near < far"
+            }
+        );
+    }
+
+    #[test]
+    fn orthographic_projection() {
+        let projection: Matrix<f64> =
+            Matrix::orthographic(-4.0, 4.0, -1.0, 3.0, 1.0, 11.0).unwrap();
+
+        let near_corner: Vec<f64> = projection.try_mul_vec(&[-4.0, -1.0, -1.0, 1.0]).unwrap();
+        let far_corner: Vec<f64> = projection.try_mul_vec(&[4.0, 3.0, -11.0, 1.0]).unwrap();
+
+        for (actual, expected) in near_corner.iter().zip([-1.0, -1.0, -1.0, 1.0]) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+        for (actual, expected) in far_corner.iter().zip([1.0, 1.0, 1.0, 1.0]) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+        assert_eq!(
+            Matrix::orthographic(1.0, 1.0, -1.0, 1.0, 1.0, 2.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "projection box",
+                requirement: "must have a non-zero width and height"
+            }
+        );
+        assert_eq!(
+            Matrix::orthographic(-1.0, 1.0, -1.0, 1.0, 2.0, 2.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "clipping planes",
+                requirement: "must satisfy near < far"
+            }
+        );
+    }
+
+    #[test]
+    fn look_at_view_matrix() {
+        let eye: [f64; 3] = [3.0, 2.0, -1.0];
+        let target: [f64; 3] = [-1.0, 4.0, 3.0];
+        let view: Matrix<f64> = Matrix::look_at(eye, target, [0.0, 1.0, 0.0]).unwrap();
+
+        let eye_view: Vec<f64> = view.try_mul_vec(&[3.0, 2.0, -1.0, 1.0]).unwrap();
+        let target_view: Vec<f64> = view.try_mul_vec(&[-1.0, 4.0, 3.0, 1.0]).unwrap();
+        for (actual, expected) in eye_view.iter().zip([0.0, 0.0, 0.0, 1.0]) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+        // The target is distance 6 in front of the camera, which looks down -z
+        for (actual, expected) in target_view.iter().zip([0.0, 0.0, -6.0, 1.0]) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+
+        let rotation: Matrix<f64> = view.submatrix(0, 0, 3, 3).unwrap();
+        assert_matrix_approx_eq!(
+            rotation.clone() * rotation.transpose(),
+            Matrix::identity_matrix(3),
+            tol = COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn look_at_degenerate() {
+        assert_eq!(
+            Matrix::<f64>::look_at([1.0, 2.0, 3.0], [1.0, 2.0, 3.0], [0.0, 1.0, 0.0]).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "target",
+                requirement: "must differ from the eye position"
+            }
+        );
+        assert_eq!(
+            Matrix::<f64>::look_at([0.0, 0.0, 0.0], [0.0, -5.0, 0.0], [0.0, 1.0, 0.0]).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "up vector",
+                requirement: "must not be parallel to the view direction"
+            }
+        );
+    }
+
+    #[test]
+    fn quaternion_round_trip() {
+        for seed in 0..20 {
+            let q: [f64; 4] =
+                [0, 1, 2, 3].map(|i| ((seed * 31 + i * 17 + 5) % 23) as f64 / 11.5 - 1.0);
+            let rotation: Matrix<f64> = Matrix::from_quaternion(q).unwrap();
+            let quaternion: [f64; 4] = rotation.to_quaternion().unwrap();
+
+            assert!(quaternion[0] >= 0.0);
+            assert_matrix_approx_eq!(
+                Matrix::from_quaternion(quaternion).unwrap(),
+                rotation,
+                tol = COMPARISON_TOLERANCE
+            );
+        }
+    }
+
+    #[test]
+    fn quaternion_half_turns() {
+        let half: f64 = std::f64::consts::FRAC_1_SQRT_2;
+        // 180 degree rotations have a trace of -1, so w = 0 and the trace-based formula divides by zero
+        for q in [
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+            [0.0, half, half, 0.0],
+            [0.0, 0.0, -half, half],
+            [0.0, -0.6, 0.0, 0.8],
+        ] {
+            let rotation: Matrix<f64> = Matrix::from_quaternion(q).unwrap();
+            let quaternion: [f64; 4] = rotation.to_quaternion().unwrap();
+
+            assert!(quaternion.iter().all(|value| value.is_finite()));
+            assert_matrix_approx_eq!(
+                Matrix::from_quaternion(quaternion).unwrap(),
+                rotation,
+                tol = COMPARISON_TOLERANCE
+            );
+
+            // With w = 0 both q and -q are candidates, so the one with a positive first non-zero component is chosen
+            let leading: f64 = *q.iter().find(|value| **value != 0.0).unwrap();
+            let expected: [f64; 4] = q.map(|value| value * leading.signum());
+            for negated in [q, q.map(|value| -value)] {
+                let quaternion: [f64; 4] = Matrix::from_quaternion(negated)
+                    .unwrap()
+                    .to_quaternion()
+                    .unwrap();
+                for (actual, expected) in quaternion.iter().zip(expected) {
+                    assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quaternion_invalid_input() {
+        let reflection: Matrix<f64> = Matrix::from_diagonal(&[1.0, 1.0, -1.0]);
+
+        assert_eq!(
+            Matrix::<f64>::from_quaternion([0.0; 4]).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "quaternion",
+                requirement: "must not be zero",
+            }
+        );
+        assert_eq!(
+            Matrix::from_diagonal(&[2.0, 1.0, 1.0])
+                .to_quaternion()
+                .unwrap_err(),
+            MatrixError::NotRotation
+        );
+        assert_eq!(
+            reflection.to_quaternion().unwrap_err(),
+            MatrixError::NotRotation
+        );
+        assert_eq!(
+            Matrix::<f64>::identity_matrix(4)
+                .to_quaternion()
+                .unwrap_err(),
+            MatrixError::NotRotation
+        );
+    }
+
+    #[test]
+    fn euler_angles_round_trip() {
+        let orders: [EulerOrder; 6] = [
+            EulerOrder::Xyz,
+            EulerOrder::Xzy,
+            EulerOrder::Yxz,
+            EulerOrder::Yzx,
+            EulerOrder::Zxy,
+            EulerOrder::Zyx,
+        ];
+
+        for order in orders {
+            for seed in 0..10 {
+                let angles: [f64; 3] = [
+                    ((seed * 7 + 3) % 13) as f64 / 13.0 * 6.0 - 3.0,
+                    ((seed * 5 + 1) % 11) as f64 / 11.0 * 3.0 - 1.5,
+                    ((seed * 3 + 2) % 17) as f64 / 17.0 * 6.0 - 3.0,
+                ];
+                let rotation: Matrix<f64> = Matrix::from_euler_angles(order, angles);
+                let extracted: [f64; 3] = rotation.to_euler_angles(order).unwrap();
+
+                for (actual, expected) in extracted.iter().zip(angles) {
+                    assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn euler_angles_gimbal_lock() {
+        let pitch: f64 = std::f64::consts::FRAC_PI_2;
+
+        for order in [EulerOrder::Xyz, EulerOrder::Zyx, EulerOrder::Yzx] {
+            for middle in [pitch, -pitch] {
+                let rotation: Matrix<f64> = Matrix::from_euler_angles(order, [0.4, middle, -1.1]);
+                let extracted: [f64; 3] = rotation.to_euler_angles(order).unwrap();
+
+                assert!(extracted.iter().all(|angle| angle.is_finite()));
+                assert_eq!(extracted[2], 0.0);
+                assert!((extracted[1] - middle).abs() < COMPARISON_TOLERANCE);
+                assert_matrix_approx_eq!(
+                    Matrix::from_euler_angles(order, extracted),
+                    rotation,
+                    tol = COMPARISON_TOLERANCE
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn euler_angles_composition_order() {
+        let (a, b, c): (f64, f64, f64) = (0.3, -0.7, 1.2);
+        let x: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            1.0,
+            0.0,
+            0.0,
+            0.0,
+            a.cos(),
+            -a.sin(),
+            0.0,
+            a.sin(),
+            a.cos(),
+        ]);
+        let y: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            b.cos(),
+            0.0,
+            b.sin(),
+            0.0,
+            1.0,
+            0.0,
+            -b.sin(),
+            0.0,
+            b.cos(),
+        ]);
+        let z: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            c.cos(),
+            -c.sin(),
+            0.0,
+            c.sin(),
+            c.cos(),
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ]);
+
+        assert_matrix_approx_eq!(
+            Matrix::from_euler_angles(EulerOrder::Xyz, [a, b, c]),
+            x.clone() * y.clone() * z.clone(),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_matrix_approx_eq!(
+            Matrix::from_euler_angles(EulerOrder::Zyx, [c, b, a]),
+            z * y * x,
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            Matrix::from_diagonal(&[1.0, 2.0, 1.0])
+                .to_euler_angles(EulerOrder::Xyz)
+                .unwrap_err(),
+            MatrixError::NotRotation
+        );
+    }
+
+    #[test]
+    fn satisfies_characteristic() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(&[1.5, -2.0, 0.25, 3.0]);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        assert!(a.satisfies_characteristic(COMPARISON_TOLERANCE).unwrap());
+        assert!(b.satisfies_characteristic(1e-6).unwrap());
+        assert_eq!(
+            Matrix::<f64>::new(2, 3)
+                .satisfies_characteristic(COMPARISON_TOLERANCE)
+                .unwrap_err(),
+            "The matrix was not square"
+        );
+    }
+
+    /// Returns the largest distance between where the homography sends each src point and the matching dst point
+    fn max_reprojection_error(homography: &Matrix<f64>, src: &[[f64; 2]], dst: &[[f64; 2]]) -> f64 {
+        src.iter()
+            .zip(dst)
+            .map(|(src_point, dst_point)| {
+                let projected: [f64; 2] = homography.transform_point_2d(*src_point).unwrap();
+                (projected[0] - dst_point[0]).hypot(projected[1] - dst_point[1])
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn homography_exact_recovery() {
+        let homography: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.2, 0.1, 5.0, -0.2, 0.9, -3.0, 0.001, 0.002, 1.0]);
+        let src: [[f64; 2]; 4] = [[0.0, 0.0], [100.0, 10.0], [90.0, 120.0], [-5.0, 80.0]];
+        let dst: Vec<[f64; 2]> = src
+            .iter()
+            .map(|point| homography.transform_point_2d(*point).unwrap())
+            .collect();
+
+        assert_matrix_approx_eq!(
+            Matrix::homography_from_points(&src, &dst).unwrap(),
+            homography,
+            tol = COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn homography_badly_scaled_coordinates() {
+        // Large offsets square into an ill-conditioned A^T A without normalization
+        let homography: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.8, -0.05, 250.0, 0.03, 1.1, -400.0, 1e-6, -2e-6, 1.0,
+        ]);
+        let src: Vec<[f64; 2]> = (0..6)
+            .map(|i| {
+                [
+                    20000.0 + (i * 37 % 11) as f64 * 3.0,
+                    15000.0 + (i * 53 % 7) as f64 * 4.0,
+                ]
+            })
+            .collect();
+        let dst: Vec<[f64; 2]> = src
+            .iter()
+            .map(|point| homography.transform_point_2d(*point).unwrap())
+            .collect();
+
+        let estimate: Matrix<f64> = Matrix::homography_from_points(&src, &dst).unwrap();
+
+        assert!(max_reprojection_error(&estimate, &src, &dst) < 1e-6);
+    }
+
+    #[test]
+    fn homography_noisy_points() {
+        let homography: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.9, 0.2, 30.0, -0.1, 1.05, 12.0, 0.0005, -0.0003, 1.0,
+        ]);
+        let src: [[f64; 2]; 8] = [
+            [0.0, 0.0],
+            [200.0, 0.0],
+            [200.0, 150.0],
+            [0.0, 150.0],
+            [100.0, 40.0],
+            [60.0, 110.0],
+            [170.0, 90.0],
+            [30.0, 60.0],
+        ];
+        let noisy_dst: Vec<[f64; 2]> = src
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let [u, v]: [f64; 2] = homography.transform_point_2d(*point).unwrap();
+                let noise: f64 = ((i * 7 % 5) as f64 - 2.0) * 0.01;
+                [u + noise, v - noise]
+            })
+            .collect();
+
+        let estimate: Matrix<f64> = Matrix::homography_from_points(&src, &noisy_dst).unwrap();
+
+        assert!(max_reprojection_error(&estimate, &src, &noisy_dst) < 0.05);
+    }
+
+    #[test]
+    fn homography_invalid_input() {
+        let square: [[f64; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let collinear: [[f64; 2]; 4] = [[0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [0.0, 1.0]];
+
+        assert_eq!(
+            Matrix::homography_from_points(&square[..3], &square[..3]).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "point lists",
+                requirement: "must hold at least 4 points",
+            }
+        );
+        assert_eq!(
+            Matrix::homography_from_points(&square, &square[..3]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "homography_from_points",
+                left: (4, 2),
+                right: (3, 2),
+            }
+        );
+        assert_eq!(
+            Matrix::homography_from_points(&collinear, &square).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "point configuration",
+                requirement: "must not be degenerate",
+            }
+        );
+        assert_eq!(
+            Matrix::homography_from_points(&collinear, &collinear).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "point configuration",
+                requirement: "must not be degenerate",
+            }
+        );
+    }
+
+    #[test]
+    fn low_rank_approx_symmetric() {
+        // 5 u u^T - 2 w w^T for orthogonal u and w, which has rank 2
+        let u: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 0.0, -1.0], 4, 1);
+        let w: Matrix<f64> = Matrix::matrix_from_list(&[2.0, -1.0, 1.0, 0.0], 4, 1);
+        let matrix: Matrix<f64> = u.clone() * u.transpose() * 5.0 - w.clone() * w.transpose() * 2.0;
+
+        let rank_two: Matrix<f64> = matrix.low_rank_approx_symmetric(2, 1000).unwrap();
+        let rank_one: Matrix<f64> = matrix.low_rank_approx_symmetric(1, 1000).unwrap();
+
+        assert_matrix_approx_eq!(rank_two, matrix, tol = 1e-6);
+        assert_matrix_approx_eq!(rank_one, u.clone() * u.transpose() * 5.0, tol = 1e-6);
+        assert_eq!(
+            matrix.low_rank_approx_symmetric(5, 1000).unwrap_err(),
+            "The rank is larger than the size of the matrix"
+        );
+    }
+
+    #[test]
+    fn gaussian_kernel() {
+        let kernel: Matrix<f64> = Matrix::gaussian_kernel(5, 1.3).unwrap();
+        let total: f64 = kernel.row_sums().iter().sum();
+        let delta: Matrix<f64> = Matrix::gaussian_kernel(3, 0.01).unwrap();
+
+        assert!((total - 1.0).abs() < COMPARISON_TOLERANCE);
+        assert_matrix_approx_eq!(kernel.transpose(), kernel, tol = 0.0);
+        for row in 0..5 {
+            for column in 0..5 {
+                assert_eq!(kernel[row][column], kernel[4 - row][4 - column]);
+            }
+        }
+        assert_matrix_approx_eq!(
+            delta,
+            Matrix::square_matrix_from_list(&[0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            Matrix::<f64>::gaussian_kernel(4, 1.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "kernel size",
+                requirement: "must be odd",
+            }
+        );
+        assert_eq!(
+            Matrix::<f64>::gaussian_kernel(3, 0.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "standard deviation",
+                requirement: "must be positive",
+            }
+        );
+    }
+
+    #[test]
+    fn derivative_kernels() {
+        // Increases by 1.5 per column and 0.5 per row
+        let list: Vec<f64> = (0..30)
+            .map(|i| (i % 6) as f64 * 1.5 + (i / 6) as f64 * 0.5)
+            .collect();
+        let image: Matrix<f64> = Matrix::matrix_from_list(&list, 5, 6);
+
+        let x_response: Matrix<f64> = image.convolve(&Matrix::sobel_x()).unwrap();
+        let y_response: Matrix<f64> = image.convolve(&Matrix::sobel_y()).unwrap();
+        let laplacian: Matrix<f64> = image.convolve(&Matrix::laplacian_kernel()).unwrap();
+
+        assert_matrix_approx_eq!(
+            x_response,
+            Matrix::matrix_from_list(&[12.0; 12], 3, 4),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            y_response,
+            Matrix::matrix_from_list(&[4.0; 12], 3, 4),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(laplacian, Matrix::new(3, 4), tol = COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn box_kernel_and_convolve() {
+        let image: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+
+        assert_matrix_approx_eq!(
+            image.convolve(&Matrix::box_kernel(3).unwrap()).unwrap(),
+            Matrix::square_matrix_from_list(&[5.0]),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            Matrix::<f64>::box_kernel(0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "kernel size",
+                requirement: "must be positive",
+            }
+        );
+        assert_eq!(
+            image.convolve(&Matrix::new(4, 1)).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "convolve_2d",
+                left: (3, 3),
+                right: (4, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn convolve_padding_modes() {
+        let image: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        let ones: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0; 9]);
+        let expected: [(Padding, [f64; 9]); 4] = [
+            (
+                Padding::Zero,
+                [12.0, 21.0, 16.0, 27.0, 45.0, 33.0, 24.0, 39.0, 28.0],
+            ),
+            (
+                Padding::Replicate,
+                [21.0, 27.0, 33.0, 39.0, 45.0, 51.0, 57.0, 63.0, 69.0],
+            ),
+            (
+                Padding::Reflect,
+                [33.0, 36.0, 39.0, 42.0, 45.0, 48.0, 51.0, 54.0, 57.0],
+            ),
+            (Padding::Wrap, [45.0; 9]),
+        ];
+
+        for (padding, sums) in expected {
+            assert_matrix_approx_eq!(
+                image
+                    .convolve_2d(&ones, ConvolutionMode::Same, padding)
+                    .unwrap(),
+                Matrix::square_matrix_from_list(&sums),
+                tol = COMPARISON_TOLERANCE
+            );
+        }
+    }
+
+    #[test]
+    fn convolve_versus_correlate() {
+        let image: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        // Correlating with this kernel reads the entry to the right, while convolving flips it to read the left
+        let shift: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+
+        assert_matrix_approx_eq!(
+            image
+                .correlate_2d(&shift, ConvolutionMode::Same, Padding::Replicate)
+                .unwrap(),
+            Matrix::square_matrix_from_list(&[2.0, 3.0, 3.0, 5.0, 6.0, 6.0, 8.0, 9.0, 9.0]),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            image
+                .convolve_2d(&shift, ConvolutionMode::Same, Padding::Reflect)
+                .unwrap(),
+            Matrix::square_matrix_from_list(&[2.0, 1.0, 2.0, 5.0, 4.0, 5.0, 8.0, 7.0, 8.0]),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            Matrix::square_matrix_from_list(&[1.0, 2.0, 3.0, 4.0])
+                .convolve_2d(
+                    &Matrix::square_matrix_from_list(&[1.0; 4]),
+                    ConvolutionMode::Full,
+                    Padding::Zero
+                )
+                .unwrap(),
+            Matrix::square_matrix_from_list(&[1.0, 3.0, 2.0, 4.0, 10.0, 6.0, 3.0, 7.0, 4.0]),
+            tol = 0.0
+        );
+    }
+
+    #[test]
+    fn convolve_wrap_is_circulant() {
+        let signal: [f64; 5] = [1.0, -2.0, 0.5, 4.0, 3.0];
+        let kernel: [f64; 3] = [0.25, 2.0, -1.0];
+
+        // In Same mode y[j] = sum over m of kernel[m] * x[(j + 1 - m) mod 5], a circulant matrix times x
+        let mut circulant: Matrix<f64> = Matrix::square_matrix(5);
+        for j in 0..5 {
+            for (m, weight) in kernel.iter().enumerate() {
+                circulant.set_value(j, (j + 6 - m) % 5, *weight);
+            }
+        }
+
+        let wrapped: Matrix<f64> = Matrix::matrix_from_list(&signal, 1, 5)
+            .convolve_2d(
+                &Matrix::matrix_from_list(&kernel, 1, 3),
+                ConvolutionMode::Same,
+                Padding::Wrap,
+            )
+            .unwrap();
+
+        assert_matrix_approx_eq!(
+            wrapped,
+            Matrix::matrix_from_list(&circulant.try_mul_vec(&signal).unwrap(), 1, 5),
+            tol = COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn padded_round_trip() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+
+        for padding in [
+            Padding::Zero,
+            Padding::Replicate,
+            Padding::Reflect,
+            Padding::Wrap,
+        ] {
+            let padded: Matrix<f64> = matrix.padded(1, 3, 4, 2, padding);
+
+            assert_eq!((padded.rows(), padded.columns()), (6, 9));
+            assert_matrix_approx_eq!(padded.submatrix(1, 4, 2, 3).unwrap(), matrix, tol = 0.0);
+        }
+        // Reflecting past the far edge of a dimension of size 2 bounces back and forth
+        assert_matrix_approx_eq!(
+            Matrix::matrix_from_list(&[1.0, 2.0], 1, 2).padded(0, 0, 3, 3, Padding::Reflect),
+            Matrix::matrix_from_list(&[2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0], 1, 8),
+            tol = 0.0
+        );
+    }
+
+    #[test]
+    fn pooling_non_overlapping() {
+        let image: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            1.0, 3.0, 2.0, 0.0, 4.0, 2.0, 1.0, 5.0, -1.0, 0.0, 7.0, 6.0, 2.0, -3.0, 8.0, 1.0,
+        ]);
+
+        assert_matrix_approx_eq!(
+            image.max_pool(2, 2, 2, 2, false).unwrap(),
+            Matrix::square_matrix_from_list(&[4.0, 5.0, 2.0, 8.0]),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            image.average_pool(2, 2, 2, 2, false).unwrap(),
+            Matrix::square_matrix_from_list(&[2.5, 2.0, -0.5, 5.5]),
+            tol = COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn pooling_overlapping_windows() {
+        let image: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 5.0, 2.0, 4.0, 0.0, 3.0, 7.0, 1.0, 6.0]);
+
+        assert_matrix_approx_eq!(
+            image.max_pool(2, 2, 1, 1, false).unwrap(),
+            Matrix::square_matrix_from_list(&[5.0, 5.0, 7.0, 6.0]),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            image.average_pool(2, 2, 1, 1, false).unwrap(),
+            Matrix::square_matrix_from_list(&[2.5, 2.5, 3.0, 2.5]),
+            tol = COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn pooling_odd_edges() {
+        let image: Matrix<f64> = Matrix::matrix_from_list(
+            &[
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0,
+            ],
+            3,
+            5,
+        );
+
+        // Dropping the overhanging windows leaves floor((3 - 2) / 2) + 1 by floor((5 - 2) / 2) + 1
+        assert_matrix_approx_eq!(
+            image.max_pool(2, 2, 2, 2, false).unwrap(),
+            Matrix::matrix_from_list(&[7.0, 9.0], 1, 2),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            image.average_pool(2, 2, 2, 2, true).unwrap(),
+            Matrix::matrix_from_list(&[4.0, 6.0, 7.5, 11.5, 13.5, 15.0], 2, 3),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            image.max_pool(2, 0, 2, 2, false).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "pool size",
+                requirement: "must be non-zero",
+            }
+        );
+        assert_eq!(
+            image.average_pool(2, 2, 2, 0, true).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "stride",
+                requirement: "must be non-zero",
+            }
+        );
+    }
+
+    #[test]
+    fn max_pool_argmax() {
+        let image: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 9.0, 2.0, 0.0, 4.0, 3.0, 8.0, 1.0, 6.0, 5.0, 2.0, 7.0],
+            3,
+            4,
+        );
+
+        let (pooled, positions) = image.max_pool_with_argmax(2, 2, 2, 2, true).unwrap();
+
+        assert_eq!(positions, vec![vec![(0, 1), (1, 2)], vec![(2, 0), (2, 3)]]);
+        for (row, position_row) in positions.iter().enumerate() {
+            for (column, (source_row, source_column)) in position_row.iter().enumerate() {
+                assert_eq!(pooled[row][column], image[*source_row][*source_column]);
+            }
+        }
+    }
+
+    #[test]
+    fn signum_zero() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(&[-2.5, 0.0, -0.0, 1e-300], 1, 4);
+
+        assert_matrix_approx_eq!(
+            matrix.signum(),
+            Matrix::matrix_from_list(&[-1.0, 0.0, 0.0, 1.0], 1, 4),
+            tol = 0.0
+        );
+    }
+
+    #[test]
+    fn one_hot_regression_recovers_class_means() {
+        let labels: [usize; 7] = [0, 1, 2, 1, 0, 2, 2];
+        let targets: Vec<f64> = vec![1.0, 10.0, -4.0, 14.0, 3.0, -2.0, 0.0];
+        let design: Matrix<f64> = Matrix::one_hot(&labels, 3).unwrap();
+
+        let coefficients: Vec<f64> = design.least_squares_solution(targets).unwrap();
+
+        for (coefficient, mean) in coefficients.iter().zip([2.0, 12.0, -2.0]) {
+            assert!((coefficient - mean).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn pairwise_distance_rows() {
+        let points: Matrix<f64> =
+            Matrix::matrix_from_list(&[0.0, 0.0, 3.0, 4.0, -1.0, 0.0, 3.0, 0.0], 4, 2);
+
+        let distances: Matrix<f64> = points.pairwise_distance_rows().unwrap();
+
+        assert_matrix_approx_eq!(
+            distances,
+            Matrix::square_matrix_from_list(&[
+                0.0,
+                5.0,
+                1.0,
+                3.0,
+                5.0,
+                0.0,
+                32.0_f64.sqrt(),
+                4.0,
+                1.0,
+                32.0_f64.sqrt(),
+                0.0,
+                4.0,
+                3.0,
+                4.0,
+                4.0,
+                0.0,
+            ]),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_matrix_approx_eq!(distances.transpose(), distances, tol = 0.0);
+        assert_eq!(
+            Matrix::<f64>::new(0, 2)
+                .pairwise_distance_rows()
+                .unwrap_err(),
+            "The matrix has no rows"
+        );
+    }
+
+    #[test]
+    fn page_rank_cycle_and_star() {
+        let cycle: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        // Every leaf links to the hub, and the hub links back to every leaf
+        let star: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0,
+        ]);
+
+        let cycle_scores: Vec<f64> = cycle.page_rank(0.85, 1e-12, 1000).unwrap();
+        let star_scores: Vec<f64> = star.page_rank(0.85, 1e-12, 1000).unwrap();
+
+        for score in cycle_scores {
+            assert!((score - 1.0 / 3.0).abs() < COMPARISON_TOLERANCE);
+        }
+        // The hub h and each leaf l satisfy h = 0.15 / 4 + 0.85 * 3l and h + 3l = 1
+        let hub: f64 = (0.15 / 4.0 + 0.85) / 1.85;
+        assert!((star_scores[0] - hub).abs() < COMPARISON_TOLERANCE);
+        assert!((star_scores.iter().sum::<f64>() - 1.0).abs() < COMPARISON_TOLERANCE);
+        assert!(star_scores[1..].iter().all(|score| *score < star_scores[0]));
+    }
+
+    #[test]
+    fn page_rank_dangling_node() {
+        // Node 2 has no outgoing links
+        let graph: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+
+        let scores: Vec<f64> = graph.page_rank(0.85, 1e-12, 1000).unwrap();
+
+        assert!((scores.iter().sum::<f64>() - 1.0).abs() < COMPARISON_TOLERANCE);
+        assert!(scores.iter().all(|score| *score > 0.0));
+        assert!(scores[2] > scores[1] && scores[1] > scores[0]);
+    }
+
+    #[test]
+    fn page_rank_errors() {
+        let cycle: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        let chain: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+
+        assert_eq!(
+            chain.page_rank(0.85, 1e-12, 2).unwrap_err(),
+            MatrixError::NotConverged { iterations: 2 }
+        );
+        assert_eq!(
+            cycle.page_rank(1.0, 1e-12, 1000).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "damping factor",
+                requirement: "must be strictly between 0 and 1",
+            }
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.0, -1.0, 1.0, 0.0])
+                .page_rank(0.85, 1e-12, 1000)
+                .unwrap_err(),
+            MatrixError::NegativeEntry { row: 0, column: 1 }
+        );
+        assert_eq!(
+            Matrix::<f64>::new(2, 3)
+                .page_rank(0.85, 1e-12, 1000)
+                .unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn absorbing_chain_drunkards_walk() {
+        // A walk on 0..=4 moving left or right with equal probability, stopping at either end
+        let mut transition: Matrix<f64> = Matrix::square_matrix(5);
+        transition.set_value(0, 0, 1.0);
+        transition.set_value(4, 4, 1.0);
+        for state in 1..4 {
+            transition.set_value(state, state - 1, 0.5);
+            transition.set_value(state, state + 1, 0.5);
+        }
+
+        let chain = transition.absorbing_chain_analysis(&[0, 4]).unwrap();
+
+        assert_eq!(chain.transient_states(), &[1, 2, 3]);
+        assert_matrix_approx_eq!(
+            chain.fundamental_matrix().clone(),
+            Matrix::square_matrix_from_list(&[1.5, 1.0, 0.5, 1.0, 2.0, 1.0, 0.5, 1.0, 1.5]),
+            tol = COMPARISON_TOLERANCE
+        );
+        // Starting at k, the walk takes k(4 - k) steps and ends at 4 with probability k / 4
+        for (actual, expected) in chain.expected_steps().iter().zip([3.0, 4.0, 3.0]) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+        assert_matrix_approx_eq!(
+            chain.absorption_probabilities().clone(),
+            Matrix::matrix_from_list(&[0.75, 0.25, 0.5, 0.5, 0.25, 0.75], 3, 2),
+            tol = COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn absorbing_chain_probabilities_sum_to_one() {
+        let transition: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.2, 0.3, 0.1, 0.4, 0.0, //
+            0.0, 1.0, 0.0, 0.0, 0.0, //
+            0.5, 0.0, 0.1, 0.2, 0.2, //
+            0.1, 0.0, 0.6, 0.1, 0.2, //
+            0.0, 0.0, 0.0, 0.0, 1.0,
+        ]);
+
+        let chain = transition.absorbing_chain_analysis(&[4, 1]).unwrap();
+
+        assert_eq!(chain.absorbing_states(), &[4, 1]);
+        for sum in chain.absorption_probabilities().row_sums() {
+            assert!((sum - 1.0).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn absorbing_chain_errors() {
+        let transition: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 0.0, 0.0, 0.5, 0.0, 0.5, 0.0, 0.0, 1.0]);
+
+        assert_eq!(
+            transition.absorbing_chain_analysis(&[0, 1]).unwrap_err(),
+            MatrixError::NotAbsorbing { state: 1 }
+        );
+        assert_eq!(
+            transition.absorbing_chain_analysis(&[0, 0]).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "absorbing states",
+                requirement: "must be distinct",
+            }
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.5, 0.6, 0.0, 1.0])
+                .absorbing_chain_analysis(&[1])
+                .unwrap_err(),
+            MatrixError::NotStochastic { row: 0 }
+        );
+        assert_eq!(
+            transition.absorbing_chain_analysis(&[0, 3]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "state",
+                position: 1,
+                index: 3,
+                bound: 3,
+            }
+        );
+        // States 0 and 1 swap forever without reaching state 2
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0])
+                .absorbing_chain_analysis(&[2])
+                .unwrap_err(),
+            MatrixError::NeverAbsorbed
+        );
+    }
+
+    #[test]
+    fn graph_laplacian() {
+        let path: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+        let weighted: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.0, 2.0, 0.5, 0.0, 2.0, 0.0, 1.5, 3.0, 0.5, 1.5, 0.0, 0.25, 0.0, 3.0, 0.25, 0.0,
+        ]);
+
+        let laplacian: Matrix<f64> = path.graph_laplacian().unwrap();
+        let weighted_laplacian: Matrix<f64> = weighted.graph_laplacian().unwrap();
+
+        assert_matrix_approx_eq!(
+            laplacian,
+            Matrix::square_matrix_from_list(&[1.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 1.0]),
+            tol = 0.0
+        );
+        for sum in weighted_laplacian.row_sums() {
+            assert!(sum.abs() < COMPARISON_TOLERANCE);
+        }
+        let (eigenvalues, _) = weighted_laplacian.symmetric_eigen(1e-12, 100).unwrap();
+        assert!(eigenvalues[3].abs() < COMPARISON_TOLERANCE);
+        assert!(eigenvalues[2] > 0.1);
+    }
+
+    #[test]
+    fn normalized_laplacian() {
+        // A path on vertices 0, 1, 2 with vertex 3 isolated
+        let graph: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        ]);
+        let edge: f64 = -std::f64::consts::FRAC_1_SQRT_2;
+
+        assert_matrix_approx_eq!(
+            graph.normalized_laplacian().unwrap(),
+            Matrix::square_matrix_from_list(&[
+                1.0, edge, 0.0, 0.0, edge, 1.0, edge, 0.0, 0.0, edge, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+            ]),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 2.0, 0.0])
+                .normalized_laplacian()
+                .unwrap_err(),
+            MatrixError::NotSymmetric
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0.0, -1.0, -1.0, 0.0])
+                .graph_laplacian()
+                .unwrap_err(),
+            MatrixError::NegativeEntry { row: 0, column: 1 }
+        );
+    }
+
+    #[test]
+    fn incidence_matrix_laplacian() {
+        let edges: [(usize, usize); 5] = [(0, 1), (2, 1), (1, 3), (3, 0), (2, 4)];
+
+        let incidence: Matrix<f64> = Matrix::incidence_from_edges(5, &edges).unwrap();
+        let adjacency: Matrix<f64> = Matrix::adjacency_from_edges(5, &edges, false).unwrap();
+
+        assert_eq!((incidence.rows(), incidence.columns()), (5, 5));
+        assert_matrix_approx_eq!(
+            incidence.clone() * incidence.transpose(),
+            adjacency.graph_laplacian().unwrap(),
+            tol = 0.0
+        );
+    }
+
+    #[test]
+    fn edge_list_errors_and_direction() {
+        let edges: [(usize, usize); 3] = [(0, 1), (1, 2), (2, 2)];
+
+        let directed: Matrix<f64> = Matrix::adjacency_from_edges(3, &edges, true).unwrap();
+        let undirected: Matrix<f64> = Matrix::adjacency_from_edges(3, &edges, false).unwrap();
+
+        assert_matrix_approx_eq!(
+            directed,
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(
+            undirected.clone(),
+            Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0]),
+            tol = 0.0
+        );
+        assert_matrix_approx_eq!(undirected.transpose(), undirected, tol = 0.0);
+        assert_eq!(
+            Matrix::<f64>::incidence_from_edges(3, &edges).unwrap_err(),
+            MatrixError::SelfLoop { vertex: 2 }
+        );
+        assert_eq!(
+            Matrix::<f64>::incidence_from_edges(2, &[(0, 1), (0, 2)]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "vertex",
+                position: 1,
+                index: 2,
+                bound: 2,
+            }
+        );
+        assert_eq!(
+            Matrix::<f64>::adjacency_from_edges(2, &[(3, 0)], true).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "vertex",
+                position: 0,
+                index: 3,
+                bound: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn trace_of_inverse() {
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0]);
+
+        let expected: f64 = b.inverse().unwrap().trace().unwrap();
+
+        assert!((b.trace_of_inverse().unwrap() - expected).abs() < COMPARISON_TOLERANCE);
+        assert_eq!(
+            singular.trace_of_inverse().unwrap_err(),
+            "Matrix is not invertible"
+        );
+    }
+
+    #[test]
+    fn rank_by_pivot_ratio() {
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let wide: Matrix<f64> =
+            Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0], 2, 4);
+        let nearly_singular: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[2.0, 1.0, 0.0, 1.0, 3.0, 1.0, 3.0, 4.0, 1.0 + 1e-13]);
+
+        assert_eq!(b.rank_by_pivot_ratio(1e-9), b.rank());
+        assert_eq!(wide.rank_by_pivot_ratio(1e-9), wide.rank());
+        assert_eq!(nearly_singular.rank(), 3);
+        assert_eq!(nearly_singular.rank_by_pivot_ratio(1e-9), 2);
+        assert_eq!((nearly_singular * 1e12).rank_by_pivot_ratio(1e-9), 2);
+        assert_eq!(Matrix::<f64>::new(3, 3).rank_by_pivot_ratio(1e-9), 0);
+    }
+
+    #[test]
+    fn in_column_space() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        // The columns of A span the plane of vectors whose entries are in arithmetic progression
+        assert!(a
+            .in_column_space(&[1.0, 3.0, 5.0], COMPARISON_TOLERANCE)
+            .unwrap());
+        assert!(!a
+            .in_column_space(&[1.0, 3.0, 6.0], COMPARISON_TOLERANCE)
+            .unwrap());
+        assert_eq!(
+            a.in_column_space(&[1.0, 3.0], COMPARISON_TOLERANCE)
+                .unwrap_err(),
+            "Your b vector is not the correct length!"
+        );
+
+        // Exact elimination would take the rounding error in the last pivot as a third pivot, spanning everything
+        let inexact: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]);
+        assert!(!inexact.in_column_space(&[1.0, 0.0, 0.0], 1e-9).unwrap());
+        assert!(inexact.in_column_space(&[0.3, 0.6, 0.9], 1e-9).unwrap());
+    }
+
+    #[test]
+    fn vector_angle() {
+        let angle = |a: &[f64], b: &[f64]| Matrix::vector_angle(a, b).unwrap();
+
+        assert!(
+            (angle(&[1.0, 0.0], &[0.0, 3.0]) - std::f64::consts::FRAC_PI_2).abs()
+                < COMPARISON_TOLERANCE
+        );
+        assert!(
+            (angle(&[1.0, 2.0, -1.0], &[2.0, 1.0, 4.0]) - std::f64::consts::FRAC_PI_2).abs()
+                < COMPARISON_TOLERANCE
+        );
+        assert!(angle(&[0.1, 0.2, 0.3], &[0.3, 0.6, 0.9]).abs() < COMPARISON_TOLERANCE);
+        assert!(
+            (angle(&[1.0, 1.0], &[-2.0, -2.0]) - std::f64::consts::PI).abs() < COMPARISON_TOLERANCE
+        );
+        assert!(
+            (angle(&[1.0, 0.0], &[1.0, 1.0]) - std::f64::consts::FRAC_PI_4).abs()
+                < COMPARISON_TOLERANCE
+        );
+
+        assert_eq!(
+            Matrix::vector_angle(&[1.0, 2.0], &[1.0, 2.0, 3.0]),
+            Err("The vectors must have the same length")
+        );
+        assert_eq!(
+            Matrix::vector_angle(&[0.0, 0.0], &[1.0, 2.0]),
+            Err("The angle is undefined for a zero vector")
+        );
+    }
+
+    #[test]
+    fn projection_matrix() {
+        let a: Matrix<f64> =
+            Matrix::matrix_from_list(&[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, -1.0], 4, 2);
+        let projector: Matrix<f64> = a.projection_matrix().unwrap();
+
+        assert_matrix_approx_eq!(
+            projector.clone() * projector.clone(),
+            projector,
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_matrix_approx_eq!(projector.transpose(), projector, tol = COMPARISON_TOLERANCE);
+        // The columns are already in the column space, and the trace is the rank
+        assert_matrix_approx_eq!(projector.clone() * a.clone(), a, tol = COMPARISON_TOLERANCE);
+        assert!((projector.trace().unwrap() - 2.0).abs() < COMPARISON_TOLERANCE);
+
+        // Projecting b gives A times the least squares solution
+        let b: Vec<f64> = vec![1.0, -2.0, 0.5, 3.0];
+        let x: Vec<f64> = a.least_squares_solution(b.clone()).unwrap();
+        let projected: Vec<f64> = projector.try_mul_vec(&b).unwrap();
+        let fitted: Vec<f64> = a.try_mul_vec(&x).unwrap();
+        for (value, expected) in projected.iter().zip(&fitted) {
+            assert!((value - expected).abs() < COMPARISON_TOLERANCE);
+        }
+
+        let dependent: Matrix<f64> =
+            Matrix::matrix_from_list(&[1.0, 2.0, 2.0, 4.0, 3.0, 6.0], 3, 2);
+        assert_eq!(
+            dependent.projection_matrix().unwrap_err(),
+            "The columns are linearly dependent, so A^T A is singular"
+        );
+    }
+
+    #[test]
+    fn gram_schmidt() {
+        let a: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+            4,
+            3,
+        );
+        let q: Matrix<f64> = a.gram_schmidt().unwrap();
+
+        assert_eq!((q.rows(), q.columns()), (4, 3));
+        assert_matrix_approx_eq!(
+            q.transpose() * q.clone(),
+            Matrix::identity_matrix(3),
+            tol = COMPARISON_TOLERANCE
+        );
+        // The first column keeps its direction, and the span is unchanged
+        assert!((q[0][0] - 1.0 / 3f64.sqrt()).abs() < COMPARISON_TOLERANCE);
+        assert_matrix_approx_eq!(
+            q.clone() * q.transpose() * a.clone(),
+            a,
+            tol = COMPARISON_TOLERANCE
+        );
+
+        // The third column is the sum of the first two and the fourth is zero, so both are dropped
+        let dependent: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 2.0, 3.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, -1.0, 0.0, 0.0],
+            3,
+            4,
+        );
+        let q: Matrix<f64> = dependent.gram_schmidt().unwrap();
+        assert_eq!(q.columns(), 2);
+        assert_matrix_approx_eq!(
+            q.transpose() * q.clone(),
+            Matrix::identity_matrix(2),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_matrix_approx_eq!(
+            q.clone() * q.transpose() * dependent.clone(),
+            dependent,
+            tol = COMPARISON_TOLERANCE
+        );
+
+        assert_eq!(
+            Matrix::<f64>::new(3, 2).gram_schmidt().unwrap_err(),
+            "The column space only contains the zero vector"
+        );
+    }
+
+    #[test]
+    fn row_echelon() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let echelon: EchelonForm<f64> = a.row_echelon();
+
+        for (pivot_row, pivot_column) in echelon.pivots() {
+            assert_ne!(echelon.matrix()[*pivot_row][*pivot_column], 0.0);
+            for row in pivot_row + 1..3 {
+                assert_eq!(echelon.matrix()[row][*pivot_column], 0.0);
+            }
+        }
+        assert_eq!(echelon.pivots(), &[(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(echelon.permutation(), &[0, 1, 2]);
+        assert_eq!(echelon.rank(), 3);
+        assert!((echelon.determinant().unwrap() - a.determinant()).abs() < COMPARISON_TOLERANCE);
+        assert!(
+            (echelon.determinant().unwrap() - a.reduced_echelon_and_det().1.unwrap()).abs()
+                < COMPARISON_TOLERANCE
+        );
+
+        // A zero in the top left forces a swap, which flips the sign of the determinant but not the pivot product
+        let swapped: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 2.0, 1.0, 3.0, 1.0, 4.0, 1.0, 5.0, 9.0]);
+        let echelon: EchelonForm<f64> = swapped.row_echelon();
+        assert_eq!(echelon.permutation(), &[1, 0, 2]);
+        assert_eq!(echelon.matrix()[0], vec![3.0, 1.0, 4.0]);
+        assert!(
+            (echelon.determinant().unwrap() - swapped.determinant()).abs() < COMPARISON_TOLERANCE
+        );
+        assert!((echelon.pivot_product() + swapped.determinant()).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn row_echelon_rank_deficient() {
+        // The second row is twice the first, and the second column is twice the first
+        let a: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 2.0, 0.0, 3.0, 2.0, 4.0, 0.0, 6.0, 3.0, 6.0, 1.0, 0.0],
+            3,
+            4,
+        );
+        let echelon: EchelonForm<f64> = a.row_echelon();
+
+        assert_eq!(echelon.pivots(), &[(0, 0), (1, 2)]);
+        assert_eq!(echelon.rank(), a.rank());
+        assert_eq!(echelon.permutation(), &[0, 2, 1]);
+        assert_matrix_approx_eq!(
+            echelon.matrix(),
+            Matrix::matrix_from_list(
+                &[1.0, 2.0, 0.0, 3.0, 0.0, 0.0, 1.0, -9.0, 0.0, 0.0, 0.0, 0.0],
+                3,
+                4
+            ),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_eq!(
+            echelon.determinant().unwrap_err(),
+            "The matrix was not square"
+        );
+
+        // The pivot columns agree with the reduced echelon form
+        let reduced: Matrix<f64> = a.reduced_echelon_form();
+        let reduced_pivots: Vec<usize> = (0..3)
+            .filter_map(|row| (0..4).find(|column| reduced[row][*column] != 0.0))
+            .collect();
+        let pivot_columns: Vec<usize> =
+            echelon.pivots().iter().map(|(_, column)| *column).collect();
+        assert_eq!(pivot_columns, reduced_pivots);
+
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        assert_eq!(singular.row_echelon().determinant(), Ok(0.0));
+    }
+
+    #[test]
+    fn echelon_form_solve() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.0, 2.0, -1.0, 3.0, //
+            4.0, 1.0, 0.5, -2.0, //
+            -1.0, 3.0, 2.0, 1.0, //
+            2.0, 0.0, 1.0, 1.5,
+        ]);
+        let echelon: EchelonForm<f64> = a.row_echelon();
+
+        let permutation: Matrix<f64> = Matrix::permutation(echelon.permutation()).unwrap();
+        assert_matrix_approx_eq!(
+            permutation * a.clone(),
+            echelon.lower().clone() * echelon.matrix().clone(),
+            tol = COMPARISON_TOLERANCE
+        );
+
+        for b in [
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![3.5, -2.0, 7.25, 1.0],
+            vec![-10.0, 4.0, 0.0, 2.5],
+        ] {
+            let x: Vec<f64> = echelon.solve(&b).unwrap();
+            let expected: Vec<f64> = a.solve(b).unwrap();
+            for (value, expected_value) in x.iter().zip(&expected) {
+                assert!((value - expected_value).abs() < COMPARISON_TOLERANCE);
+            }
+        }
+        assert_eq!(
+            echelon.solve(&[1.0]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve",
+                left: (4, 4),
+                right: (1, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn echelon_form_solve_rank_deficient() {
+        // The third row is the sum of the first two, and the second column is twice the first
+        let a: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 2.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 1.0],
+            3,
+            4,
+        );
+        let echelon: EchelonForm<f64> = a.row_echelon();
+
+        // Free variables are zero, matching the particular solution of solve_general
+        let b: Vec<f64> = vec![3.0, -1.0, 2.0];
+        let x: Vec<f64> = echelon.solve(&b).unwrap();
+        assert_eq!(x, a.solve_general(&b).unwrap().0);
+        assert_eq!(a.try_mul_vec(&x).unwrap(), b);
+
+        assert_eq!(
+            echelon.solve(&[3.0, -1.0, 5.0]).unwrap_err(),
+            MatrixError::Inconsistent
+        );
+        assert!(a.solve_general(&[3.0, -1.0, 5.0]).is_err());
+    }
+
+    #[test]
+    fn row_echelon_with_tolerance() {
+        // Rank 2, but the last pivot of exact elimination is rounding error
+        let a: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]);
+        assert_eq!(a.row_echelon().rank(), 3);
+
+        let echelon: EchelonForm<f64> = a.row_echelon_with_tolerance(1e-12);
+        assert_eq!(echelon.rank(), 2);
+        assert_eq!(echelon.pivots(), &[(0, 0), (1, 1)]);
+        assert_eq!(echelon.matrix()[2][2], 0.0);
+        assert_eq!(echelon.determinant(), Ok(0.0));
+        assert_eq!(
+            echelon.solve(&[1.0, 0.0, 0.0]).unwrap_err(),
+            MatrixError::Inconsistent
+        );
+
+        // The first column is consistent, up to rounding in the leftover row
+        let x: Vec<f64> = echelon.solve(&[0.1, 0.4, 0.7]).unwrap();
+        let product: Vec<f64> = a.try_mul_vec(&x).unwrap();
+        for (value, expected) in product.iter().zip([0.1, 0.4, 0.7]) {
+            assert!((value - expected).abs() < COMPARISON_TOLERANCE);
+        }
+
+        assert_eq!(a.log_determinant(), Ok((0.0, f64::NEG_INFINITY)));
+        assert_eq!(
+            a.solve_with_diagnostics(&[1.0, 0.0, 0.0]).unwrap_err(),
+            MatrixError::Singular
+        );
+    }
+
+    #[test]
+    fn invert_in_place() {
+        for list in [
+            STANDARD_MATRIX_B,
+            &[2.0, -1.0, 0.0, 3.0],
+            &[0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 4.0, -3.0, 8.0],
+            &[
+                4.0, 1.0, 0.0, 2.0, 1.0, 3.0, 1.0, 0.0, 0.0, 1.0, 5.0, 1.0, 2.0, 0.0, 1.0, 6.0,
+                1.0, 1.0, 1.0, 1.0, 3.0, 2.0, 0.0, 1.0, 7.0,
+            ],
+        ] {
+            let original: Matrix<f64> = Matrix::square_matrix_from_list(list);
+            let mut inverted: Matrix<f64> = original.clone();
+            inverted.invert_in_place().unwrap();
+            let identity: Matrix<f64> = Matrix::identity_matrix(original.rows());
+            assert_matrix_approx_eq!(
+                inverted.clone() * original.clone(),
+                identity,
+                tol = COMPARISON_TOLERANCE
+            );
+            assert_matrix_approx_eq!(original * inverted, identity, tol = COMPARISON_TOLERANCE);
+        }
+
+        let mut singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0]);
+        assert!(singular.invert_in_place().is_err());
+        assert_matrix_eq!(
+            singular,
+            Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0])
+        );
+
+        // Singularity only shows up at the last step, after a row swap, so two steps are reversed
+        let singular_list: &[f64] = &[0.0, 1.0, 2.0, 1.0, 2.0, 3.0, 2.0, 3.0, 4.0];
+        let mut singular: Matrix<f64> = Matrix::square_matrix_from_list(singular_list);
+        assert_eq!(
+            singular.invert_in_place().unwrap_err(),
+            "Matrix is not invertible"
+        );
+        assert_matrix_eq!(singular, Matrix::square_matrix_from_list(singular_list));
+
+        let mut rectangular: Matrix<f64> = Matrix::new(2, 3);
+        assert_eq!(
+            rectangular.invert_in_place().unwrap_err(),
+            "The matrix was not square"
+        );
+        assert_matrix_eq!(rectangular, Matrix::new(2, 3));
+    }
+
+    #[test]
+    fn solve_with_diagnostics_well_conditioned() {
+        let a: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[4.0, 1.0, -1.0, 2.0, 5.0, 1.0, -1.0, 1.0, 6.0]);
+        let b: Vec<f64> = vec![3.0, -2.0, 7.5];
+        let report: SolveReport<f64> = a.solve_with_diagnostics(&b).unwrap();
+
+        let expected: Vec<f64> = a.solve(b.clone()).unwrap();
+        for (value, expected_value) in report.solution().iter().zip(&expected) {
+            assert!((value - expected_value).abs() < COMPARISON_TOLERANCE);
+        }
+        assert!(report.residual_norm() < 1e-14);
+        assert!(!report.is_ill_conditioned());
+        assert_eq!(report.smallest_pivot(), 4.0);
+
+        // The condition number from the explicit inverse
+        let inverse: Matrix<f64> = a.inverse().unwrap();
+        let one_norm = |m: &Matrix<f64>| {
+            (0..3)
+                .map(|column| (0..3).map(|row| m[row][column].abs()).sum::<f64>())
+                .fold(0.0, f64::max)
+        };
+        assert!(
+            (report.condition_estimate() - one_norm(&a) * one_norm(&inverse)).abs()
+                < COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn solve_with_diagnostics_hilbert() {
+        let size: usize = 10;
+        let mut hilbert: Matrix<f64> = Matrix::new(size, size);
+        for row in 0..size {
+            for column in 0..size {
+                hilbert.set_value(row, column, 1.0 / (row + column + 1) as f64);
+            }
+        }
+        let b: Vec<f64> = hilbert.row_sums();
+        let report: SolveReport<f64> = hilbert.solve_with_diagnostics(&b).unwrap();
+
+        // The condition number of the 10x10 Hilbert matrix is about 3.5e13 in the 1-norm
+        assert!(report.is_ill_conditioned());
+        assert!(report.condition_estimate() > 1e12);
+        assert!(report.smallest_pivot() < 1e-10);
+
+        // The residual is checked against Ax - b computed independently of the report
+        let mut residual_squared: f64 = 0.0;
+        for row in 0..size {
+            let ax: f64 = (0..size)
+                .map(|column| hilbert[row][column] * report.solution()[column])
+                .sum();
+            residual_squared += (ax - b[row]).powi(2);
+        }
+        assert!((report.residual_norm() - residual_squared.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn solve_with_diagnostics_errors() {
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(
+            singular.solve_with_diagnostics(&[1.0, 2.0]).unwrap_err(),
+            MatrixError::Singular
+        );
+        assert_eq!(
+            Matrix::<f64>::new(2, 3)
+                .solve_with_diagnostics(&[1.0, 2.0])
+                .unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3,
+            }
+        );
+        assert_eq!(
+            Matrix::<f64>::identity_matrix(2)
+                .solve_with_diagnostics(&[1.0])
+                .unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve_with_diagnostics",
+                left: (2, 2),
+                right: (1, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn log_determinant() {
+        for list in [
+            STANDARD_MATRIX_B,
+            &[0.0, 2.0, 1.0, 3.0, -1.0, 4.0, 1.0, 5.0, -9.0],
+            &[-2.0, 1.0, 0.5, 3.0],
+        ] {
+            let a: Matrix<f64> = Matrix::square_matrix_from_list(list);
+            let (sign, log_magnitude): (f64, f64) = a.log_determinant().unwrap();
+            let determinant: f64 = a.determinant();
+            assert_eq!(sign, determinant.signum());
+            assert!(
+                (sign * log_magnitude.exp() - determinant).abs()
+                    < COMPARISON_TOLERANCE * determinant.abs()
+            );
+        }
+
+        // The determinant is -1e600, far past f64::MAX
+        let mut diagonal: Vec<f64> = vec![1e10; 60];
+        diagonal[7] = -1e10;
+        let large: Matrix<f64> = Matrix::from_diagonal(&diagonal);
+        assert!(large.determinant().is_infinite());
+        let (sign, log_magnitude): (f64, f64) = large.log_determinant().unwrap();
+        assert_eq!(sign, -1.0);
+        assert!((log_magnitude - 600.0 * 10f64.ln()).abs() < COMPARISON_TOLERANCE);
+
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        assert_eq!(singular.log_determinant(), Ok((0.0, f64::NEG_INFINITY)));
+        assert_eq!(
+            Matrix::<f64>::new(2, 3).log_determinant().unwrap_err(),
+            "The matrix was not square"
+        );
+    }
+
+    #[test]
+    fn solve_refined() {
+        // A Vandermonde system on the nodes 1, 1.37, ..., 4.33, which loses several digits to conditioning
+        let size: usize = 10;
+        let mut vandermonde: Matrix<f64> = Matrix::new(size, size);
+        for row in 0..size {
+            for column in 0..size {
+                vandermonde.set_value(row, column, (1.0 + 0.37 * row as f64).powi(column as i32));
+            }
+        }
+        let b: Vec<f64> = (0..size).map(|i| (i * 7 % 5) as f64 - 1.3).collect();
+        let residual = |x: &[f64]| {
+            let product: Vec<f64> = vandermonde.try_mul_vec(x).unwrap();
+            product
+                .iter()
+                .zip(&b)
+                .map(|(ax, b)| (ax - b).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        let unrefined: Vec<f64> = vandermonde.solve_refined(&b, 0, 0.0).unwrap();
+        let refined: Vec<f64> = vandermonde.solve_refined(&b, 5, 0.0).unwrap();
+        assert_eq!(
+            unrefined,
+            vandermonde.factorize().unwrap().solve(&b).unwrap()
+        );
+        assert!(residual(&refined) < residual(&unrefined) / 2.0);
+
+        // A well-conditioned system is already accurate after a single refinement
+        let a: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[4.0, 1.0, -1.0, 2.0, 5.0, 1.0, -1.0, 1.0, 6.0]);
+        let b: Vec<f64> = vec![3.0, -2.0, 7.5];
+        let once: Vec<f64> = a.solve_refined(&b, 1, 1e-14).unwrap();
+        assert_eq!(once, a.solve_refined(&b, 10, 1e-14).unwrap());
+        let product: Vec<f64> = a.try_mul_vec(&once).unwrap();
+        assert!(product.iter().zip(&b).all(|(ax, b)| (ax - b).abs() < 1e-14));
+
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(
+            singular.solve_refined(&[1.0, 2.0], 3, 1e-12).unwrap_err(),
+            MatrixError::Singular
+        );
+        assert_eq!(
+            a.solve_refined(&[1.0], 3, 1e-12).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve_refined",
+                left: (3, 3),
+                right: (1, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn weighted_least_squares() {
+        // Fitting a line to five points
+        let a: Matrix<f64> =
+            Matrix::matrix_from_list(&[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0], 5, 2);
+        let b: Vec<f64> = vec![1.1, 2.9, 5.2, 7.1, 8.8];
+
+        let unweighted: Vec<f64> = a.least_squares_solution(b.clone()).unwrap();
+        for weights in [vec![1.0; 5], vec![3.5; 5]] {
+            let weighted: Vec<f64> = a.weighted_least_squares(&b, &weights).unwrap();
+            for (value, expected) in weighted.iter().zip(&unweighted) {
+                assert!((value - expected).abs() < COMPARISON_TOLERANCE);
+            }
+        }
+
+        // With zero weight on the last two points, the line goes exactly through the first two
+        let weighted: Vec<f64> = a
+            .weighted_least_squares(&b, &[1.0, 1.0, 0.0, 0.0, 0.0])
+            .unwrap();
+        assert!((weighted[0] - 1.1).abs() < COMPARISON_TOLERANCE);
+        assert!((weighted[1] - 1.8).abs() < COMPARISON_TOLERANCE);
+
+        assert_eq!(
+            a.weighted_least_squares(&b, &[1.0; 4]).unwrap_err(),
+            "There must be one weight for each row"
+        );
+        assert_eq!(
+            a.weighted_least_squares(&b[..4], &[1.0; 5]).unwrap_err(),
+            "Your b vector is not the correct length!"
+        );
+        assert_eq!(
+            a.weighted_least_squares(&b, &[1.0, 1.0, -1.0, 1.0, 1.0])
+                .unwrap_err(),
+            "The weights must not be negative"
+        );
+    }
+
+    #[test]
+    fn solve_equilibrated_badly_scaled() {
+        // The first row is 2^-56 x0 + x1 = 1 multiplied through by 2^66, so partial pivoting wrongly picks it for the
+        // first pivot. The exact solution is within 1e-16 of [1, 1, 1]
+        let scale: f64 = 2f64.powi(66);
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            2f64.powi(-56) * scale,
+            scale,
+            0.0,
+            1.0,
+            1.0,
+            1.0,
+            0.0,
+            1.0,
+            2.0,
+        ]);
+        let b: Vec<f64> = vec![scale, 3.0, 3.0];
+
+        let plain: Vec<f64> = a.factorize().unwrap().solve(&b).unwrap();
+        assert!((plain[0] - 1.0).abs() > 0.5);
+        let equilibrated: Vec<f64> = a.solve_equilibrated(&b).unwrap();
+        for value in equilibrated {
+            assert!((value - 1.0).abs() < COMPARISON_TOLERANCE);
+        }
+
+        let (scaled, row_scales, column_scales): (Matrix<f64>, Vec<f64>, Vec<f64>) =
+            a.equilibrate();
+        for scale in row_scales.iter().chain(&column_scales) {
+            assert_eq!(2f64.powi(scale.log2() as i32), *scale);
+        }
+        assert_eq!(row_scales, vec![2f64.powi(-66), 1.0, 0.5]);
+        assert_eq!(column_scales, vec![1.0; 3]);
+        assert_eq!(scaled[0][1], 1.0);
+    }
+
+    #[test]
+    fn equilibrate_zero_rows_and_well_scaled_round_trip() {
+        let a: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[0.0, 0.0, 0.0, 3.0, 0.0, 100.0, 0.25, 0.0, -7.0]);
+        let (scaled, row_scales, column_scales): (Matrix<f64>, Vec<f64>, Vec<f64>) =
+            a.equilibrate();
+        assert_eq!(row_scales, vec![1.0, 2f64.powi(-7), 2f64.powi(-3)]);
+        assert_eq!(column_scales, vec![32.0, 1.0, 1.0]);
+        assert!(scaled.frobenius_norm().is_finite());
+        assert_eq!(scaled[0], vec![0.0; 3]);
+
+        // On a well-scaled system the answer doesn't change
+        let well_scaled: Matrix<f64> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+        let b: Vec<f64> = vec![1.0, -2.0, 0.5];
+        let expected: Vec<f64> = well_scaled.solve(b.clone()).unwrap();
+        for (value, expected_value) in well_scaled
+            .solve_equilibrated(&b)
+            .unwrap()
+            .iter()
+            .zip(&expected)
+        {
+            assert!((value - expected_value).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn ridge_least_squares() {
+        // The two columns differ by only 1e-8, so A^T A is singular to working precision
+        let near_singular: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 1.0, 1.0, 1.0 + 1e-8, 1.0, 1.0 - 1e-8, 2.0, 2.0],
+            4,
+            2,
+        );
+        let b: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(near_singular.least_squares_solution(b.clone()).is_err());
+
+        let lambda: f64 = 1e-3;
+        let x: Vec<f64> = near_singular.ridge_least_squares(&b, lambda).unwrap();
+        // The regularization splits the weight almost evenly between the nearly identical columns
+        assert!((x[0] - x[1]).abs() < 1e-4);
+        // (A^T A + lambda I) x = A^T b
+        let transpose: Matrix<f64> = near_singular.transpose();
+        let normal: Vec<f64> = (transpose.clone() * near_singular.clone())
+            .try_mul_vec(&x)
+            .unwrap();
+        let normal_b: Vec<f64> = transpose.try_mul_vec(&b).unwrap();
+        for i in 0..2 {
+            assert!((normal[i] + lambda * x[i] - normal_b[i]).abs() < 1e-6);
+        }
+
+        // With no regularization it's ordinary least squares
+        let a: Matrix<f64> =
+            Matrix::matrix_from_list(&[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0], 4, 2);
+        let unregularized: Vec<f64> = a.ridge_least_squares(&b, 0.0).unwrap();
+        for (value, expected) in unregularized
+            .iter()
+            .zip(&a.least_squares_solution(b.clone()).unwrap())
+        {
+            assert!((value - expected).abs() < COMPARISON_TOLERANCE);
+        }
+
+        assert_eq!(
+            a.ridge_least_squares(&b, -1.0).unwrap_err(),
+            "The regularization parameter must not be negative"
+        );
+        assert_eq!(
+            a.ridge_least_squares(&b[..3], 1.0).unwrap_err(),
+            "Your b vector is not the correct length!"
+        );
+    }
+
+    #[test]
+    fn pivoted_cholesky_recovers_rank() {
+        let factor: Matrix<f64> = Matrix::matrix_from_list(
+            &[
+                1.0, 2.0, 0.0, //
+                -1.0, 0.5, 3.0, //
+                2.0, 1.0, 1.0, //
+                0.0, -2.0, 4.0, //
+                3.0, 0.0, -1.0, //
+                1.5, 1.0, 2.0,
+            ],
+            6,
+            3,
+        );
+        let matrix: Matrix<f64> = factor.clone() * factor.transpose();
+
+        let cholesky = matrix.pivoted_cholesky(1e-10).unwrap();
+
+        assert_eq!(cholesky.rank(), 3);
+        assert_eq!(cholesky.lower().rows(), 6);
+        assert_eq!(cholesky.low_rank_factor().columns(), 3);
+        let mut sorted: Vec<usize> = cholesky.permutation().to_vec();
+        sorted.sort();
+        assert_eq!(sorted, vec![0, 1, 2, 3, 4, 5]);
+        assert_matrix_approx_eq!(cholesky.reconstruct(), matrix, tol = 1e-10);
+        // Pivoting on the largest diagonal keeps L's diagonal non-increasing
+        for column in 1..3 {
+            assert!(cholesky.lower()[column][column] <= cholesky.lower()[column - 1][column - 1]);
+        }
+    }
+
+    #[test]
+    fn pivoted_cholesky_matches_cholesky() {
+        let matrix: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[4.0, 2.0, 0.4, 2.0, 10.0, 1.0, 0.4, 1.0, 6.0]);
+
+        let cholesky = matrix.pivoted_cholesky(1e-12).unwrap();
+
+        assert_eq!(cholesky.rank(), 3);
+        assert_eq!(cholesky.permutation(), &[1, 2, 0]);
+        // With the rows and columns reordered the same way, plain Cholesky gives the same factor
+        let permutation: &[usize] = cholesky.permutation();
+        let mut permuted: Matrix<f64> = Matrix::square_matrix(3);
+        for row in 0..3 {
+            for column in 0..3 {
+                permuted.set_value(row, column, matrix[permutation[row]][permutation[column]]);
+            }
+        }
+        assert_matrix_approx_eq!(
+            cholesky.lower().clone(),
+            permuted.cholesky().unwrap(),
+            tol = COMPARISON_TOLERANCE
+        );
+        assert_matrix_approx_eq!(cholesky.reconstruct(), matrix, tol = COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn pivoted_cholesky_errors() {
+        let indefinite: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 1.0]);
+        assert_eq!(
+            indefinite.pivoted_cholesky(1e-10).unwrap_err(),
+            MatrixError::NotPositiveSemiDefinite
+        );
+
+        let negative: Matrix<f64> = Matrix::square_matrix_from_list(&[0.0, 0.0, 0.0, -1.0]);
+        assert_eq!(
+            negative.pivoted_cholesky(1e-10).unwrap_err(),
+            MatrixError::NotPositiveSemiDefinite
+        );
+
+        let asymmetric: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 0.0, 1.0]);
+        assert_eq!(
+            asymmetric.pivoted_cholesky(1e-10).unwrap_err(),
+            MatrixError::NotSymmetric
+        );
+
+        // NaN can't be ordered when choosing a pivot, so it's rejected rather than panicking
+        for bad in [f64::NAN, f64::INFINITY] {
+            let non_finite: Matrix<f64> = Matrix::square_matrix_from_list(&[2.0, 0.0, 0.0, bad]);
+            assert_eq!(
+                non_finite.pivoted_cholesky(1e-10).unwrap_err(),
+                MatrixError::NonFinite { row: 1, column: 1 }
+            );
+        }
+
+        // A zero matrix has rank zero rather than failing
+        let zero: Matrix<f64> = Matrix::square_matrix(3);
+        assert_eq!(zero.pivoted_cholesky(1e-10).unwrap().rank(), 0);
+    }
+
+    #[test]
+    fn nearest_orthogonal_recovers_rotation() {
+        let rotation: Matrix<f64> = Matrix::from_euler_angles(EulerOrder::Zyx, [0.3, -0.7, 1.1]);
+        let noise: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            0.002, -0.001, 0.003, //
+            0.001, 0.002, -0.002, //
+            -0.003, 0.001, 0.001,
+        ]);
+        let perturbed: Matrix<f64> = rotation.clone() + noise;
+
+        let orthogonal: Matrix<f64> = perturbed.nearest_orthogonal().unwrap();
+
+        assert_matrix_approx_eq!(
+            orthogonal.transpose() * orthogonal.clone(),
+            Matrix::identity_matrix(3),
+            tol = 1e-12
+        );
+        assert!((orthogonal.determinant() - 1.0).abs() < 1e-12);
+        assert!((orthogonal.clone() - rotation.clone()).frobenius_norm() < 0.01);
+        // The remaining polar factor Q^T A is symmetric
+        assert!((orthogonal.transpose() * perturbed).is_symmetric(1e-12));
+        // An orthogonal matrix is its own nearest orthogonal matrix
+        assert_matrix_approx_eq!(
+            rotation.nearest_orthogonal().unwrap(),
+            rotation,
+            tol = 1e-12
+        );
+    }
+
+    #[test]
+    fn nearest_orthogonal_errors() {
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(
+            singular.nearest_orthogonal().unwrap_err(),
+            "Matrix is not invertible"
+        );
+
+        let rectangular: Matrix<f64> = Matrix::new(2, 3);
+        assert_eq!(
+            rectangular.nearest_orthogonal().unwrap_err(),
+            "The matrix was not square"
+        );
+    }
+
+    #[test]
+    fn ldlt_reconstructs_and_solves() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            4.0, 2.0, -2.0, 1.0, //
+            2.0, -3.0, 1.0, 0.5, //
+            -2.0, 1.0, 5.0, -1.0, //
+            1.0, 0.5, -1.0, -2.0,
+        ]);
+
+        let (lower, diagonal) = matrix.ldlt().unwrap();
+
+        for row in 0..4 {
+            assert_eq!(lower[row][row], 1.0);
+            for column in row + 1..4 {
+                assert_eq!(lower[row][column], 0.0);
+            }
+        }
+        let mut diagonal_matrix: Matrix<f64> = Matrix::square_matrix(4);
+        for (index, value) in diagonal.iter().enumerate() {
+            diagonal_matrix.set_value(index, index, *value);
+        }
+        assert_matrix_approx_eq!(
+            lower.clone() * diagonal_matrix * lower.transpose(),
+            matrix.clone(),
+            tol = COMPARISON_TOLERANCE
+        );
+
+        let b: Vec<f64> = vec![1.0, -2.0, 0.5, 3.0];
+        let expected: Vec<f64> = matrix.solve(b.clone()).unwrap();
+        for (actual, expected) in matrix.ldlt_solve(&b).unwrap().iter().zip(&expected) {
+            assert!((actual - expected).abs() < COMPARISON_TOLERANCE);
+        }
+        assert!(
+            (matrix.ldlt_determinant().unwrap() - matrix.determinant()).abs()
+                < COMPARISON_TOLERANCE
+        );
+    }
+
+    #[test]
+    fn ldlt_inertia() {
+        // A KKT matrix [A B^T; B 0] with A positive-definite and B of full rank has inertia (n, m, 0)
+        let kkt: Matrix<f64> = Matrix::square_matrix_from_list(&[
+            2.0, 0.0, 1.0, //
+            0.0, 2.0, 1.0, //
+            1.0, 1.0, 0.0,
+        ]);
+        assert_eq!(kkt.inertia().unwrap(), (2, 1, 0));
+
+        // The eigenvalues are 2 and 0
+        let singular: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(singular.inertia().unwrap(), (1, 0, 1));
+        assert_eq!(singular.ldlt_determinant().unwrap(), 0.0);
+        assert_eq!(
+            singular.ldlt_solve(&[1.0, 1.0]).unwrap_err(),
+            MatrixError::Singular
+        );
+        assert_eq!(
+            singular.ldlt_solve(&[1.0]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "ldlt_solve",
+                left: (2, 2),
+                right: (1, 1),
+            }
+        );
+
+        // The eigenvalues are 4, -1 and -3
+        let rotation: Matrix<f64> = Matrix::from_euler_angles(EulerOrder::Xyz, [0.4, 1.2, -0.6]);
+        let mut eigenvalues: Matrix<f64> = Matrix::square_matrix(3);
+        eigenvalues.set_value(0, 0, 4.0);
+        eigenvalues.set_value(1, 1, -1.0);
+        eigenvalues.set_value(2, 2, -3.0);
+        let indefinite: Matrix<f64> = rotation.clone() * eigenvalues * rotation.transpose();
+        assert_eq!(indefinite.inertia().unwrap(), (1, 2, 0));
+    }
+
+    #[test]
+    fn ldlt_errors() {
+        let asymmetric: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, 0.0, 1.0]);
+        assert_eq!(asymmetric.ldlt().unwrap_err(), MatrixError::NotSymmetric);
+
+        // Needs a 2x2 pivot
+        let swap: Matrix<f64> = Matrix::square_matrix_from_list(&[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(
+            swap.ldlt().unwrap_err(),
+            MatrixError::ZeroPivot { index: 0 }
+        );
+    }
+
+    #[test]
+    fn solve_sylvester_reconstructs_c() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 2.0, -1.0, 3.0]);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(&[4.0, 0.5, 1.0, -2.0]);
+        let c: Matrix<f64> = Matrix::square_matrix_from_list(&[3.0, -1.0, 0.0, 7.0]);
+
+        let x: Matrix<f64> = Matrix::solve_sylvester(&a, &b, &c).unwrap();
+
+        assert_matrix_approx_eq!(a * x.clone() + x * b, c, tol = COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn solve_sylvester_rectangular_and_errors() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(&[2.0, 1.0, 0.0, 3.0]);
+        let b: Matrix<f64> =
+            Matrix::square_matrix_from_list(&[1.0, 0.0, 2.0, 1.0, 5.0, 0.0, 0.0, 1.0, 4.0]);
+        let c: Matrix<f64> = Matrix::matrix_from_list(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+
+        let x: Matrix<f64> = Matrix::solve_sylvester(&a, &b, &c).unwrap();
+        assert_matrix_approx_eq!(
+            a.clone() * x.clone() + x * b.clone(),
+            c,
+            tol = COMPARISON_TOLERANCE
+        );
+
+        assert_eq!(
+            Matrix::solve_sylvester(&a, &b, &Matrix::new(3, 2)).unwrap_err(),
+            "C must have the rows of A and the columns of B"
+        );
+        assert_eq!(
+            Matrix::solve_sylvester(&Matrix::new(2, 3), &b, &c).unwrap_err(),
+            "Both matrices must be square"
+        );
+        // A and -A share every eigenvalue, and I is not in the range of X -> AX - XA
+        assert_eq!(
+            Matrix::solve_sylvester(&a, &(a.clone() * -1.0), &Matrix::identity_matrix(2))
+                .unwrap_err(),
+            "The system was inconsistent and there is no solution for b."
+        );
+    }
+
+    #[test]
+    fn qr_column_pivoted_factors() {
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(
+            &[
+                1.0, -2.0, 0.5, 4.0, //
+                3.0, 1.0, -1.0, 0.0, //
+                0.0, 2.0, 6.0, -1.0, //
+                -2.0, 0.5, 1.0, 2.0, //
+                1.5, -1.0, 0.0, 3.0,
+            ],
+            5,
+            4,
+        );
+
+        let qr = matrix.qr_column_pivoted(1e-12).unwrap();
+
+        assert_eq!(qr.q().rows(), 5);
+        assert_eq!(qr.q().columns(), 4);
+        assert_eq!(qr.rank(1e-12), 4);
+        assert_matrix_approx_eq!(
+            qr.q().transpose() * qr.q().clone(),
+            Matrix::identity_matrix(4),
+            tol = COMPARISON_TOLERANCE
+        );
+        let mut permuted: Matrix<f64> = Matrix::new(5, 4);
+        for (column, original) in qr.permutation().iter().enumerate() {
+            for row in 0..5 {
+                permuted.set_value(row, column, matrix[row][*original]);
+            }
+        }
+        assert_matrix_approx_eq!(
+            qr.q().clone() * qr.r().clone(),
+            permuted,
+            tol = COMPARISON_TOLERANCE
+        );
+        for index in 0..4 {
+            for row in index + 1..4 {
+                assert_eq!(qr.r()[row][index], 0.0);
+            }
+            if index > 0 {
+                assert!(qr.r()[index][index].abs() <= qr.r()[index - 1][index - 1].abs());
+            }
+        }
+    }
+
+    #[test]
+    fn qr_column_pivoted_detects_rank() {
+        // Columns 3 and 4 repeat columns 0 and 1
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(
+            &[
+                1.0, 0.0, 2.0, 1.0, 0.0, //
+                2.0, 1.0, -1.0, 2.0, 1.0, //
+                0.0, 3.0, 1.0, 0.0, 3.0, //
+                -1.0, 1.0, 0.0, -1.0, 1.0, //
+                4.0, -2.0, 1.0, 4.0, -2.0, //
+                1.0, 1.0, 1.0, 1.0, 1.0,
+            ],
+            6,
+            5,
+        );
+
+        let qr = matrix.qr_column_pivoted(1e-10).unwrap();
+
+        assert_eq!(qr.rank(1e-10), 3);
+        assert_eq!(qr.selected_columns().len(), 3);
+        let selected: Vec<usize> = qr
+            .selected_columns()
+            .iter()
+            .map(|column| column % 3)
+            .collect();
+        let mut sorted: Vec<usize> = selected.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![0, 1, 2]);
+
+        // b is outside the column space, so only the normal equations A^T (Ax - b) = 0 hold
+        let b: Vec<f64> = vec![1.0, 0.0, 2.0, -1.0, 3.0, 0.5];
+        let solution: Vec<f64> = qr.least_squares_solve(&b).unwrap();
+        let residual: Vec<f64> = matrix
+            .try_mul_vec(&solution)
+            .unwrap()
+            .iter()
+            .zip(&b)
+            .map(|(fitted, target)| fitted - target)
+            .collect();
+        for gradient in matrix.transpose().try_mul_vec(&residual).unwrap() {
+            assert!(gradient.abs() < COMPARISON_TOLERANCE);
+        }
+        let nonzero: usize = solution.iter().filter(|value| **value != 0.0).count();
+        assert_eq!(nonzero, 3);
+
+        // The basic solution matches the normal equations A_S^T A_S x = A_S^T b solved on the selected columns alone
+        let reduced: Matrix<f64> = matrix.select_columns(qr.selected_columns()).unwrap();
+        let normal_matrix: Matrix<f64> = reduced.transpose() * reduced.clone();
+        let normal_b: Vec<f64> = reduced.transpose().try_mul_vec(&b).unwrap();
+        let expected: Vec<f64> = normal_matrix.factorize().unwrap().solve(&normal_b).unwrap();
+        for (column, value) in qr.selected_columns().iter().zip(expected) {
+            assert!((solution[*column] - value).abs() < COMPARISON_TOLERANCE);
+        }
+
+        assert_eq!(
+            qr.least_squares_solve(&[1.0]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "least_squares_solve",
+                left: (matrix.rows(), matrix.columns()),
+                right: (1, 1),
+            }
+        );
+        assert_eq!(
+            matrix.qr_column_pivoted(-1.0).unwrap_err(),
+            MatrixError::InvalidArgument {
+                name: "tolerance",
+                requirement: "must not be negative",
+            }
+        );
+        let non_finite: Matrix<f64> = Matrix::matrix_from_list(&[1.0, f64::NAN, 0.0, 1.0], 2, 2);
+        assert_eq!(
+            non_finite.qr_column_pivoted(1e-10).unwrap_err(),
+            MatrixError::NonFinite { row: 0, column: 1 }
+        );
+    }
+
+    #[test]
+    fn commutes_with_delta() {
+        let rotation: Matrix<f64> = Matrix::from_euler_angles(EulerOrder::Xyz, [0.0, 0.0, 0.4]);
+        let other_rotation: Matrix<f64> =
+            Matrix::from_euler_angles(EulerOrder::Xyz, [0.0, 0.0, 1.3]);
+        // Rotations about the same axis commute, up to rounding
+        assert!(rotation
+            .commutes_with(&other_rotation, COMPARISON_TOLERANCE)
+            .unwrap());
+
+        let tilted: Matrix<f64> = Matrix::from_euler_angles(EulerOrder::Xyz, [0.5, 0.0, 0.0]);
+        assert!(!rotation
+            .commutes_with(&tilted, COMPARISON_TOLERANCE)
+            .unwrap());
+        assert!(rotation.commutes_with(&tilted, 1.0).unwrap());
+    }
+
+    #[test]
+    fn sort_rows_by_column_puts_nan_last() {
+        let table: Matrix<f64> = Matrix::matrix_from_list(
+            &[0.0, 0.5, 1.0, f64::NAN, 2.0, -1.0, 3.0, f64::NAN, 4.0, 2.5],
+            5,
+            2,
+        );
+
+        assert_eq!(table.argsort_column(1, false).unwrap(), vec![2, 0, 4, 1, 3]);
+        assert_eq!(table.argsort_column(1, true).unwrap(), vec![4, 0, 2, 1, 3]);
+
+        let sorted: Matrix<f64> = table.sort_rows_by_column(1, true).unwrap();
+        let ids: Vec<f64> = (0..5).map(|row| sorted[row][0]).collect();
+        assert_eq!(ids, vec![4.0, 0.0, 2.0, 1.0, 3.0]);
+        assert!(sorted[3][1].is_nan() && sorted[4][1].is_nan());
+    }
+
+    #[test]
+    fn unique_rows_within_tolerance() {
+        // Row 1 is near row 0, and row 2 is near row 1 but not row 0, so it starts its own group
+        let matrix: Matrix<f64> = Matrix::matrix_from_list(
+            &[1.0, 2.0, 1.0004, 2.0, 1.0008, 2.0, 5.0, 5.0, 0.9997, 2.0003],
+            5,
+            2,
+        );
+
+        let (unique, mapping) = matrix.unique_rows_within(0.0005);
+
+        assert_eq!(mapping, vec![0, 0, 1, 2, 0]);
+        assert_eq!(
+            unique,
+            Matrix::matrix_from_list(&[1.0, 2.0, 1.0008, 2.0, 5.0, 5.0], 3, 2)
+        );
+        // Exact comparison keeps every noisy row apart
+        assert_eq!(matrix.unique_rows().0, matrix);
+
+        let with_nan: Matrix<f64> = Matrix::matrix_from_list(&[f64::NAN, f64::NAN], 2, 1);
+        assert_eq!(with_nan.unique_rows().1, vec![0, 1]);
+    }
+
+    #[test]
+    fn numerical_range_2x2_symmetric_is_segment() {
+        // The eigenvalues are 1 and 5
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[3.0, 2.0, 2.0, 3.0]);
+
+        let points: Vec<(f64, f64)> = matrix.numerical_range_2x2(16).unwrap();
+
+        assert_eq!(points.len(), 16);
+        for (real, imaginary) in &points {
+            assert!(imaginary.abs() < COMPARISON_TOLERANCE);
+            assert!(*real >= 1.0 - COMPARISON_TOLERANCE && *real <= 5.0 + COMPARISON_TOLERANCE);
+        }
+        assert!((points[0].0 - 5.0).abs() < COMPARISON_TOLERANCE);
+        assert!((points[8].0 - 1.0).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn numerical_range_2x2_boundary_points() {
+        // For a nilpotent Jordan block the range is the disc of radius 1/2 about the origin
+        let jordan: Matrix<f64> = Matrix::square_matrix_from_list(&[0.0, 1.0, 0.0, 0.0]);
+        for (real, imaginary) in jordan.numerical_range_2x2(12).unwrap() {
+            assert!((real.hypot(imaginary) - 0.5).abs() < COMPARISON_TOLERANCE);
+        }
+
+        // A rotation by 90 degrees is normal with eigenvalues ±i, so the range is the segment between them
+        let rotation: Matrix<f64> = Matrix::square_matrix_from_list(&[0.0, -1.0, 1.0, 0.0]);
+        let points: Vec<(f64, f64)> = rotation.numerical_range_2x2(8).unwrap();
+        for (real, imaginary) in &points {
+            assert!(real.abs() < COMPARISON_TOLERANCE);
+            assert!(imaginary.abs() <= 1.0 + COMPARISON_TOLERANCE);
+        }
+        assert!((points[0].1 - 1.0).abs() < COMPARISON_TOLERANCE);
+
+        // The range reaches along the real axis exactly as far as the extreme eigenvalues of the symmetric part
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, 4.0, 0.0, 2.0]);
+        let points: Vec<(f64, f64)> = matrix.numerical_range_2x2(4).unwrap();
+        let symmetric: Matrix<f64> = (matrix.clone() + matrix.transpose()) * 0.5;
+        let (eigenvalues, _) = symmetric.symmetric_eigen(1e-12, 100).unwrap();
+        assert!((points[0].0 - eigenvalues[0]).abs() < 1e-9);
+        assert!((points[2].0 - eigenvalues[1]).abs() < 1e-9);
+
+        assert_eq!(
+            Matrix::<f64>::identity_matrix(3)
+                .numerical_range_2x2(4)
+                .unwrap_err(),
+            "The matrix must be 2x2"
+        );
+        assert_eq!(
+            matrix.numerical_range_2x2(0).unwrap_err(),
+            "At least one sample is needed"
+        );
+    }
+}
+
+// Runs the exact same tests* as f64tests, but with f32 instead
+// least_squares_a is different because it can actually solve the system consistently
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod f32tests {
+    use crate::matrix::Matrix;
+
+    const COMPARISON_TOLERANCE: f32 = 0.001;
+    const STANDARD_MATRIX_A: &[f32] = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+    const STANDARD_MATRIX_B: &[f32] = &[5.7, 1.2, 0.0, 4.9, -7.1, -2.1, 77.1, 0.0, 9.1];
+    // These matrices were calculated using reshish.com
+    const STANDARD_MATRIX_MULTIPLICATION_SOLUTION: &[f32] =
+        &[246.8, -13.0, 23.1, 509.9, -30.7, 44.1, 773.0, -48.4, 65.1];
+    const STANDARD_MATRIX_REVERSE_MULTIPLICATION_SOLUTION: &[f32] =
+        &[10.5, 17.4, 24.3, -38.2, -42.5, -46.8, 140.8, 227.0, 313.2];
+    const STANDARD_MATRIX_B_INVERSE_SOLUTION: &[f32] = &[
+        0.10487325447955369214,
+        0.017725057095135835292,
+        0.0040903977911851927602,
+        0.33518537455545329559,
+        -0.084194021201895217641,
+        -0.019429389508129665611,
+        -0.88854152971138347965,
+        -0.15017603319065636276,
+        0.075234102230727652557,
+    ];
+
+    const STANDARD_MATRIX_A_DETERMINANT: f32 = 0.0;
+    const STANDARD_MATRIX_B_DETERMINANT: f32 = -616.077;
+
+    const STANDARD_MATRIX_A_REF: &[f32] = &[1.0, 0.0, -1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0];
+
+    const STANDARD_MATRIX_B_REF: &[f32] = &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+
+    const STANDARD_MATRIX_A_TRANSPOSE: &[f32] = &[1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0];
+
+    const STANDARD_MATRIX_B_TRANSPOSE: &[f32] = &[5.7, 4.9, 77.1, 1.2, -7.1, 0.0, 0.0, -2.1, 9.1];
+
+    const B_VECTOR: &[f32] = &[3.9, 7.2, -1.0];
+
+    const WRONG_LENGTH_B_VECTOR: &[f32] = &[-2.0, 1.0, 9.8, -0.1];
+
+    const STANDARD_MATRIX_B_SOLUTION: &[f32] = &[
+        0.53253570576405222074,
+        0.72045539762075195146,
+        -4.6218135070778490354,
+    ];
+
+    const LEAST_SQUARES_A_SOLUTION: &[f32] = &[-7.45, 6.6333333333333332766, 0.0];
+
+    #[test]
+    fn square_addition() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let mut solution_list: Vec<f32> = Vec::with_capacity(9);
+        for index in 0..9 {
+            solution_list.push(STANDARD_MATRIX_A[index] + STANDARD_MATRIX_B[index]);
+        }
+        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(&solution_list);
+        assert!(solution_matrix.equals(&(a + b), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn square_subtraction() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let mut solution_list: Vec<f32> = Vec::with_capacity(9);
+        for index in 0..9 {
+            solution_list.push(STANDARD_MATRIX_A[index] - STANDARD_MATRIX_B[index]);
+        }
+        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(&solution_list);
+        assert!(solution_matrix.equals(&(a - b), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn square_scalar() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let mut solution_list: Vec<f32> = Vec::with_capacity(9);
+        for value in STANDARD_MATRIX_A {
+            solution_list.push(value * 3.7);
+        }
+        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(&solution_list);
+        assert!(solution_matrix.equals(&(a * 3.7), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn square_multiplication() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_matrix: Matrix<f32> =
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_MULTIPLICATION_SOLUTION);
+        assert!(solution_matrix.equals(&(a * b), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn square_reverse_multiplication() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_matrix: Matrix<f32> =
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_REVERSE_MULTIPLICATION_SOLUTION);
+        assert!(solution_matrix.equals(&(b * a), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn a_inverse() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        assert_eq!(a.inverse().unwrap_err(), "Matrix is not invertible");
+    }
+
+    #[test]
+    fn b_inverse() {
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_matrix: Matrix<f32> =
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_B_INVERSE_SOLUTION);
+        assert!(solution_matrix.equals(&(b.inverse()).unwrap(), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn a_determinant() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let determinant: f32 = a.determinant();
+
+        assert!((determinant - STANDARD_MATRIX_A_DETERMINANT).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn b_determinant() {
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let determinant: f32 = b.determinant();
+
+        assert!((determinant - STANDARD_MATRIX_B_DETERMINANT).abs() < COMPARISON_TOLERANCE);
+    }
+
+    #[test]
+    fn a_ref() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A_REF);
+        assert!(solution_matrix.equals(&(a.reduced_echelon_form()), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn b_ref() {
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_matrix: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B_REF);
+        assert!(solution_matrix.equals(&(b.reduced_echelon_form()), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn a_transpose() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let solution_matrix: Matrix<f32> =
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_A_TRANSPOSE);
+        assert!(solution_matrix.equals(&(a.transpose()), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    fn b_transpose() {
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_matrix: Matrix<f32> =
+            Matrix::square_matrix_from_list(STANDARD_MATRIX_B_TRANSPOSE);
+        assert!(solution_matrix.equals(&(b.transpose()), COMPARISON_TOLERANCE));
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_length_b_vector() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let _ = a.solve(WRONG_LENGTH_B_VECTOR.to_vec());
+    }
+
+    #[test]
+    fn solve_a() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        assert_eq!(
+            a.solve(B_VECTOR.to_vec()).unwrap_err(),
+            "The system was inconsistent and there is no solution for b."
+        );
+    }
+
+    #[test]
+    fn solve_b() {
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_vector: Vec<f32> = STANDARD_MATRIX_B_SOLUTION.to_vec();
+
+        let b_solution: Vec<f32> = b.solve(B_VECTOR.to_vec()).unwrap();
+
+        for i in 0..solution_vector.len() {
+            assert!((solution_vector[i] - b_solution[i]).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn least_squares_a() {
+        let a: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_A);
+
+        let solution_vector: Vec<f32> = LEAST_SQUARES_A_SOLUTION.to_vec();
+
+        let a_solution: Vec<f32> = a.least_squares_solution(B_VECTOR.to_vec()).unwrap();
+
+        for i in 0..solution_vector.len() {
+            assert!((solution_vector[i] - a_solution[i]).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn least_squares_b() {
+        let b: Matrix<f32> = Matrix::square_matrix_from_list(STANDARD_MATRIX_B);
+
+        let solution_vector: Vec<f32> = STANDARD_MATRIX_B_SOLUTION.to_vec();
+
+        let b_solution: Vec<f32> = b.least_squares_solution(B_VECTOR.to_vec()).unwrap();
+
+        for i in 0..solution_vector.len() {
+            assert!((solution_vector[i] - b_solution[i]).abs() < COMPARISON_TOLERANCE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod displaytests {
+    use crate::matrix::Matrix;
+    use crate::matrix_display::DisplayOptions;
+
+    #[test]
+    fn truncated_display() {
+        let list: Vec<i32> = (0..400).collect();
+        let matrix: Matrix<i32> = Matrix::square_matrix_from_list(&list);
+
+        let options: DisplayOptions = DisplayOptions::new().max_rows(6).max_columns(6);
+
+        assert_eq!(
+            matrix.to_string_with(&options),
+            "[  0,   1,   2, ...,  17,  18,  19]\n\
+             [ 20,  21,  22, ...,  37,  38,  39]\n\
+             [ 40,  41,  42, ...,  57,  58,  59]\n\
+             [..., ..., ..., ..., ..., ..., ...]\n\
+             [340, 341, 342, ..., 357, 358, 359]\n\
+             [360, 361, 362, ..., 377, 378, 379]\n\
+             [380, 381, 382, ..., 397, 398, 399]"
+        );
+    }
+
+    #[test]
+    fn untruncated_display() {
+        let matrix: Matrix<i32> = Matrix::matrix_from_list(&[1, -20, 3, 4, 5, 600], 2, 3);
+
+        assert_eq!(
+            matrix.display().max_rows(8).max_columns(8).to_string(),
+            "[1, -20,   3]\n[4,   5, 600]"
+        );
+        assert_eq!(matrix.to_string(), "[1, -20,   3]\n[4,   5, 600]");
+    }
+
+    #[test]
+    fn precision_display() {
+        let matrix: Matrix<f64> = Matrix::square_matrix_from_list(&[1.0, -0.5, 2.345, 10.0]);
+
+        assert_eq!(
+            matrix.display().precision(2).to_string(),
+            "[1.00, -0.50]\n[2.35, 10.00]"
+        );
+    }
+}
+
+#[cfg(test)]
+mod asserttests {
+    use crate::matrix::Matrix;
+    use crate::{assert_matrix_approx_eq, assert_matrix_eq};
+    use std::panic;
+
+    /// Runs the given closure and returns the message it panicked with
+    fn panic_message<F>(function: F) -> String
+    where
+        F: FnOnce() + panic::UnwindSafe,
+    {
+        let payload = panic::catch_unwind(function).unwrap_err();
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast::<&str>().unwrap().to_string(),
+        }
+    }
+
+    #[test]
+    fn equal_matrices() {
+        let a: Matrix<i32> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+        let b: Matrix<i32> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+
+        assert_matrix_eq!(a, &b);
+        assert_eq!(a.diff_report(&b, 0), None);
+    }
+
+    #[test]
+    fn mismatch_report() {
+        let message: String = panic_message(|| {
+            let a: Matrix<i32> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+            let b: Matrix<i32> = Matrix::square_matrix_from_list(&[1, 5, 3, 14]);
+            assert_matrix_eq!(a, b);
+        });
+
+        assert!(message.contains("both are 2x2, 2 mismatched element(s)"));
+        // The largest difference is reported first
+        let first: usize = message.find("(1, 1): left=4, right=14, |diff|=10").unwrap();
+        let second: usize = message.find("(0, 1): left=2, right=5, |diff|=3").unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn shape_mismatch_report() {
+        let message: String = panic_message(|| {
+            let a: Matrix<f64> = Matrix::new(2, 3);
+            let b: Matrix<f64> = Matrix::new(3, 2);
+            assert_matrix_approx_eq!(&a, &b, tol = 1e-9);
+        });
+
+        assert!(message.contains("left is 2x3, right is 3x2"));
+    }
+
+    #[test]
+    fn approximate_equality() {
+        let a: Matrix<f64> = Matrix::square_matrix_from_list(&[1000.0, 1.0, 0.0, -1.0]);
+        let b: Matrix<f64> = Matrix::square_matrix_from_list(&[1000.1, 1.0, 0.0, -1.0]);
+
+        assert_matrix_approx_eq!(a, b, tol = 0.2);
+        assert_matrix_approx_eq!(a, b, rel = 1e-3);
+
+        let message: String = panic_message(|| {
+            assert_matrix_approx_eq!(a, b, tol = 1e-3);
+        });
+        assert!(message.contains("1 mismatched element(s)"));
+        assert!(message.contains("(0, 0): left=1000, right=1000.1"));
+    }
+
+    /// A crate without its own num_traits dependency sees the macros' expansion from here, since this empty module
+    /// shadows the num_traits crate
+    mod without_num_traits {
+        use crate::matrix::Matrix;
+        use crate::{assert_matrix_approx_eq, assert_matrix_eq};
+
+        #[allow(dead_code)]
+        mod num_traits {}
+
+        #[test]
+        fn macros_expand_without_num_traits() {
+            let a: Matrix<i32> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+
+            assert_matrix_eq!(a, a.clone());
+            assert_matrix_approx_eq!(a, a.clone(), tol = 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod i64tests {
+    use crate::assert_matrix_eq;
+    use crate::matrix::{inverse_permutation, orient_2d, orient_3d, Matrix};
+    use crate::matrix_error::MatrixError;
+    use crate::semiring::{BooleanSemiring, MaxPlus, StandardSemiring};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn integer_inverse() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 3, 1, 2]);
+
+        let inverse: Matrix<i64> = matrix.integer_inverse().unwrap();
+
+        assert_matrix_eq!(inverse, Matrix::square_matrix_from_list(&[2, -3, -1, 2]));
+        assert_matrix_eq!(inverse * matrix, Matrix::identity_matrix(2));
+    }
+
+    #[test]
+    fn integer_inverse_not_unimodular() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 0, 0, 1]);
+
+        assert_eq!(
+            matrix.integer_inverse().unwrap_err(),
+            "The determinant of the matrix was not 1 or -1"
+        );
+    }
+
+    #[test]
+    fn kronecker_sum() {
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+        let b: Matrix<i64> = Matrix::square_matrix_from_list(&[0, 1, 1, 0]);
+
+        let solution_matrix: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[1, 1, 2, 0, 1, 1, 0, 2, 3, 0, 4, 1, 0, 3, 1, 4]);
+        assert_matrix_eq!(a.kronecker_sum(&b).unwrap(), solution_matrix);
+        assert!(a.kronecker_sum(&Matrix::new(2, 3)).is_err());
+    }
+
+    #[test]
+    fn diagonal_dominance() {
+        let strictly_dominant: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[4, -1, 2, 1, -5, 3, 0, 2, 3]);
+        let weakly_dominant: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[3, -1, 2, 1, -5, 3, 0, 2, 3]);
+        let not_dominant: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+
+        assert!(strictly_dominant.is_diagonally_dominant(true));
+        assert!(strictly_dominant.is_diagonally_dominant(false));
+        assert!(!weakly_dominant.is_diagonally_dominant(true));
+        assert!(weakly_dominant.is_diagonally_dominant(false));
+        assert!(!not_dominant.is_diagonally_dominant(true));
+        assert!(!not_dominant.is_diagonally_dominant(false));
+    }
+
+    #[test]
+    fn orientation() {
+        assert_eq!(orient_2d([0, 0], [2, 2], [5, 5]).unwrap(), Ordering::Equal);
+        assert_eq!(
+            orient_2d([0, 0], [2, 0], [1, 1]).unwrap(),
+            Ordering::Greater
+        );
+        assert_eq!(orient_2d([0, 0], [2, 0], [1, -1]).unwrap(), Ordering::Less);
+
+        assert_eq!(
+            orient_3d([0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]).unwrap(),
+            Ordering::Greater
+        );
+        assert_eq!(
+            orient_3d([0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, -1]).unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(
+            orient_3d([0, 0, 0], [1, 0, 0], [0, 1, 0], [3, 4, 0]).unwrap(),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn permute_rows() {
+        let mut matrix: Matrix<i64> = Matrix::matrix_from_list(&[0, 0, 1, 1, 2, 2, 3, 3], 4, 2);
+
+        // Position 0 receives row 1, position 1 receives row 2, and so on. Moving row i to position
+        // permutation[i] instead would give the rows in the order 3, 0, 1, 2
+        matrix.permute_rows_in_place(&[1, 2, 3, 0]).unwrap();
+
+        assert_matrix_eq!(
+            matrix,
+            Matrix::matrix_from_list(&[1, 1, 2, 2, 3, 3, 0, 0], 4, 2)
+        );
+
+        matrix
+            .permute_rows_in_place(&inverse_permutation(&[1, 2, 3, 0]))
+            .unwrap();
+        assert_matrix_eq!(
+            matrix,
+            Matrix::matrix_from_list(&[0, 0, 1, 1, 2, 2, 3, 3], 4, 2)
+        );
+    }
+
+    #[test]
+    fn permute_columns() {
+        let mut matrix: Matrix<i64> = Matrix::matrix_from_list(&[0, 1, 2, 10, 11, 12], 2, 3);
+
+        matrix.permute_columns_in_place(&[2, 0, 1]).unwrap();
+
+        assert_matrix_eq!(
+            matrix,
+            Matrix::matrix_from_list(&[2, 0, 1, 12, 10, 11], 2, 3)
+        );
+    }
+
+    #[test]
+    fn identity_and_invalid_permutations() {
+        let original: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let mut matrix: Matrix<i64> = original.clone();
+
+        matrix.permute_rows_in_place(&[0, 1, 2]).unwrap();
+        matrix.permute_columns_in_place(&[0, 1, 2]).unwrap();
+        assert_matrix_eq!(matrix, original);
+
+        let not_permutation: MatrixError = MatrixError::NotPermutation { length: 3 };
+        assert_eq!(
+            matrix.permute_rows_in_place(&[0, 0, 1]).unwrap_err(),
+            not_permutation
+        );
+        assert_eq!(
+            matrix.permute_rows_in_place(&[0, 1]).unwrap_err(),
+            not_permutation
+        );
+        assert_eq!(
+            matrix.permute_columns_in_place(&[0, 1, 3]).unwrap_err(),
+            not_permutation
+        );
+        assert_matrix_eq!(matrix, original);
+    }
+
+    #[test]
+    fn determinant_mod_prime() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            3, -7, 12, 5, 0, 4, -9, 2, 8, 1, 6, -3, 2, 11, -5, 7,
+        ]);
+        let determinant: i64 = matrix.determinant_cofactor().unwrap();
+
+        for prime in [2, 7, 13, 101, 1_000_000_007] {
+            assert_eq!(
+                matrix.determinant_mod(prime).unwrap(),
+                determinant.rem_euclid(prime)
+            );
+        }
+    }
+
+    #[test]
+    fn determinant_mod_errors() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 1, 1, 1]);
+
+        assert_eq!(
+            matrix.determinant_mod(4).unwrap_err(),
+            "The modulus is not prime"
+        );
+        assert_eq!(
+            matrix.determinant_mod(1).unwrap_err(),
+            "The modulus must be at least 2"
+        );
+        assert_eq!(
+            Matrix::<i64>::new(2, 3).determinant_mod(7).unwrap_err(),
+            "The matrix was not square"
+        );
+    }
+
+    #[test]
+    fn solve_mod_prime() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 3, 1, 1, -1, 4, 5, 2, -3]);
+        let x: [i64; 3] = [4, 9, 2];
+        let b: Vec<i64> = matrix.try_mul_vec(&x).unwrap();
+
+        assert_eq!(matrix.solve_mod(&b, 13).unwrap(), vec![4, 9, 2]);
+        assert_eq!(matrix.solve_mod(&b, 7).unwrap(), vec![4, 2, 2]);
+    }
+
+    #[test]
+    fn solve_mod_errors() {
+        // The determinant is 5, so the matrix is singular modulo 5 but the pivot 2 has no inverse modulo 4
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 1, 1, 3]);
+
+        assert_eq!(
+            matrix.solve_mod(&[1, 2], 5).unwrap_err(),
+            "The matrix is singular modulo the modulus"
+        );
+        assert_eq!(
+            matrix.solve_mod(&[1, 2], 4).unwrap_err(),
+            "The modulus is not prime"
+        );
+        assert_eq!(
+            matrix.solve_mod(&[1, 2, 3], 7).unwrap_err(),
+            "Your b vector is not the correct length!"
+        );
+    }
+
+    #[test]
+    fn permanent() {
+        // Expanding by permutations: 1*5*9 + 1*6*8 + 2*4*9 + 2*6*7 + 3*4*8 + 3*5*7
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        // The permanent of the all-ones matrix counts the permutations, 4! = 24
+        let ones: Matrix<i64> = Matrix::square_matrix_from_list(&[1; 16]);
+
+        assert_eq!(matrix.permanent().unwrap(), 450);
+        assert_eq!(ones.permanent().unwrap(), 24);
+        assert_eq!(
+            Matrix::<i64>::new(2, 3).permanent().unwrap_err(),
+            "The matrix was not square"
+        );
+    }
+
+    #[test]
+    fn characteristic_polynomial() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[4, -2, 7, 3]);
+        let trace: i64 = matrix.trace().unwrap();
+        let determinant: i64 = matrix.determinant_cofactor().unwrap();
+        // The eigenvalues 1, 2, and 3 give (x - 1)(x - 2)(x - 3)
+        let triangular: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[1, 5, -4, 0, 2, 6, 0, 0, 3]);
+
+        assert_eq!(
+            matrix.characteristic_polynomial().unwrap(),
+            vec![1, -trace, determinant]
+        );
+        assert_eq!(
+            triangular.characteristic_polynomial().unwrap(),
+            vec![1, -6, 11, -6]
+        );
+        assert_eq!(
+            Matrix::<i64>::new(2, 3)
+                .characteristic_polynomial()
+                .unwrap_err(),
+            "The matrix was not square"
+        );
+    }
+
+    #[test]
+    fn abs() {
+        let matrix: Matrix<i64> = Matrix::matrix_from_list(&[-3, 0, 7, -1, 4, -9], 2, 3);
+
+        let absolute: Matrix<i64> = matrix.abs();
+
+        assert!((0..2).all(|row| (0..3).all(|column| absolute[row][column] >= 0)));
+        assert_matrix_eq!(
+            absolute,
+            Matrix::matrix_from_list(&[3, 0, 7, 1, 4, 9], 2, 3)
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let matrix: Matrix<i64> = Matrix::matrix_from_list(&[-7, 0, 3, 12, 5, -1], 2, 3);
+
+        assert_matrix_eq!(
+            matrix.clamp(-1, 5),
+            Matrix::matrix_from_list(&[-1, 0, 3, 5, 5, -1], 2, 3)
+        );
+        assert_matrix_eq!(matrix.clamp(-10, 20), matrix);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clamp_inverted_bounds() {
+        let _ = Matrix::<i64>::identity_matrix(2).clamp(1, 0);
+    }
+
+    #[test]
+    fn signum() {
+        let matrix: Matrix<i64> = Matrix::matrix_from_list(&[-3, 0, 7, -1, 4, 0], 2, 3);
+
+        assert_matrix_eq!(
+            matrix.signum(),
+            Matrix::matrix_from_list(&[-1, 0, 1, -1, 1, 0], 2, 3)
+        );
+    }
+
+    #[test]
+    fn one_hot_round_trip() {
+        let labels: [usize; 6] = [2, 0, 1, 2, 3, 0];
+
+        let encoded: Matrix<i64> = Matrix::one_hot(&labels, 4).unwrap();
+
+        assert_eq!((encoded.rows(), encoded.columns()), (6, 4));
+        assert_eq!(encoded.row_sums(), vec![1; 6]);
+        assert_eq!(encoded.from_one_hot().unwrap(), labels.to_vec());
+        assert_eq!(
+            Matrix::<i64>::one_hot(&[0, 4, 1, 5], 4).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "label",
+                position: 1,
+                index: 4,
+                bound: 4
+            }
+        );
+    }
+
+    #[test]
+    fn indicator() {
+        let matrix: Matrix<i64> = Matrix::indicator(2, 3, &[(0, 2), (1, 0), (0, 2)]).unwrap();
+
+        assert_matrix_eq!(matrix, Matrix::matrix_from_list(&[0, 0, 1, 1, 0, 0], 2, 3));
+        assert_eq!(
+            Matrix::<i64>::indicator(2, 3, &[(2, 0)]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "row",
+                position: 0,
+                index: 2,
+                bound: 2,
+            }
+        );
+        assert_eq!(
+            Matrix::<i64>::indicator(2, 3, &[(0, 0), (1, 3)]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "column",
+                position: 1,
+                index: 3,
+                bound: 3,
+            }
+        );
+        assert_eq!(matrix.from_one_hot().unwrap(), vec![2, 0]);
+        assert_eq!(
+            Matrix::<i64>::indicator(2, 3, &[(0, 0), (0, 1), (1, 1)])
+                .unwrap()
+                .from_one_hot()
+                .unwrap_err(),
+            MatrixError::NotOneHot { row: 0 }
+        );
+        assert_eq!(
+            Matrix::<i64>::new(1, 3).from_one_hot().unwrap_err(),
+            MatrixError::NotOneHot { row: 0 }
+        );
+        assert_eq!(
+            Matrix::matrix_from_list(&[0, 2, 0], 1, 3)
+                .from_one_hot()
+                .unwrap_err(),
+            MatrixError::NotOneHot { row: 0 }
+        );
+    }
+
+    #[test]
+    fn interleave_rows() {
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+        let b: Matrix<i64> = Matrix::square_matrix_from_list(&[5, 6, 7, 8]);
+
+        assert_matrix_eq!(
+            a.interleave_rows(&b).unwrap(),
+            Matrix::matrix_from_list(&[1, 2, 5, 6, 3, 4, 7, 8], 4, 2)
+        );
+        assert_eq!(
+            a.interleave_rows(&Matrix::new(2, 3)).unwrap_err(),
+            "The matrices are not the same shape"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn shuffle_rows() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let list: Vec<i64> = (0..20).collect();
+        let matrix: Matrix<i64> = Matrix::matrix_from_list(&list, 10, 2);
+
+        let shuffled: Matrix<i64> = matrix.shuffle_rows(&mut StdRng::seed_from_u64(7));
+        let repeated: Matrix<i64> = matrix.shuffle_rows(&mut StdRng::seed_from_u64(7));
+
+        assert_matrix_eq!(shuffled, repeated);
+        let mut rows: Vec<Vec<i64>> = (0..10).map(|row| shuffled[row].to_vec()).collect();
+        rows.sort();
+        assert_eq!(
+            rows,
+            (0..10).map(|row| matrix[row].to_vec()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn row_batches() {
+        let list: Vec<i64> = (0..15).collect();
+        let matrix: Matrix<i64> = Matrix::matrix_from_list(&list, 5, 3);
+
+        let batches: Vec<Matrix<i64>> = matrix.row_batches(2);
+
+        assert_eq!(batches.len(), 3);
+        assert_matrix_eq!(
+            batches[0].clone(),
+            Matrix::matrix_from_list(&list[0..6], 2, 3)
+        );
+        assert_matrix_eq!(
+            batches[1].clone(),
+            Matrix::matrix_from_list(&list[6..12], 2, 3)
+        );
+        assert_matrix_eq!(
+            batches[2].clone(),
+            Matrix::matrix_from_list(&list[12..15], 1, 3)
+        );
+    }
+
+    #[test]
+    fn spanning_tree_count() {
+        let complete: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[0, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0]);
+        let cycle: Matrix<i64> =
+            Matrix::adjacency_from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)], false)
+                .unwrap();
+        let tree: Matrix<i64> =
+            Matrix::adjacency_from_edges(5, &[(0, 1), (1, 2), (1, 3), (3, 4)], false).unwrap();
+        let disconnected: Matrix<i64> =
+            Matrix::adjacency_from_edges(4, &[(0, 1), (2, 3)], false).unwrap();
+
+        assert_eq!(complete.spanning_tree_count().unwrap(), 16);
+        assert_eq!(cycle.spanning_tree_count().unwrap(), 5);
+        assert_eq!(tree.spanning_tree_count().unwrap(), 1);
+        assert_eq!(disconnected.spanning_tree_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn spanning_tree_count_multigraph() {
+        // A triangle with a doubled edge between 0 and 1: trees use two of the three vertex pairs, and the pair (0, 1)
+        // can use either of its edges, giving 2 * 1 + 2 * 1 + 1 * 1 = 5
+        let multigraph: Matrix<i64> =
+            Matrix::adjacency_from_edges(3, &[(0, 1), (0, 1), (1, 2), (2, 0)], false).unwrap();
+
+        assert_eq!(multigraph.spanning_tree_count().unwrap(), 5);
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[1, 1, 1, 0])
+                .spanning_tree_count()
+                .unwrap_err(),
+            MatrixError::SelfLoop { vertex: 0 }
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0, 1, 2, 0])
+                .spanning_tree_count()
+                .unwrap_err(),
+            MatrixError::NotSymmetric
+        );
+        assert_eq!(
+            Matrix::square_matrix_from_list(&[0, -1, -1, 0])
+                .spanning_tree_count()
+                .unwrap_err(),
+            MatrixError::NegativeEntry { row: 0, column: 1 }
+        );
+    }
+
+    #[test]
+    fn spanning_tree_count_overflow() {
+        // K_n has n^(n - 2) spanning trees, and 20^18 is larger than u64::MAX
+        let complete: Matrix<i64> = Matrix::from_vector(
+            &(0..20)
+                .map(|row| (0..20).map(|column| i64::from(row != column)).collect())
+                .collect::<Vec<Vec<i64>>>(),
+        );
+        let smaller: Matrix<i64> = complete.submatrix(0, 0, 15, 15).unwrap();
+
+        assert_eq!(smaller.spanning_tree_count().unwrap(), 15_u64.pow(13));
+        assert_eq!(
+            complete.spanning_tree_count().unwrap_err(),
+            MatrixError::Overflow {
+                op: "spanning_tree_count"
+            }
+        );
+    }
+
+    #[test]
+    fn all_pairs_shortest_paths() {
+        let x: i64 = i64::MAX;
+        let weights: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            0, 4, x, 10, x, //
+            x, 0, 3, x, x, //
+            x, x, 0, 2, x, //
+            x, -1, x, 0, x, //
+            1, x, x, x, 0,
+        ]);
+
+        let distances: Matrix<i64> = weights.all_pairs_shortest_paths().unwrap();
+
+        // Vertex 4 can reach every vertex through 0, but no vertex can reach it
+        assert_matrix_eq!(
+            distances.clone(),
+            Matrix::square_matrix_from_list(&[
+                0, 4, 7, 9, x, //
+                x, 0, 3, 5, x, //
+                x, 1, 0, 2, x, //
+                x, -1, 2, 0, x, //
+                1, 5, 8, 10, 0,
+            ])
+        );
+        // Squaring in the (min, +) semiring ceil(log2(5)) = 3 times gives the same distances
+        let mut powers: Matrix<i64> = weights.clone();
+        for _ in 0..3 {
+            powers = powers.min_plus_product(&powers).unwrap();
+        }
+        assert_matrix_eq!(powers, distances);
+    }
+
+    #[test]
+    fn shortest_path_errors() {
+        let x: i64 = i64::MAX;
+        let negative_cycle: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[0, 2, x, x, 0, -1, -2, x, 0]);
+
+        assert_eq!(
+            negative_cycle.all_pairs_shortest_paths().unwrap_err(),
+            MatrixError::NegativeCycle { vertex: 0 }
+        );
+        assert_eq!(
+            negative_cycle
+                .min_plus_product(&Matrix::new(2, 3))
+                .unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "min_plus_product",
+                left: (3, 3),
+                right: (2, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn standard_semiring_product_matches_multiplication() {
+        let a: Matrix<i64> = Matrix::matrix_from_list(&[1, -2, 3, 4, 0, -1], 2, 3);
+        let b: Matrix<i64> = Matrix::matrix_from_list(&[2, 1, 0, -3, 5, 4], 3, 2);
+
+        assert_matrix_eq!(
+            a.product_with::<StandardSemiring>(&b).unwrap(),
+            a.clone() * b.clone()
+        );
+        assert_eq!(
+            a.product_with::<StandardSemiring>(&a).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "product_with",
+                left: (2, 3),
+                right: (2, 3),
+            }
+        );
+
+        let square: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 1, 0, 1, 0, 2, -1, 3, 1]);
+        assert_matrix_eq!(
+            square.pow_with::<StandardSemiring>(3).unwrap(),
+            square.clone() * square.clone() * square.clone()
+        );
+        assert_matrix_eq!(
+            square.pow_with::<StandardSemiring>(0).unwrap(),
+            Matrix::identity_matrix(3)
+        );
+        assert_eq!(
+            a.pow_with::<StandardSemiring>(2).unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn boolean_semiring_powers_give_reachability() {
+        let x: i64 = i64::MAX;
+        // 0 -> 1 -> 2 -> 0 is a cycle that leads out to 3 -> 4
+        let adjacency: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            0, 7, 0, 0, 0, //
+            0, 0, 7, 0, 0, //
+            7, 0, 0, 7, 0, //
+            0, 0, 0, 0, 7, //
+            0, 0, 0, 0, 0,
+        ]);
+        let weights: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            0, 1, x, x, x, //
+            x, 0, 1, x, x, //
+            1, x, 0, 1, x, //
+            x, x, x, 0, 1, //
+            x, x, x, x, 0,
+        ]);
+
+        // Paths of up to 4 steps cover every reachable pair, the same pairs with a finite shortest path
+        let with_loops: Matrix<i64> = adjacency.clone() + Matrix::identity_matrix(5);
+        let closure: Matrix<i64> = with_loops.pow_with::<BooleanSemiring>(4).unwrap();
+        let distances: Matrix<i64> = weights.all_pairs_shortest_paths().unwrap();
+        for row in 0..5 {
+            for column in 0..5 {
+                let reachable: i64 = if distances[row][column] == x { 0 } else { 1 };
+                assert_eq!(closure[row][column], reachable);
+            }
+        }
+
+        // Exactly two steps
+        assert_matrix_eq!(
+            adjacency.pow_with::<BooleanSemiring>(2).unwrap(),
+            Matrix::square_matrix_from_list(&[
+                0, 0, 1, 0, 0, //
+                1, 0, 0, 1, 0, //
+                0, 1, 0, 0, 1, //
+                0, 0, 0, 0, 0, //
+                0, 0, 0, 0, 0,
+            ])
+        );
+    }
+
+    #[test]
+    fn max_plus_semiring_finds_longest_paths() {
+        let x: i64 = i64::MIN;
+        // A DAG where 0 -> 2 -> 3 -> 4 is longer than the direct 0 -> 3 edge
+        let weights: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            0, 3, 2, 5, x, //
+            x, 0, x, 4, x, //
+            x, x, 0, 6, x, //
+            x, x, x, 0, 1, //
+            x, x, x, x, 0,
+        ]);
+
+        // With no cycles, paths of up to 4 steps include the longest ones
+        assert_matrix_eq!(
+            weights.pow_with::<MaxPlus>(4).unwrap(),
+            Matrix::square_matrix_from_list(&[
+                0, 3, 2, 8, 9, //
+                x, 0, x, 4, 5, //
+                x, x, 0, 6, 7, //
+                x, x, x, 0, 1, //
+                x, x, x, x, 0,
+            ])
+        );
+    }
+    /// Checks U * A = H, that U is unimodular, and the pivot conditions of the Hermite normal form
+    fn check_hermite_normal_form(a: &Matrix<i64>) -> Matrix<i64> {
+        let (hermite, unimodular): (Matrix<i64>, Matrix<i64>) = a.hermite_normal_form().unwrap();
+
+        assert_matrix_eq!(unimodular.clone() * a.clone(), hermite.clone());
+        // det(U) = ±1 exactly, checked modulo two large primes since integer elimination isn't exact past 4x4
+        for prime in [1_000_000_007, 998_244_353] {
+            assert!([1, prime - 1].contains(&unimodular.determinant_mod(prime).unwrap()));
+        }
+
+        let mut previous_pivot: Option<usize> = None;
+        for row in 0..hermite.rows() {
+            let Some(pivot) = (0..hermite.columns()).find(|column| hermite[row][*column] != 0)
+            else {
+                // Zero rows are only at the bottom
+                assert!((row..hermite.rows()).all(|r| hermite[r].iter().all(|value| *value == 0)));
+                break;
+            };
+            assert!(previous_pivot.is_none_or(|previous| pivot > previous));
+            assert!(hermite[row][pivot] > 0);
+            for above in 0..row {
+                assert!((0..hermite[row][pivot]).contains(&hermite[above][pivot]));
+            }
+            previous_pivot = Some(pivot);
+        }
+
+        // The Hermite normal form is unique, so it's its own normal form
+        assert_matrix_eq!(hermite.hermite_normal_form().unwrap().0, hermite.clone());
+        hermite
+    }
+
+    #[test]
+    fn hermite_normal_form_published_examples() {
+        // The examples from the Wikipedia article on the Hermite normal form
+        let square: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            3, 3, 1, 4, //
+            0, 1, 0, 0, //
+            0, 0, 19, 16, //
+            0, 0, 0, 3,
+        ]);
+        assert_matrix_eq!(
+            check_hermite_normal_form(&square),
+            Matrix::square_matrix_from_list(&[
+                3, 0, 1, 1, //
+                0, 1, 0, 0, //
+                0, 0, 19, 1, //
+                0, 0, 0, 3,
+            ])
+        );
+
+        let wide: Matrix<i64> =
+            Matrix::matrix_from_list(&[2, 3, 6, 2, 5, 6, 1, 6, 8, 3, 1, 1], 3, 4);
+        assert_matrix_eq!(
+            check_hermite_normal_form(&wide),
+            Matrix::matrix_from_list(&[1, 0, 50, -11, 0, 3, 28, -2, 0, 0, 61, -13], 3, 4)
+        );
+    }
+
+    #[test]
+    fn hermite_normal_form_rectangular_and_rank_deficient() {
+        let tall: Matrix<i64> = Matrix::matrix_from_list(&[4, -6, 6, 9, -2, 3, 10, 15, 0, 0], 5, 2);
+        assert_matrix_eq!(
+            check_hermite_normal_form(&tall),
+            Matrix::matrix_from_list(&[2, 3, 0, 6, 0, 0, 0, 0, 0, 0], 5, 2)
+        );
+
+        // The second row is twice the first and the first column is zero
+        let deficient: Matrix<i64> = Matrix::matrix_from_list(&[0, -2, 4, 0, -4, 8, 0, 3, 5], 3, 3);
+        assert_matrix_eq!(
+            check_hermite_normal_form(&deficient),
+            Matrix::matrix_from_list(&[0, 1, 9, 0, 0, 22, 0, 0, 0], 3, 3)
+        );
+
+        check_hermite_normal_form(&Matrix::new(2, 3));
+        check_hermite_normal_form(&Matrix::square_matrix_from_list(&[
+            -5, 7, 11, 3, 0, -8, 2, 9, 13,
+        ]));
+    }
+
+    #[test]
+    fn hermite_normal_form_overflow() {
+        // U for this column is [1 -1; -126 127], which just fits in i8
+        let a: Matrix<i8> = Matrix::matrix_from_list(&[127, 126], 2, 1);
+        assert_eq!(
+            a.hermite_normal_form().unwrap().0,
+            Matrix::matrix_from_list(&[1, 0], 2, 1)
+        );
+        // Here U and H need entries well outside the range of i8
+        let b: Matrix<i8> = Matrix::matrix_from_list(&[127, -128, 100, 101], 2, 2);
+        assert_eq!(
+            b.hermite_normal_form().unwrap_err(),
+            MatrixError::Overflow {
+                op: "hermite_normal_form"
+            }
+        );
+    }
+
+    #[test]
+    fn integer_echelon_form() {
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            2, 3, 1, 5, //
+            4, 6, 2, 1, //
+            -1, 2, 7, 3, //
+            3, 0, 5, -2,
+        ]);
+
+        // Rows 1 and 2 are swapped for the second pivot, with the row moved down negated
+        assert_matrix_eq!(
+            a.integer_echelon_form(),
+            Matrix::square_matrix_from_list(&[
+                2, 3, 1, 5, //
+                0, 7, 15, 11, //
+                0, 0, 92, -17, //
+                0, 0, 0, -828,
+            ])
+        );
+        assert_eq!(a.determinant(), -828);
+
+        // The last pivot is the determinant, which here needs a swap for the very first pivot
+        let b: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            0, 2, -1, 3, 4, //
+            1, 5, 2, 0, -3, //
+            2, -1, 4, 1, 1, //
+            -3, 2, 0, 5, 2, //
+            1, 1, 1, 1, 6,
+        ]);
+        let echelon: Matrix<i64> = b.integer_echelon_form();
+        assert_eq!(echelon[4][4], 1326);
+        for row in 1..5 {
+            assert!((0..row).all(|column| echelon[row][column] == 0));
+        }
+
+        // Rectangular and rank deficient, where the second column has no pivot
+        let c: Matrix<i64> = Matrix::matrix_from_list(&[1, 2, 3, 4, 2, 4, 6, 9, 3, 6, 10, 1], 3, 4);
+        assert_matrix_eq!(
+            c.integer_echelon_form(),
+            Matrix::matrix_from_list(&[1, 2, 3, 4, 0, 0, 1, -11, 0, 0, 0, -1], 3, 4)
+        );
+    }
+
+    #[test]
+    fn inverse_mod_hill_cipher_keys() {
+        // The Hill cipher keys from the Wikipedia article, with their published inverses mod 26
+        let key: Matrix<i64> = Matrix::square_matrix_from_list(&[3, 3, 2, 5]);
+        let inverse: Matrix<i64> = key.inverse_mod(26).unwrap();
+        assert_matrix_eq!(
+            inverse.clone(),
+            Matrix::square_matrix_from_list(&[15, 17, 20, 9])
+        );
+        assert_matrix_eq!(
+            key.mul_mod(&inverse, 26).unwrap(),
+            Matrix::identity_matrix(2)
+        );
+
+        let key: Matrix<i64> = Matrix::square_matrix_from_list(&[6, 24, 1, 13, 16, 10, 20, 17, 15]);
+        let inverse: Matrix<i64> = key.inverse_mod(26).unwrap();
+        assert_matrix_eq!(
+            inverse.clone(),
+            Matrix::square_matrix_from_list(&[8, 5, 10, 21, 8, 21, 21, 12, 8])
+        );
+        assert_matrix_eq!(
+            key.mul_mod(&inverse, 26).unwrap(),
+            Matrix::identity_matrix(3)
+        );
+        assert_matrix_eq!(
+            inverse.mul_mod(&key, 26).unwrap(),
+            Matrix::identity_matrix(3)
+        );
+    }
+
+    #[test]
+    fn inverse_mod_entries_in_range() {
+        // Negative entries, and a first column where no entry is a unit mod 12 on its own, so the pivot has to come
+        // from combining rows
+        let a: Matrix<i32> = Matrix::square_matrix_from_list(&[0, -3, 7, 3, 5, -1, -2, 9, 4]);
+        let inverse: Matrix<i32> = a.inverse_mod(12).unwrap();
+        assert!((0..3).all(|row| inverse[row].iter().all(|value| (0..12).contains(value))));
+        assert_matrix_eq!(a.mul_mod(&inverse, 12).unwrap(), Matrix::identity_matrix(3));
+
+        let product: Matrix<i32> = a.mul_mod(&a, 7).unwrap();
+        assert!((0..3).all(|row| product[row].iter().all(|value| (0..7).contains(value))));
+    }
+
+    #[test]
+    fn inverse_mod_large_entries() {
+        // The Mersenne prime 2^61 - 1, where products of residues would overflow i64
+        let modulus: i64 = (1 << 61) - 1;
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            i64::MAX,
+            modulus - 2,
+            3,
+            i64::MIN + 5,
+            1 << 40,
+            -(1 << 59),
+            12345678901234567,
+            -98765432109876543,
+            modulus / 3,
+        ]);
+        let inverse: Matrix<i64> = a.inverse_mod(modulus).unwrap();
+
+        assert_matrix_eq!(
+            a.mul_mod(&inverse, modulus).unwrap(),
+            Matrix::identity_matrix(3)
+        );
+        assert!((0..3).all(|row| inverse[row]
+            .iter()
+            .all(|value| (0..modulus).contains(value))));
+    }
+
+    #[test]
+    fn inverse_mod_errors() {
+        // The determinant is 2, which shares a factor with 26
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 4, 1, 3]);
+        assert_eq!(
+            a.inverse_mod(26).unwrap_err(),
+            MatrixError::NotInvertibleModulo
+        );
+        assert_matrix_eq!(
+            a.inverse_mod(27).unwrap(),
+            Matrix::square_matrix_from_list(&[15, 25, 13, 1])
+        );
+
+        let small_modulus: MatrixError = MatrixError::InvalidArgument {
+            name: "modulus",
+            requirement: "must be at least 2",
+        };
+        assert_eq!(a.inverse_mod(1).unwrap_err(), small_modulus);
+        assert_eq!(a.mul_mod(&a, 0).unwrap_err(), small_modulus);
+        assert_eq!(
+            Matrix::<i64>::new(2, 3).inverse_mod(26).unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3
+            }
+        );
+        assert_eq!(
+            a.mul_mod(&Matrix::new(3, 2), 26).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "mul_mod",
+                left: (2, 2),
+                right: (3, 2)
+            }
+        );
+
+        // Products of residues below 2^64 wouldn't fit in i128
+        let wide: Matrix<i128> = Matrix::square_matrix_from_list(&[1, 2, 3, 5]);
+        assert_eq!(
+            wide.inverse_mod(1 << 64).unwrap_err(),
+            MatrixError::Overflow { op: "inverse_mod" }
+        );
+        assert_matrix_eq!(
+            wide.inverse_mod(1 << 61).unwrap(),
+            Matrix::square_matrix_from_list(&[(1 << 61) - 5, 2, 3, (1 << 61) - 1])
+        );
+    }
+
+    #[test]
+    fn determinant_bareiss() {
+        let matrices: [Matrix<i64>; 5] = [
+            Matrix::square_matrix_from_list(&[7]),
+            Matrix::square_matrix_from_list(&[0, 3, -2, 5]),
+            Matrix::square_matrix_from_list(&[1, 2, 3, 4, 5, 6, 7, 8, 9]),
+            Matrix::square_matrix_from_list(&[
+                0, 0, 2, 1, //
+                3, 0, 1, 4, //
+                0, 5, -1, 2, //
+                1, 1, 1, 0,
+            ]),
+            Matrix::square_matrix_from_list(&[
+                0, 2, -1, 3, 4, //
+                1, 5, 2, 0, -3, //
+                2, -1, 4, 1, 1, //
+                -3, 2, 0, 5, 2, //
+                1, 1, 1, 1, 6,
+            ]),
+        ];
+        for matrix in &matrices {
+            assert_eq!(matrix.determinant_bareiss(), matrix.determinant_cofactor());
+        }
+        assert_eq!(matrices[4].determinant_bareiss(), Ok(1326));
+        assert_eq!(Matrix::<i64>::new(0, 0).determinant_bareiss(), Ok(1));
+        assert_eq!(
+            Matrix::<i64>::new(2, 3).determinant_bareiss().unwrap_err(),
+            "The matrix was not square"
+        );
+
+        // The determinant is far past 2^53, so elimination in f64 can't represent it, while i128 stays exact
+        let entries: [i128; 25] = [
+            2992, 1309, 411, 747, 336, //
+            -951, 2807, 1787, 981, 1898, //
+            1161, -2879, 2551, 513, 2840, //
+            176, 2954, -1391, 236, 1231, //
+            1881, 2988, 105, -1618, 517,
+        ];
+        let exact: Matrix<i128> = Matrix::square_matrix_from_list(&entries);
+        let determinant: i128 = exact.determinant_bareiss().unwrap();
+        assert_eq!(Ok(determinant), exact.determinant_cofactor());
+
+        // Overflow is reported rather than wrapping, whether it happens during elimination or in the final narrowing
+        let huge: Matrix<i128> =
+            Matrix::square_matrix_from_list(&[i128::MAX / 2, 1, 1, i128::MAX / 2]);
+        assert_eq!(
+            huge.determinant_bareiss().unwrap_err(),
+            "An intermediate value of the determinant overflowed"
+        );
+        let narrow: Matrix<i8> = Matrix::square_matrix_from_list(&[100, 0, 0, 100]);
+        assert_eq!(
+            narrow.determinant_bareiss().unwrap_err(),
+            "The determinant doesn't fit in the element type"
+        );
+
+        let floating: Matrix<f64> =
+            Matrix::square_matrix_from_list(&entries.map(|value| value as f64));
+        let float_determinant: f64 = floating.reduced_echelon_and_det().1.unwrap();
+        assert!(determinant.abs() > 1 << 53);
+        assert_ne!(float_determinant as i128, determinant);
+    }
+
+    #[test]
+    fn determinant_sign_integer() {
+        // Row reduction in integer arithmetic truncates its divisions, and calls this singular matrix positive
+        let singular: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            3, 2, 0, 1, //
+            2, 3, 1, 0, //
+            0, 1, 3, 2, //
+            1, 0, 2, 3,
+        ]);
+        assert_ne!(singular.reduced_echelon_and_det().1, Ok(0));
+        assert_eq!(singular.determinant_cofactor(), Ok(0));
+        assert_eq!(singular.determinant_sign(0).unwrap(), Ordering::Equal);
+
+        let negative: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            0, 0, 2, 1, //
+            3, 0, 1, 4, //
+            0, 5, -1, 2, //
+            1, 1, 1, 0,
+        ]);
+        let positive: Matrix<i64> = negative.select_rows(&[1, 0, 2, 3]).unwrap();
+        assert!(negative.determinant_cofactor().unwrap() < 0);
+        assert_eq!(negative.determinant_sign(0).unwrap(), Ordering::Less);
+        assert_eq!(positive.determinant_sign(0).unwrap(), Ordering::Greater);
+
+        // Products of entries this large overflow i64, but not the exact i128 calculation
+        let large: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            4_000_000_000,
+            3_000_000_000,
+            3_000_000_000,
+            2_000_000_000,
+        ]);
+        assert_eq!(large.determinant_sign(0).unwrap(), Ordering::Less);
+        let large: Matrix<i64> = Matrix::square_matrix_from_list(&[
+            3_000_000_000,
+            1,
+            0, //
+            0,
+            3_000_000_000,
+            1, //
+            1,
+            0,
+            -3_000_000_000,
+        ]);
+        assert_eq!(large.determinant_sign(0).unwrap(), Ordering::Less);
+        assert_eq!(
+            orient_2d(
+                [0_i64, 0],
+                [4_000_000_000, 3_000_000_000],
+                [8_000_000_000, 6_000_000_000]
+            ),
+            Ok(Ordering::Equal)
+        );
+
+        // Bareiss multiplies two 3x3 minors of about 2.7e28 on its last step, which is past i128 too
+        let large: Matrix<i64> = Matrix::from_diagonal(&[3_000_000_000; 4]);
+        assert_eq!(
+            large.determinant_sign(0).unwrap_err(),
+            MatrixError::Overflow {
+                op: "determinant_sign"
+            }
+        );
+        let huge: Matrix<i64> = Matrix::from_diagonal(&[i64::MAX; 4]);
+        assert_eq!(
+            huge.determinant_sign(0).unwrap_err(),
+            MatrixError::Overflow {
+                op: "determinant_sign"
+            }
+        );
+        assert_eq!(
+            Matrix::<i64>::new(2, 3).determinant_sign(0).unwrap_err(),
+            MatrixError::NotSquare {
+                rows: 2,
+                columns: 3
+            }
+        );
+    }
+
+    #[test]
+    fn rank_mod2() {
+        // The all-ones matrix has rank 1 both over the rationals and mod 2
+        let ones: Matrix<i64> = Matrix::square_matrix_from_list(&[1; 9]);
+        assert_eq!(ones.rank_mod2(), 1);
+        assert_eq!(ones.rank(), 1);
+
+        // The determinant is -2, which vanishes mod 2
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 1, 1, -1]);
+        assert_eq!(a.rank(), 2);
+        assert_eq!(a.rank_mod2(), 1);
+
+        let b: Matrix<i64> =
+            Matrix::matrix_from_list(&[3, -4, 5, 2, 7, -1, 9, 6, 0, 1, 4, 2], 3, 4);
+        assert_matrix_eq!(
+            b.reduced_echelon_mod2(),
+            Matrix::matrix_from_list(&[1, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0], 3, 4)
+        );
+        assert_eq!(b.rank_mod2(), 2);
+        assert_eq!(b.rank(), 3);
+        // The original is untouched
+        assert_eq!(b[0][0], 3);
+    }
+
+    #[test]
+    fn solve_mod2_lights_out() {
+        // Pressing a light on the 3x3 board toggles it and its orthogonal neighbors
+        let mut presses: Matrix<i64> = Matrix::new(9, 9);
+        for light in 0..9 {
+            for press in 0..9 {
+                let (light_row, light_column): (usize, usize) = (light / 3, light % 3);
+                let (press_row, press_column): (usize, usize) = (press / 3, press % 3);
+                if light_row.abs_diff(press_row) + light_column.abs_diff(press_column) <= 1 {
+                    presses.set_value(light, press, 1);
+                }
+            }
+        }
+        assert_eq!(presses.rank_mod2(), 9);
+
+        // Turn every light off from a board that's all on, which takes pressing the corners and the center
+        let board: Vec<u8> = vec![1; 9];
+        let solution: Vec<u8> = presses.solve_mod2(&board).unwrap();
+        assert_eq!(solution, vec![1, 0, 1, 0, 1, 0, 1, 0, 1]);
+        for light in 0..9 {
+            let toggles: i64 = (0..9)
+                .map(|press| presses[light][press] * solution[press] as i64)
+                .sum();
+            assert_eq!(toggles % 2, board[light] as i64);
+        }
+    }
+
+    #[test]
+    fn solve_mod2_errors() {
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 3, -1, 5]);
+
+        assert_eq!(
+            a.solve_mod2(&[1, 0]).unwrap_err(),
+            MatrixError::Inconsistent
+        );
+        assert_eq!(a.solve_mod2(&[3, 1]).unwrap(), vec![1, 0]);
+        assert_eq!(
+            a.solve_mod2(&[1]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "solve_mod2",
+                left: (2, 2),
+                right: (1, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn is_permutation_matrix() {
+        let permutation: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[0, 1, 0, 0, 0, 1, 1, 0, 0]);
+        assert!(permutation.is_permutation_matrix());
+        assert!(Matrix::<i64>::identity_matrix(4).is_permutation_matrix());
+
+        // Rows 0 and 2 both use column 1, leaving column 2 empty
+        let repeated_column: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[0, 1, 0, 1, 0, 0, 0, 1, 0]);
+        assert!(!repeated_column.is_permutation_matrix());
+
+        assert!(!Matrix::<i64>::square_matrix_from_list(&[0, -1, 1, 0]).is_permutation_matrix());
+        assert!(!Matrix::<i64>::square_matrix_from_list(&[1, 1, 0, 1]).is_permutation_matrix());
+        assert!(!Matrix::<i64>::matrix_from_list(&[1, 0, 0, 0, 1, 0], 2, 3).is_permutation_matrix());
+    }
+
+    #[test]
+    fn permutation_vector_round_trip() {
+        for permutation in [vec![], vec![0], vec![2, 0, 1], vec![3, 1, 0, 4, 2]] {
+            let matrix: Matrix<i64> = Matrix::permutation(&permutation).unwrap();
+            assert!(matrix.is_permutation_matrix());
+            assert_eq!(matrix.to_permutation_vector().unwrap(), permutation);
+        }
+
+        // Multiplying on the left moves row permutation[i] to position i, like permute_rows_in_place
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let mut permuted: Matrix<i64> = a.clone();
+        permuted.permute_rows_in_place(&[2, 0, 1]).unwrap();
+        assert_matrix_eq!(Matrix::permutation(&[2, 0, 1]).unwrap() * a, permuted);
+
+        assert_eq!(
+            Matrix::<i64>::permutation(&[0, 2, 2]).unwrap_err(),
+            "The list is not a valid permutation"
+        );
+        assert_eq!(
+            Matrix::<i64>::square_matrix_from_list(&[0, 1, 0, 1, 0, 0, 0, 1, 0])
+                .to_permutation_vector()
+                .unwrap_err(),
+            "The matrix is not a permutation matrix"
+        );
+        assert_eq!(
+            Matrix::<i64>::square_matrix_from_list(&[1, 0, 0, 2])
+                .to_permutation_vector()
+                .unwrap_err(),
+            "The matrix is not a permutation matrix"
+        );
+    }
+
+    #[test]
+    fn commutator() {
+        let diagonal_a: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[2, 0, 0, 0, -1, 0, 0, 0, 5]);
+        let diagonal_b: Matrix<i64> =
+            Matrix::square_matrix_from_list(&[7, 0, 0, 0, 3, 0, 0, 0, -4]);
+        assert_eq!(
+            diagonal_a.commutator(&diagonal_b).unwrap(),
+            Matrix::square_matrix(3)
+        );
+
+        // The Pauli matrices σx and σz satisfy [σx, σz] = -2iσy, which is real
+        let pauli_x: Matrix<i64> = Matrix::square_matrix_from_list(&[0, 1, 1, 0]);
+        let pauli_z: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 0, 0, -1]);
+        let commutator: Matrix<i64> = pauli_x.commutator(&pauli_z).unwrap();
+        assert_eq!(commutator, Matrix::square_matrix_from_list(&[0, -2, 2, 0]));
+        assert_eq!(pauli_z.commutator(&pauli_x).unwrap(), commutator * -1);
+
+        assert_eq!(
+            pauli_x.commutator(&diagonal_a).unwrap_err(),
+            "Both matrices must be square and of the same size"
+        );
+    }
+
+    #[test]
+    fn commutes_with() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+        // Polynomials in a matrix commute with it
+        let polynomial: Matrix<i64> = matrix.clone() * matrix.clone() * 3
+            + matrix.clone() * -2
+            + Matrix::identity_matrix(2) * 5;
+        assert!(matrix.commutes_with(&polynomial, 0).unwrap());
+        assert!(matrix
+            .commutes_with(&Matrix::identity_matrix(2), 0)
+            .unwrap());
+
+        let shear: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 1, 0, 1]);
+        assert!(!matrix.commutes_with(&shear, 0).unwrap());
+        assert_eq!(
+            matrix.commutes_with(&Matrix::new(2, 3), 0).unwrap_err(),
+            "Both matrices must be square and of the same size"
+        );
+    }
+
+    #[test]
+    fn frobenius_inner_product() {
+        let a: Matrix<i64> = Matrix::matrix_from_list(&[1, -2, 3, 0, 4, 5], 2, 3);
+        let b: Matrix<i64> = Matrix::matrix_from_list(&[6, 1, -1, 2, 2, -3], 2, 3);
+
+        let product: i64 = a.frobenius_inner_product(&b).unwrap();
+
+        assert_eq!(product, -6);
+        assert_eq!(product, (a.transpose() * b.clone()).trace().unwrap());
+        assert_eq!(product, b.frobenius_inner_product(&a).unwrap());
+        assert_eq!(a.frobenius_inner_product(&a).unwrap(), 55);
+        assert_eq!(
+            a.frobenius_inner_product(&a.transpose()).unwrap_err(),
+            "The inner product requires both matrices to have the same size"
+        );
+    }
+
+    #[test]
+    fn select_rows_and_columns() {
+        let matrix: Matrix<i64> =
+            Matrix::matrix_from_list(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], 4, 3);
+
+        assert_eq!(
+            matrix.select_rows(&[3, 0, 2]).unwrap(),
+            Matrix::matrix_from_list(&[10, 11, 12, 1, 2, 3, 7, 8, 9], 3, 3)
+        );
+        assert_eq!(
+            matrix.select_rows(&[1, 1]).unwrap(),
+            Matrix::matrix_from_list(&[4, 5, 6, 4, 5, 6], 2, 3)
+        );
+        assert_eq!(
+            matrix.select_columns(&[2, 2, 0]).unwrap(),
+            Matrix::matrix_from_list(&[3, 3, 1, 6, 6, 4, 9, 9, 7, 12, 12, 10], 4, 3)
+        );
+
+        let no_rows: Matrix<i64> = matrix.select_rows(&[]).unwrap();
+        assert_eq!((no_rows.rows(), no_rows.columns()), (0, 3));
+        let no_columns: Matrix<i64> = matrix.select_columns(&[]).unwrap();
+        assert_eq!((no_columns.rows(), no_columns.columns()), (4, 0));
+
+        assert_eq!(
+            matrix.select_rows(&[0, 4, 7]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "row",
+                position: 1,
+                index: 4,
+                bound: 4
+            }
+        );
+        assert_eq!(
+            matrix.select_columns(&[3]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "column",
+                position: 0,
+                index: 3,
+                bound: 3
+            }
+        );
+        assert_eq!(
+            matrix.select_rows(&[0, 4]).unwrap_err().to_string(),
+            "The row index 4 at position 1 is out of range, since it must be below 4"
+        );
+    }
+
+    #[test]
+    fn gather_cross_product() {
+        let matrix: Matrix<i64> =
+            Matrix::matrix_from_list(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], 3, 4);
+        let rows: &[usize] = &[2, 0, 2];
+        let columns: &[usize] = &[3, 1];
+
+        let gathered: Matrix<i64> = matrix.gather(rows, columns).unwrap();
+
+        assert_eq!(
+            gathered,
+            Matrix::matrix_from_list(&[12, 10, 4, 2, 12, 10], 3, 2)
+        );
+        assert_eq!(
+            gathered,
+            matrix
+                .select_columns(columns)
+                .unwrap()
+                .select_rows(rows)
+                .unwrap()
+        );
+        for (i, row) in rows.iter().enumerate() {
+            for (j, column) in columns.iter().enumerate() {
+                assert_eq!(gathered[i][j], matrix[*row][*column]);
+            }
+        }
+        assert_eq!(
+            matrix.gather(&[0], &[1, 4]).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "column",
+                position: 1,
+                index: 4,
+                bound: 4
+            }
+        );
+    }
+
+    #[test]
+    fn split_ldu() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[4, -1, 2, 3, 5, -6, 7, 8, 9]);
+
+        let (lower, diagonal, upper) = matrix.split_ldu();
+
+        assert_eq!(
+            lower,
+            Matrix::square_matrix_from_list(&[0, 0, 0, 3, 0, 0, 7, 8, 0])
+        );
+        assert_eq!(
+            diagonal,
+            Matrix::square_matrix_from_list(&[4, 0, 0, 0, 5, 0, 0, 0, 9])
+        );
+        assert_eq!(
+            upper,
+            Matrix::square_matrix_from_list(&[0, -1, 2, 0, 0, -6, 0, 0, 0])
+        );
+        assert_eq!(lower + diagonal + upper, matrix);
+
+        let rectangular: Matrix<i64> = Matrix::matrix_from_list(&[1, 2, 3, 4, 5, 6], 2, 3);
+        let (lower, diagonal, upper) = rectangular.split_ldu();
+        assert_eq!(
+            diagonal,
+            Matrix::matrix_from_list(&[1, 0, 0, 0, 5, 0], 2, 3)
+        );
+        assert_eq!(lower + diagonal + upper, rectangular);
+    }
+
+    #[test]
+    fn sort_rows_by_column_is_stable() {
+        // (id, score) rows with ties on the score
+        let results: Matrix<i64> = Matrix::matrix_from_list(&[0, 3, 1, 1, 2, 3, 3, 2, 4, 1], 5, 2);
+
+        assert_eq!(
+            results.argsort_column(1, false).unwrap(),
+            vec![1, 4, 3, 0, 2]
+        );
+        assert_eq!(
+            results.sort_rows_by_column(1, false).unwrap(),
+            Matrix::matrix_from_list(&[1, 1, 4, 1, 3, 2, 0, 3, 2, 3], 5, 2)
+        );
+        assert_eq!(
+            results.argsort_column(1, true).unwrap(),
+            vec![0, 2, 3, 1, 4]
+        );
+        assert_eq!(
+            results.sort_rows_by_column(1, true).unwrap(),
+            Matrix::matrix_from_list(&[0, 3, 2, 3, 3, 2, 1, 1, 4, 1], 5, 2)
+        );
+
+        assert_eq!(
+            results.argsort_column(2, false).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "column",
+                position: 0,
+                index: 2,
+                bound: 2,
+            }
+        );
+        assert_eq!(
+            results.sort_rows_by_column(2, true).unwrap_err(),
+            MatrixError::IndexOutOfRange {
+                axis: "column",
+                position: 0,
+                index: 2,
+                bound: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn unique_rows() {
+        let matrix: Matrix<i64> =
+            Matrix::matrix_from_list(&[1, 2, 3, 4, 1, 2, 5, 6, 3, 4, 1, 2], 6, 2);
+
+        let (unique, mapping) = matrix.unique_rows();
+
+        assert_eq!(unique, Matrix::matrix_from_list(&[1, 2, 3, 4, 5, 6], 3, 2));
+        assert_eq!(mapping, vec![0, 1, 0, 2, 1, 0]);
+        assert_eq!(unique.select_rows(&mapping).unwrap(), matrix);
+        assert_eq!(
+            matrix.row_counts(),
+            vec![(vec![1, 2], 3), (vec![3, 4], 2), (vec![5, 6], 1)]
+        );
+
+        let distinct: Matrix<i64> = Matrix::matrix_from_list(&[3, 1, 2, 1, 3, 2], 3, 2);
+        let (unique, mapping) = distinct.unique_rows();
+        assert_eq!(unique, distinct);
+        assert_eq!(mapping, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn push_row_builds_matrix() {
+        let rows: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![-1, 0, 1]];
+
+        let mut matrix: Matrix<i64> = Matrix::new(0, 0);
+        for row in &rows {
+            matrix.push_row(row).unwrap();
+        }
+
+        assert_eq!(matrix, Matrix::from_vector(&rows));
+        assert_eq!((matrix.rows(), matrix.columns()), (4, 3));
+        assert_eq!(
+            matrix.push_row(&[1, 2]).unwrap_err(),
+            MatrixError::RowLengthMismatch {
+                row: 4,
+                expected: 3,
+                actual: 2,
+            }
+        );
+        assert_eq!(matrix.rows(), 4);
+
+        // A matrix with columns but no rows keeps its width
+        let mut empty: Matrix<i64> = Matrix::new(0, 2);
+        assert_eq!(
+            empty.push_row(&[1, 2, 3]).unwrap_err(),
+            MatrixError::RowLengthMismatch {
+                row: 0,
+                expected: 2,
+                actual: 3,
+            }
+        );
+        empty.push_row(&[1, 2]).unwrap();
+        assert_eq!(empty, Matrix::matrix_from_list(&[1, 2], 1, 2));
+    }
+
+    #[test]
+    fn push_column_builds_matrix() {
+        let mut matrix: Matrix<i64> = Matrix::new(0, 0);
+        matrix.push_column(&[1, 4]).unwrap();
+        matrix.push_column(&[2, 5]).unwrap();
+        matrix.push_column(&[3, 6]).unwrap();
+
+        assert_eq!(matrix, Matrix::matrix_from_list(&[1, 2, 3, 4, 5, 6], 2, 3));
+        assert_eq!(
+            matrix.push_column(&[7, 8, 9]).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "push_column",
+                left: (2, 3),
+                right: (3, 1),
+            }
+        );
+
+        matrix.push_row(&[7, 8, 9]).unwrap();
+        matrix.push_column(&[0, 0, 0]).unwrap();
+        assert_eq!(
+            matrix,
+            Matrix::matrix_from_list(&[1, 2, 3, 0, 4, 5, 6, 0, 7, 8, 9, 0], 3, 4)
+        );
+    }
+
+    #[test]
+    fn extend_rows_stacks_vertically() {
+        let top: Matrix<i64> = Matrix::matrix_from_list(&[1, 2, 3, 4], 2, 2);
+        let bottom: Matrix<i64> = Matrix::matrix_from_list(&[5, 6, 7, 8, 9, 10], 3, 2);
+
+        let mut stacked: Matrix<i64> = top.clone();
+        stacked.extend_rows(&bottom).unwrap();
+
+        // Stacking vertically is combining side by side in the transposed space
+        assert_eq!(
+            stacked,
+            top.transpose()
+                .try_combine(&bottom.transpose())
+                .unwrap()
+                .transpose()
+        );
+
+        let mut empty: Matrix<i64> = Matrix::new(0, 0);
+        empty.extend_rows(&bottom).unwrap();
+        assert_eq!(empty, bottom);
+        assert_eq!(
+            stacked.extend_rows(&Matrix::new(1, 3)).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "extend_rows",
+                left: (5, 2),
+                right: (1, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn equality_is_symmetric() {
+        let smaller: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 2, 3, 4]);
+        let larger: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 5, 3, 4]);
+
+        assert!(smaller != larger);
+        assert!(larger != smaller);
+        assert!(!smaller.equals(&larger, 2));
+        assert!(!larger.equals(&smaller, 2));
+        assert!(smaller.equals(&larger, 3));
+        assert!(larger.equals(&smaller, 3));
+    }
+}
+
+#[cfg(test)]
+mod bitmatrixtests {
+    use crate::bit_matrix::{BitMatrix, CmpOp};
+    use crate::matrix::Matrix;
+    use crate::matrix_error::MatrixError;
+
+    // The parity-check matrix of the Hamming(7, 4) code, where column j is j + 1 in binary
+    const HAMMING_PARITY_CHECK: &[i32] = &[
+        0, 0, 0, 1, 1, 1, 1, //
+        0, 1, 1, 0, 0, 1, 1, //
+        1, 0, 1, 0, 1, 0, 1,
+    ];
+
+    /// Rank mod 2 by plain Gaussian elimination on the dense entries
+    fn slow_rank_mod_2(matrix: &Matrix<i32>) -> usize {
+        let mut rows: Vec<Vec<i32>> = (0..matrix.rows())
+            .map(|row| {
+                (0..matrix.columns())
+                    .map(|column| matrix[row][column].rem_euclid(2))
+                    .collect()
+            })
+            .collect();
+
+        let mut rank: usize = 0;
+        for column in 0..matrix.columns() {
+            if let Some(found) = (rank..rows.len()).find(|row| rows[*row][column] == 1) {
+                rows.swap(rank, found);
+                let pivot: Vec<i32> = rows[rank].clone();
+                for (row, values) in rows.iter_mut().enumerate() {
+                    if row != rank && values[column] == 1 {
+                        for (value, pivot_value) in values.iter_mut().zip(&pivot) {
+                            *value ^= *pivot_value;
+                        }
+                    }
+                }
+                rank += 1;
+            }
+        }
+
+        rank
+    }
+
+    /// Multiplies by the given vector mod 2
+    fn apply_mod_2(matrix: &Matrix<i32>, x: &[bool]) -> Vec<bool> {
+        (0..matrix.rows())
+            .map(|row| {
+                (0..matrix.columns())
+                    .filter(|column| x[*column])
+                    .map(|column| matrix[row][column])
+                    .sum::<i32>()
+                    % 2
+                    == 1
+            })
+            .collect()
+    }
+
+    /// A small xorshift generator, so the random tests don't need the rand feature
+    fn random_bits(rows: usize, columns: usize, seed: u64) -> Matrix<i32> {
+        let mut state: u64 = seed;
+        let mut output: Matrix<i32> = Matrix::new(rows, columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                output.set_value(row, column, (state >> 32) as i32 & 1);
+            }
+        }
+
+        output
+    }
+
+    #[test]
+    fn round_trip_conversions() {
+        let dense: Matrix<i32> = Matrix::matrix_from_list(HAMMING_PARITY_CHECK, 3, 7);
+        let bits: BitMatrix = BitMatrix::from_matrix(&dense);
+
+        assert_eq!(bits.rows(), 3);
+        assert_eq!(bits.columns(), 7);
+        assert_eq!(bits.to_matrix(), dense);
+        assert!(bits.get_value(2, 6));
+        assert!(!bits.get_value(0, 0));
+
+        // Non-zero entries of any type become ones
+        let signed: Matrix<f64> = Matrix::matrix_from_list(&[0.0, -2.5, 3.0, 0.0], 2, 2);
+        assert_eq!(
+            BitMatrix::from_matrix(&signed).to_matrix(),
+            Matrix::matrix_from_list(&[0, 1, 1, 0], 2, 2)
+        );
+
+        let rows: Vec<Vec<bool>> = vec![
+            (0..130).map(|column| column % 3 == 0).collect(),
+            (0..130).map(|column| column % 64 == 63).collect(),
+        ];
+        let wide: BitMatrix = BitMatrix::from_bool_rows(&rows);
+        assert_eq!(BitMatrix::from_matrix(&wide.to_matrix()), wide);
+        for (row, values) in rows.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                assert_eq!(wide.get_value(row, column), *value);
+            }
+        }
+
+        let empty: BitMatrix = BitMatrix::from_bool_rows(&[]);
+        assert_eq!((empty.rows(), empty.columns()), (0, 0));
+        assert_eq!(empty, BitMatrix::new(0, 0));
+    }
+
+    #[test]
+    fn addition_and_multiplication() {
+        let a: Matrix<i32> = Matrix::square_matrix_from_list(&[1, 1, 0, 0, 1, 1, 1, 0, 1]);
+        let b: Matrix<i32> = Matrix::square_matrix_from_list(&[0, 1, 1, 1, 1, 0, 1, 0, 0]);
+        let bits_a: BitMatrix = BitMatrix::from_matrix(&a);
+        let bits_b: BitMatrix = BitMatrix::from_matrix(&b);
+
+        assert_eq!(
+            (bits_a.clone() + bits_b.clone()).to_matrix(),
+            Matrix::square_matrix_from_list(&[1, 0, 1, 1, 0, 1, 0, 0, 1])
+        );
+        assert_eq!(bits_a.clone() + bits_a.clone(), BitMatrix::new(3, 3));
+
+        // The integer product reduced mod 2
+        let product: Matrix<i32> = a.clone() * b.clone();
+        let mut expected: Matrix<i32> = Matrix::new(3, 3);
+        for row in 0..3 {
+            for column in 0..3 {
+                expected.set_value(row, column, product[row][column] % 2);
+            }
+        }
+        assert_eq!((bits_a.clone() * bits_b).to_matrix(), expected);
+        assert_eq!(bits_a.clone() * BitMatrix::identity_matrix(3), bits_a);
+
+        assert_eq!(
+            bits_a.try_add(&BitMatrix::new(3, 4)).unwrap_err(),
+            "Addition requires both matrices to have the same size"
+        );
+        assert_eq!(
+            bits_a.try_mul(&BitMatrix::new(4, 3)).unwrap_err(),
+            "Multiplication requires the left hand columns to equal the right hand rows"
+        );
+    }
+
+    #[test]
+    fn parity_check_rank_solve_and_nullspace() {
+        let dense: Matrix<i32> = Matrix::matrix_from_list(HAMMING_PARITY_CHECK, 3, 7);
+        let bits: BitMatrix = BitMatrix::from_matrix(&dense);
+
+        assert_eq!(bits.rank_gf2(), 3);
+        assert_eq!(bits.rank_gf2(), slow_rank_mod_2(&dense));
+
+        // A single flipped bit has its position as the syndrome, so solving finds an error pattern that explains it
+        let syndrome: Vec<bool> = vec![true, false, true];
+        let error: Vec<bool> = bits.solve_gf2(&syndrome).unwrap();
+        assert_eq!(apply_mod_2(&dense, &error), syndrome);
+
+        // The null space is the Hamming code itself, with 2^4 codewords
+        let code: Vec<Vec<bool>> = bits.nullspace_gf2();
+        assert_eq!(code.len(), 4);
+        for codeword in &code {
+            assert_eq!(apply_mod_2(&dense, codeword), vec![false; 3]);
+        }
+        let basis: BitMatrix = BitMatrix::from_bool_rows(&code);
+        assert_eq!(basis.rank_gf2(), 4);
+
+        // A square system agrees with the dense modular solver
+        let square: Matrix<i32> = Matrix::square_matrix_from_list(&[
+            1, 1, 0, 1, //
+            1, 0, 1, 1, //
+            0, 1, 1, 1, //
+            1, 0, 0, 1,
+        ]);
+        let b: Vec<bool> = vec![true, false, true, true];
+        let b_mod: Vec<i64> = b.iter().map(|value| *value as i64).collect();
+        let square_i64: Matrix<i64> = Matrix::matrix_from_list(
+            &(0..16)
+                .map(|i| square[i / 4][i % 4] as i64)
+                .collect::<Vec<i64>>(),
+            4,
+            4,
+        );
+        let expected: Vec<bool> = square_i64
+            .solve_mod(&b_mod, 2)
+            .unwrap()
+            .iter()
+            .map(|value| *value == 1)
+            .collect();
+        assert_eq!(
+            BitMatrix::from_matrix(&square).solve_gf2(&b).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn solve_gf2_errors() {
+        let bits: BitMatrix = BitMatrix::from_bool_rows(&[vec![true, true], vec![true, true]]);
+
+        assert_eq!(
+            bits.solve_gf2(&[true, false]).unwrap_err(),
+            "The system was inconsistent and there is no solution for b."
+        );
+        assert_eq!(
+            bits.solve_gf2(&[true]).unwrap_err(),
+            "Your b vector is not the correct length!"
+        );
+        assert_eq!(bits.solve_gf2(&[true, true]).unwrap(), vec![true, false]);
+    }
+
+    #[test]
+    fn wide_random_matrix_matches_slow_reference() {
+        let dense: Matrix<i32> = random_bits(40, 1000, 0x9E37_79B9_7F4A_7C15);
+        let bits: BitMatrix = BitMatrix::from_matrix(&dense);
+        assert_eq!(bits.rank_gf2(), slow_rank_mod_2(&dense));
+
+        // Stacking rows that are sums of others leaves the rank unchanged
+        let mut dependent: Matrix<i32> = Matrix::new(60, 1000);
+        for row in 0..60 {
+            for column in 0..1000 {
+                let value: i32 = if row < 40 {
+                    dense[row][column]
+                } else {
+                    dense[row - 40][column] ^ dense[row - 39][column]
+                };
+                dependent.set_value(row, column, value);
+            }
+        }
+        let dependent_bits: BitMatrix = BitMatrix::from_matrix(&dependent);
+        assert_eq!(dependent_bits.rank_gf2(), slow_rank_mod_2(&dependent));
+        assert_eq!(dependent_bits.rank_gf2(), bits.rank_gf2());
+
+        // Any b in the column space is solved exactly
+        let x: Vec<bool> = (0..1000).map(|column| column % 7 == 3).collect();
+        let b: Vec<bool> = apply_mod_2(&dependent, &x);
+        let solution: Vec<bool> = dependent_bits.solve_gf2(&b).unwrap();
+        assert_eq!(apply_mod_2(&dependent, &solution), b);
+
+        let null_space: Vec<Vec<bool>> = dependent_bits.nullspace_gf2();
+        assert_eq!(null_space.len(), 1000 - bits.rank_gf2());
+        for vector in null_space.iter().step_by(97) {
+            assert_eq!(apply_mod_2(&dependent, vector), vec![false; 60]);
+        }
+
+        // Tall matrices reduce the same way as wide ones
+        let tall: Matrix<i32> = random_bits(200, 70, 12345);
+        assert_eq!(
+            BitMatrix::from_matrix(&tall).rank_gf2(),
+            slow_rank_mod_2(&tall)
+        );
+        assert_eq!(
+            BitMatrix::from_matrix(&tall)
+                .reduced_echelon_form()
+                .rank_gf2(),
+            slow_rank_mod_2(&tall)
+        );
+    }
+
+    #[test]
+    fn compare_scalar_masks() {
+        let matrix: Matrix<f64> =
+            Matrix::matrix_from_list(&[-1.5, 0.0, 2.0, 3.5, -0.25, 0.0], 2, 3);
+
+        let positive: BitMatrix = matrix.compare_scalar(CmpOp::Gt, 0.0);
+        assert_eq!(
+            positive,
+            BitMatrix::from_bool_rows(&[vec![false, false, true], vec![true, false, false]])
+        );
+        assert_eq!(positive.count_true(), 2);
+        assert_eq!(matrix.compare_scalar(CmpOp::Ge, 0.0).count_true(), 4);
+        assert_eq!(matrix.compare_scalar(CmpOp::Lt, 0.0).count_true(), 2);
+        assert_eq!(matrix.compare_scalar(CmpOp::Le, 2.0).count_true(), 5);
+        assert_eq!(matrix.compare_scalar(CmpOp::Eq(0.0), 0.0).count_true(), 2);
+        assert_eq!(matrix.compare_scalar(CmpOp::Ne(0.0), 0.0).count_true(), 4);
+    }
+
+    #[test]
+    fn compare_float_equality_tolerance() {
+        let computed: Matrix<f64> = Matrix::matrix_from_list(&[0.1 + 0.2, 1.0, 2.0 + 1e-6], 1, 3);
+        let expected: Matrix<f64> = Matrix::matrix_from_list(&[0.3, 1.0, 2.0], 1, 3);
+
+        assert_eq!(
+            computed
+                .compare(&expected, CmpOp::Eq(0.0))
+                .unwrap()
+                .count_true(),
+            1
+        );
+        assert_eq!(
+            computed
+                .compare(&expected, CmpOp::Eq(1e-12))
+                .unwrap()
+                .count_true(),
+            2
+        );
+        assert!(computed
+            .compare(&expected, CmpOp::Eq(1e-5))
+            .unwrap()
+            .all_true());
+        assert!(!computed
+            .compare(&expected, CmpOp::Ne(1e-5))
+            .unwrap()
+            .any_true());
+        assert!(computed
+            .compare(&expected, CmpOp::Ne(1e-12))
+            .unwrap()
+            .any_true());
+
+        // NaN is never equal to anything, so Ne holds wherever either side is NaN
+        let with_nan: Matrix<f64> = Matrix::matrix_from_list(&[f64::NAN, 1.0, 2.0], 1, 3);
+        assert_eq!(
+            with_nan.compare(&expected, CmpOp::Ne(1e-5)).unwrap(),
+            BitMatrix::from_bool_rows(&[vec![true, false, false]])
+        );
+        assert_eq!(
+            expected.compare(&with_nan, CmpOp::Ne(1e-5)).unwrap(),
+            BitMatrix::from_bool_rows(&[vec![true, false, false]])
+        );
+        assert!(!with_nan
+            .compare_scalar(CmpOp::Eq(1e-5), f64::NAN)
+            .any_true());
+        assert!(with_nan
+            .compare_scalar(CmpOp::Ne(1e-5), f64::NAN)
+            .all_true());
+
+        // A negative tolerance makes every pair unequal, even identical values
+        assert!(!expected
+            .compare(&expected, CmpOp::Eq(-1.0))
+            .unwrap()
+            .any_true());
+        assert!(expected
+            .compare(&expected, CmpOp::Ne(-1.0))
+            .unwrap()
+            .all_true());
+    }
+
+    #[test]
+    fn compare_integers_and_reductions() {
+        let a: Matrix<i64> = Matrix::square_matrix_from_list(&[1, 5, 3, 7]);
+        let b: Matrix<i64> = Matrix::square_matrix_from_list(&[2, 5, 1, 9]);
+
+        assert_eq!(
+            a.compare(&b, CmpOp::Lt).unwrap(),
+            BitMatrix::from_bool_rows(&[vec![true, false], vec![false, true]])
+        );
+        assert!(a.compare(&b, CmpOp::Le).unwrap().any_true());
+        assert!(!a.compare(&b, CmpOp::Le).unwrap().all_true());
+        assert!(a.compare(&a, CmpOp::Eq(0)).unwrap().all_true());
+        assert!(!a.compare(&a, CmpOp::Gt).unwrap().any_true());
+
+        // Wide masks span several words
+        let wide: Matrix<i64> = Matrix::new(2, 130);
+        assert!(wide.compare_scalar(CmpOp::Eq(0), 0).all_true());
+        assert_eq!(wide.compare_scalar(CmpOp::Eq(0), 0).count_true(), 260);
+        assert!(BitMatrix::new(0, 0).all_true());
+
+        assert_eq!(
+            a.compare(&Matrix::new(2, 3), CmpOp::Eq(0)).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "compare",
+                left: (2, 2),
+                right: (2, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn select_row_major() {
+        let matrix: Matrix<i64> = Matrix::square_matrix_from_list(&[4, -1, 7, 0, 9, -3, 2, 8, 5]);
+
+        let mask: BitMatrix = matrix.compare_scalar(CmpOp::Gt, 3);
+        assert_eq!(matrix.select(&mask).unwrap(), vec![4, 7, 9, 8, 5]);
+
+        assert_eq!(
+            matrix.select(&BitMatrix::new(3, 3)).unwrap(),
+            Vec::<i64>::new()
+        );
+        let everything: BitMatrix =
+            BitMatrix::from_bool_rows(&[vec![true; 3], vec![true; 3], vec![true; 3]]);
+        assert_eq!(
+            matrix.select(&everything).unwrap(),
+            vec![4, -1, 7, 0, 9, -3, 2, 8, 5]
+        );
+    }
+
+    #[test]
+    fn set_where_clips_outliers() {
+        let mut matrix: Matrix<f64> =
+            Matrix::matrix_from_list(&[0.5, 12.0, -3.0, 40.0, 9.9, 10.0], 2, 3);
+        let cap: f64 = 10.0;
+
+        let mask: BitMatrix = matrix.compare_scalar(CmpOp::Gt, cap);
+        matrix.set_where(&mask, cap).unwrap();
+
+        assert_eq!(
+            matrix,
+            Matrix::matrix_from_list(&[0.5, 10.0, -3.0, 10.0, 9.9, 10.0], 2, 3)
+        );
+
+        let untouched: Matrix<f64> = matrix.clone();
+        matrix.set_where(&BitMatrix::new(2, 3), 0.0).unwrap();
+        assert_eq!(matrix, untouched);
+        let all: BitMatrix = BitMatrix::from_bool_rows(&[vec![true; 3], vec![true; 3]]);
+        matrix.set_where(&all, 1.0).unwrap();
+        assert_eq!(matrix, Matrix::matrix_from_list(&[1.0; 6], 2, 3));
+    }
+
+    #[test]
+    fn assign_where_imputes_sentinels() {
+        let sentinel: i64 = -999;
+        let mut readings: Matrix<i64> = Matrix::square_matrix_from_list(&[3, -999, 5, -999]);
+        let fallback: Matrix<i64> = Matrix::square_matrix_from_list(&[30, 40, 50, 60]);
+
+        let missing: BitMatrix = readings.compare_scalar(CmpOp::Eq(0), sentinel);
+        readings.assign_where(&missing, &fallback).unwrap();
+
+        assert_eq!(readings, Matrix::square_matrix_from_list(&[3, 40, 5, 60]));
+    }
+
+    #[test]
+    fn masked_shape_errors() {
+        let mut matrix: Matrix<i64> = Matrix::square_matrix(2);
+        let wrong_mask: BitMatrix = BitMatrix::new(2, 3);
+
+        assert_eq!(
+            matrix.select(&wrong_mask).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "select",
+                left: (2, 2),
+                right: (2, 3),
+            }
+        );
+        assert_eq!(
+            matrix.set_where(&wrong_mask, 1).unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "set_where",
+                left: (2, 2),
+                right: (2, 3),
+            }
+        );
+        assert_eq!(
+            matrix
+                .assign_where(&BitMatrix::new(2, 2), &Matrix::new(3, 2))
+                .unwrap_err(),
+            MatrixError::DimensionMismatch {
+                op: "assign_where",
+                left: (2, 2),
+                right: (3, 2),
+            }
+        );
     }
 }
@@ -0,0 +1,191 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains an LU factorization that can be reused to solve many systems with the same matrix
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+
+/// Holds the LU factorization (with partial pivoting) of a square matrix A, such that PA = LU.
+/// L is unit lower triangular and U is upper triangular, and both are packed into a single matrix
+#[derive(Debug, Clone)]
+pub struct LinearSolver<T>
+where
+    T: MatrixCompatible,
+{
+    lu: Matrix<T>,
+    // Row i of PA is row permutation[i] of A
+    permutation: Vec<usize>,
+    swap_count: usize,
+}
+
+impl<T> LinearSolver<T>
+where
+    T: MatrixCompatible + PartialOrd,
+{
+    /// Factorizes the given matrix, erroring if it isn't square or is singular
+    pub fn new(matrix: &Matrix<T>) -> Result<Self, MatrixError> {
+        if matrix.rows() != matrix.columns() {
+            return Err(MatrixError::NotSquare {
+                rows: matrix.rows(),
+                columns: matrix.columns(),
+            });
+        }
+
+        let size: usize = matrix.rows();
+        let mut lu: Matrix<T> = matrix.clone();
+        let mut permutation: Vec<usize> = (0..size).collect();
+        let mut swap_count: usize = 0;
+
+        for pivot in 0..size {
+            // Partial pivoting: use the remaining row with the largest entry in this column
+            let mut pivot_row: usize = pivot;
+            for row in pivot + 1..size {
+                if lu[row][pivot].abs() > lu[pivot_row][pivot].abs() {
+                    pivot_row = row;
+                }
+            }
+
+            if lu[pivot_row][pivot] == T::zero() {
+                return Err(MatrixError::Singular);
+            }
+
+            if pivot_row != pivot {
+                for column in 0..size {
+                    let value: T = lu[pivot][column];
+                    lu.set_value(pivot, column, lu[pivot_row][column]);
+                    lu.set_value(pivot_row, column, value);
+                }
+                permutation.swap(pivot, pivot_row);
+                swap_count += 1;
+            }
+
+            for row in pivot + 1..size {
+                let factor: T = lu[row][pivot] / lu[pivot][pivot];
+                lu.set_value(row, pivot, factor);
+                for column in pivot + 1..size {
+                    let value: T = lu[row][column] - factor * lu[pivot][column];
+                    lu.set_value(row, column, value);
+                }
+            }
+        }
+
+        Ok(Self {
+            lu,
+            permutation,
+            swap_count,
+        })
+    }
+
+    /// Returns the size of the factorized matrix
+    pub fn size(&self) -> usize {
+        self.lu.rows()
+    }
+
+    /// Solves Ax = b using the stored factorization. Errors if b doesn't have one entry per row
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        let size: usize = self.size();
+        if b.len() != size {
+            return Err(MatrixError::DimensionMismatch {
+                op: "solve",
+                left: (size, size),
+                right: (b.len(), 1),
+            });
+        }
+
+        // Forward substitution with L, which has ones on its diagonal
+        let mut x: Vec<T> = Vec::with_capacity(size);
+        for row in 0..size {
+            let mut value: T = b[self.permutation[row]];
+            // x currently holds the solved entries above this row
+            for (column, x_value) in x.iter().enumerate() {
+                value -= self.lu[row][column] * *x_value;
+            }
+            x.push(value);
+        }
+
+        // Back substitution with U
+        for row in (0..size).rev() {
+            let mut value: T = x[row];
+            for (column, x_value) in x.iter().enumerate().skip(row + 1) {
+                value -= self.lu[row][column] * *x_value;
+            }
+            x[row] = value / self.lu[row][row];
+        }
+
+        Ok(x)
+    }
+
+    /// Solves AX = B for every column of B at once. Errors if B doesn't have one row per row of A
+    pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if b.rows() != self.size() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "solve_matrix",
+                left: (self.size(), self.size()),
+                right: (b.rows(), b.columns()),
+            });
+        }
+
+        let mut output: Matrix<T> = Matrix::new(b.rows(), b.columns());
+        for column in 0..b.columns() {
+            let b_column: Vec<T> = (0..b.rows()).map(|row| b[row][column]).collect();
+            for (row, value) in self.solve(&b_column)?.into_iter().enumerate() {
+                output.set_value(row, column, value);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Returns the determinant of the factorized matrix, which is the product of the pivots up to the sign of the permutation
+    pub fn determinant(&self) -> T {
+        let mut determinant: T = T::one();
+        for i in 0..self.size() {
+            determinant *= self.lu[i][i];
+        }
+
+        if self.swap_count % 2 == 1 {
+            determinant = determinant.neg();
+        }
+
+        determinant
+    }
+
+    /// Returns the inverse of the factorized matrix
+    pub fn inverse(&self) -> Matrix<T> {
+        let identity_matrix: Matrix<T> = Matrix::identity_matrix(self.size());
+
+        // The factorization succeeded, so the matrix is invertible and the identity has the correct size
+        self.solve_matrix(&identity_matrix).unwrap()
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + PartialOrd,
+{
+    /// Factorizes this matrix once so that it can be reused for many solves. Errors if the matrix is singular
+    pub fn factorize(&self) -> Result<LinearSolver<T>, MatrixError> {
+        LinearSolver::new(self)
+    }
+
+    /// Calculates the trace of the inverse without forming it, by solving Ax = e_i for each standard basis vector with
+    /// one factorization and summing the i-th entry of each solution. Errors if the matrix is singular
+    pub fn trace_of_inverse(&self) -> Result<T, &'static str> {
+        if self.rows() != self.columns() {
+            return Err("The matrix was not square");
+        }
+        let solver: LinearSolver<T> = self.factorize().map_err(|_| "Matrix is not invertible")?;
+        let size: usize = solver.size();
+
+        let mut trace: T = T::zero();
+        let mut basis_vector: Vec<T> = vec![T::zero(); size];
+        for i in 0..size {
+            basis_vector[i] = T::one();
+            // The basis vector has an entry for each row, so this can't fail
+            trace += solver.solve(&basis_vector).unwrap()[i];
+            basis_vector[i] = T::zero();
+        }
+
+        Ok(trace)
+    }
+}
@@ -2,7 +2,14 @@
 /// https://github.com/BraydenJonsson/rust-matrix
 ///
 /// Contains a struct and methods for representing a mathematical matrix
-use num_traits;
+use crate::linear_solver::LinearSolver;
+use crate::matrix_builder::MatrixBuilder;
+use crate::matrix_error::MatrixError;
+use crate::semiring::MinPlus;
+#[cfg(feature = "rand")]
+use rand::{seq::SliceRandom, Rng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::cmp;
 use std::ops;
 use trait_set::trait_set;
@@ -47,17 +54,17 @@ where
     }
 
     /// Creates a new matrix from the given 2D vector array. The array must have consistent rectangular sizing
-    pub fn from_vector(vector: &Vec<Vec<T>>) -> Self {
-        let rows: usize = vector.capacity();
-        let columns: usize = vector[0].capacity();
+    pub fn from_vector(vector: &[Vec<T>]) -> Self {
+        let rows: usize = vector.len();
+        let columns: usize = vector[0].len();
 
         for row in vector {
-            if columns != row.capacity() {
+            if columns != row.len() {
                 panic!("This matrix doesn't have equal column sizes!")
             }
         }
 
-        let matrix: Vec<Vec<T>> = vector.clone();
+        let matrix: Vec<Vec<T>> = vector.to_vec();
 
         Self {
             matrix,
@@ -77,9 +84,34 @@ where
         matrix
     }
 
+    /// Creates a new square matrix with the given list along its diagonal and zeros elsewhere
+    pub fn from_diagonal(diagonal: &[T]) -> Self {
+        let mut matrix: Self = Self::square_matrix(diagonal.len());
+
+        for (i, value) in diagonal.iter().enumerate() {
+            matrix.set_value(i, i, *value);
+        }
+
+        matrix
+    }
+
+    /// Creates the permutation matrix whose row i has its one in column permutation\[i\], so multiplying on the left
+    /// moves row permutation\[i\] to position i. Errors unless the list is a permutation of 0..length
+    pub fn permutation(permutation: &[usize]) -> Result<Self, &'static str> {
+        Self::validate_permutation(permutation, permutation.len())
+            .map_err(|_| "The list is not a valid permutation")?;
+
+        let mut matrix: Self = Self::square_matrix(permutation.len());
+        for (row, column) in permutation.iter().enumerate() {
+            matrix.set_value(row, *column, T::one());
+        }
+
+        Ok(matrix)
+    }
+
     /// Constructs a new square matrix from the given list of numbers, listed left-to-right, up-to-down.
     /// The length of the list must be a perfect square.
-    pub fn square_matrix_from_list(list_of_numbers: &Vec<T>) -> Self {
+    pub fn square_matrix_from_list(list_of_numbers: &[T]) -> Self {
         let list_length: f64 = list_of_numbers.len() as f64;
         if f64::sqrt(list_length).fract() != 0.0 {
             panic!("This list size is not a perfect square!");
@@ -101,7 +133,7 @@ where
 
     /// Constructs a new matrix from the given list of numbers, listed left-to-right, up-to-down.
     /// The length of the list must be match the dimensions
-    pub fn matrix_from_list(list_of_numbers: &Vec<T>, rows: usize, columns: usize) -> Self {
+    pub fn matrix_from_list(list_of_numbers: &[T], rows: usize, columns: usize) -> Self {
         if list_of_numbers.len() != rows * columns {
             panic!("This list size does not match the dimensions!");
         }
@@ -119,10 +151,290 @@ where
         matrix
     }
 
+    /// Constructs a new matrix from an iterator of rows, such as when the number of rows isn't known ahead of time.
+    /// Every row must have the same length, and there must be at least one row. Errors with the index and length of
+    /// the first row that doesn't match, just as MatrixBuilder::push_row does
+    pub fn from_row_iter<I>(rows: I) -> Result<Self, MatrixError>
+    where
+        I: IntoIterator<Item = Vec<T>>,
+    {
+        let mut builder: MatrixBuilder<T> = MatrixBuilder::new();
+        builder.push_rows(rows)?;
+        builder.build()
+    }
+
     // -----PRIVATE HELPERS-----
 
+    /// Calculates the determinant exactly with Bareiss elimination, where every division is exact and every
+    /// intermediate value is a minor of the original matrix. Errors if an intermediate value overflows
+    fn bareiss_determinant(mut matrix: Vec<Vec<i128>>) -> Result<i128, &'static str> {
+        let size: usize = matrix.len();
+        let mut sign: i128 = 1;
+        let mut previous_pivot: i128 = 1;
+
+        for pivot in 0..size {
+            match (pivot..size).find(|row| matrix[*row][pivot] != 0) {
+                Some(row) if row != pivot => {
+                    matrix.swap(row, pivot);
+                    sign = -sign;
+                }
+                Some(_) => {}
+                None => return Ok(0),
+            }
+
+            let pivot_row: Vec<i128> = matrix[pivot].clone();
+            for row in matrix.iter_mut().skip(pivot + 1) {
+                let factor: i128 = row[pivot];
+                for column in pivot + 1..size {
+                    let value: i128 = row[column]
+                        .checked_mul(pivot_row[pivot])
+                        .zip(factor.checked_mul(pivot_row[column]))
+                        .and_then(|(a, b)| a.checked_sub(b))
+                        .ok_or("An intermediate value of the determinant overflowed")?;
+                    row[column] = value / previous_pivot;
+                }
+            }
+            previous_pivot = pivot_row[pivot];
+        }
+
+        if size == 0 {
+            return Ok(1);
+        }
+        Ok(sign * matrix[size - 1][size - 1])
+    }
+
+    /// The largest size of matrix that inverse and determinant calculate with closed-form cofactor formulas
+    const CLOSED_FORM_MAX_SIZE: usize = 4;
+
+    /// Calculates the inverse as the adjugate divided by the determinant, erroring if is_singular returns true for the
+    /// determinant. Only valid for matrices between 1x1 and 4x4, where every entry of the adjugate is written out
+    fn closed_form_inverse<F>(&self, is_singular: F) -> Result<Self, &'static str>
+    where
+        F: Fn(T) -> bool,
+    {
+        let determinant: T = self.closed_form_determinant();
+
+        if is_singular(determinant) {
+            return Err("Matrix is not invertible");
+        }
+
+        let a: &Vec<Vec<T>> = &self.matrix;
+        let adjugate: Vec<Vec<T>> = match self.rows {
+            1 => vec![vec![T::one()]],
+            2 => vec![vec![a[1][1], -a[0][1]], vec![-a[1][0], a[0][0]]],
+            3 => vec![
+                vec![
+                    a[1][1] * a[2][2] - a[1][2] * a[2][1],
+                    a[0][2] * a[2][1] - a[0][1] * a[2][2],
+                    a[0][1] * a[1][2] - a[0][2] * a[1][1],
+                ],
+                vec![
+                    a[1][2] * a[2][0] - a[1][0] * a[2][2],
+                    a[0][0] * a[2][2] - a[0][2] * a[2][0],
+                    a[0][2] * a[1][0] - a[0][0] * a[1][2],
+                ],
+                vec![
+                    a[1][0] * a[2][1] - a[1][1] * a[2][0],
+                    a[0][1] * a[2][0] - a[0][0] * a[2][1],
+                    a[0][0] * a[1][1] - a[0][1] * a[1][0],
+                ],
+            ],
+            _ => {
+                let (s, c): ([T; 6], [T; 6]) = self.four_by_four_minors();
+                vec![
+                    vec![
+                        a[1][1] * c[5] - a[1][2] * c[4] + a[1][3] * c[3],
+                        -a[0][1] * c[5] + a[0][2] * c[4] - a[0][3] * c[3],
+                        a[3][1] * s[5] - a[3][2] * s[4] + a[3][3] * s[3],
+                        -a[2][1] * s[5] + a[2][2] * s[4] - a[2][3] * s[3],
+                    ],
+                    vec![
+                        -a[1][0] * c[5] + a[1][2] * c[2] - a[1][3] * c[1],
+                        a[0][0] * c[5] - a[0][2] * c[2] + a[0][3] * c[1],
+                        -a[3][0] * s[5] + a[3][2] * s[2] - a[3][3] * s[1],
+                        a[2][0] * s[5] - a[2][2] * s[2] + a[2][3] * s[1],
+                    ],
+                    vec![
+                        a[1][0] * c[4] - a[1][1] * c[2] + a[1][3] * c[0],
+                        -a[0][0] * c[4] + a[0][1] * c[2] - a[0][3] * c[0],
+                        a[3][0] * s[4] - a[3][1] * s[2] + a[3][3] * s[0],
+                        -a[2][0] * s[4] + a[2][1] * s[2] - a[2][3] * s[0],
+                    ],
+                    vec![
+                        -a[1][0] * c[3] + a[1][1] * c[1] - a[1][2] * c[0],
+                        a[0][0] * c[3] - a[0][1] * c[1] + a[0][2] * c[0],
+                        -a[3][0] * s[3] + a[3][1] * s[1] - a[3][2] * s[0],
+                        a[2][0] * s[3] - a[2][1] * s[1] + a[2][2] * s[0],
+                    ],
+                ]
+            }
+        };
+
+        let matrix: Vec<Vec<T>> = adjugate
+            .into_iter()
+            .map(|row| row.into_iter().map(|value| value / determinant).collect())
+            .collect();
+
+        Ok(Self {
+            matrix,
+            rows: self.rows,
+            columns: self.columns,
+        })
+    }
+
+    /// Calculates the determinant of a matrix between 0x0 and 4x4 without any allocation
+    fn closed_form_determinant(&self) -> T {
+        let a: &Vec<Vec<T>> = &self.matrix;
+
+        match self.rows {
+            0 => T::one(),
+            1 => a[0][0],
+            2 => a[0][0] * a[1][1] - a[0][1] * a[1][0],
+            3 => {
+                a[0][0] * (a[1][1] * a[2][2] - a[1][2] * a[2][1])
+                    - a[0][1] * (a[1][0] * a[2][2] - a[1][2] * a[2][0])
+                    + a[0][2] * (a[1][0] * a[2][1] - a[1][1] * a[2][0])
+            }
+            _ => {
+                let (s, c): ([T; 6], [T; 6]) = self.four_by_four_minors();
+                s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+            }
+        }
+    }
+
+    /// Calculates the six 2x2 minors of the top two rows and the six 2x2 minors of the bottom two rows of a 4x4
+    /// matrix, which the 4x4 determinant and adjugate are built from
+    fn four_by_four_minors(&self) -> ([T; 6], [T; 6]) {
+        let a: &Vec<Vec<T>> = &self.matrix;
+        let top = |i: usize, j: usize| a[0][i] * a[1][j] - a[1][i] * a[0][j];
+        let bottom = |i: usize, j: usize| a[2][i] * a[3][j] - a[3][i] * a[2][j];
+
+        (
+            [
+                top(0, 1),
+                top(0, 2),
+                top(0, 3),
+                top(1, 2),
+                top(1, 3),
+                top(2, 3),
+            ],
+            [
+                bottom(0, 1),
+                bottom(0, 2),
+                bottom(0, 3),
+                bottom(1, 2),
+                bottom(1, 3),
+                bottom(2, 3),
+            ],
+        )
+    }
+
+    /// Pads this matrix for the convolution mode, then slides the kernel over every position where it fits, flipping
+    /// it first for a convolution. For even kernel sizes in Same mode, the extra row and column of padding go on the
+    /// top and left
+    fn sliding_window(
+        &self,
+        kernel: &Self,
+        mode: ConvolutionMode,
+        padding: Padding,
+        flip: bool,
+    ) -> Result<Self, MatrixError> {
+        if kernel.rows == 0 || kernel.columns == 0 {
+            return Err(MatrixError::InvalidArgument {
+                name: "kernel",
+                requirement: "must not be empty",
+            });
+        }
+
+        let (top, bottom, left, right): (usize, usize, usize, usize) = match mode {
+            ConvolutionMode::Valid => (0, 0, 0, 0),
+            ConvolutionMode::Same => (
+                kernel.rows / 2,
+                (kernel.rows - 1) / 2,
+                kernel.columns / 2,
+                (kernel.columns - 1) / 2,
+            ),
+            ConvolutionMode::Full => (
+                kernel.rows - 1,
+                kernel.rows - 1,
+                kernel.columns - 1,
+                kernel.columns - 1,
+            ),
+        };
+        let input: Self = self.padded(top, bottom, left, right, padding);
+
+        if kernel.rows > input.rows || kernel.columns > input.columns {
+            let op: &'static str = if flip { "convolve_2d" } else { "correlate_2d" };
+            return Err(self.mismatch(op, kernel.rows, kernel.columns));
+        }
+
+        let mut result: Self = Self::new(
+            input.rows - kernel.rows + 1,
+            input.columns - kernel.columns + 1,
+        );
+        for row in 0..result.rows {
+            for column in 0..result.columns {
+                let mut sum: T = T::zero();
+                for kernel_row in 0..kernel.rows {
+                    for kernel_column in 0..kernel.columns {
+                        let weight: T = if flip {
+                            kernel[kernel.rows - 1 - kernel_row][kernel.columns - 1 - kernel_column]
+                        } else {
+                            kernel[kernel_row][kernel_column]
+                        };
+                        sum += input[row + kernel_row][column + kernel_column] * weight;
+                    }
+                }
+                result.set_value(row, column, sum);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Calculates the output size of pooling over this matrix, checking the pool size and stride are non-zero. Partial
+    /// windows are only counted if they start inside the matrix
+    fn pool_output_size(
+        &self,
+        pool_rows: usize,
+        pool_columns: usize,
+        stride_rows: usize,
+        stride_columns: usize,
+        include_partial: bool,
+    ) -> Result<(usize, usize), MatrixError> {
+        if pool_rows == 0 || pool_columns == 0 {
+            return Err(MatrixError::InvalidArgument {
+                name: "pool size",
+                requirement: "must be non-zero",
+            });
+        }
+        if stride_rows == 0 || stride_columns == 0 {
+            return Err(MatrixError::InvalidArgument {
+                name: "stride",
+                requirement: "must be non-zero",
+            });
+        }
+
+        let windows = |size: usize, pool: usize, stride: usize| -> usize {
+            if size == 0 {
+                0
+            } else if size < pool {
+                usize::from(include_partial)
+            } else if include_partial {
+                (size - pool).div_ceil(stride) + 1
+            } else {
+                (size - pool) / stride + 1
+            }
+        };
+
+        Ok((
+            windows(self.rows, pool_rows, stride_rows),
+            windows(self.columns, pool_columns, stride_columns),
+        ))
+    }
+
     /// Calculates the inner product of two input Vec<T> objects
-    fn inner_product(a: &Vec<T>, b: &Vec<T>) -> T {
+    fn inner_product(a: &[T], b: &[T]) -> T {
         if a.len() != b.len() {
             panic!("These vectors are of different sizes!");
         }
@@ -162,48 +474,193 @@ where
         new_matrix
     }
 
+    /// Builds the dimension mismatch error for an operation between this matrix and an operand of the given shape
+    fn mismatch(&self, op: &'static str, rows: usize, columns: usize) -> MatrixError {
+        MatrixError::DimensionMismatch {
+            op,
+            left: (self.rows, self.columns),
+            right: (rows, columns),
+        }
+    }
+
     /// Combines the self matrix and the input matrix such that both are side-by-side, with the input matrix (rhs) on the right.
     fn combine(&self, rhs: &Self) -> Self {
-        if self.rows != rhs.rows {
-            panic!("These two matrices must have the same number of rows!");
+        match self.try_combine(rhs) {
+            Ok(new_matrix) => new_matrix,
+            Err(error) => panic!("{}", error),
         }
-        let mut new_matrix: Self = Self::new(self.rows, self.columns + rhs.columns);
+    }
 
-        for row in 0..self.rows {
-            for column in 0..self.columns {
-                new_matrix.set_value(row, column, self[row][column]);
-            }
-            for column in 0..rhs.columns {
-                new_matrix.set_value(row, column + self.columns, rhs[row][column]);
+    /// Returns a copy of this matrix with the given row and column removed
+    fn remove_row_and_column(&self, removed_row: usize, removed_column: usize) -> Self {
+        let mut new_matrix: Self = Self::new(self.rows - 1, self.columns - 1);
+
+        for row in 0..self.rows - 1 {
+            let source_row: usize = if row < removed_row { row } else { row + 1 };
+            for column in 0..self.columns - 1 {
+                let source_column: usize = if column < removed_column {
+                    column
+                } else {
+                    column + 1
+                };
+                new_matrix.set_value(row, column, self[source_row][source_column]);
             }
         }
 
         new_matrix
     }
 
-    /// Returns the x input vector of a solved matrix
-    fn get_x_vector(solved_matrix: Matrix<T>) -> Vec<T> {
-        let last_column_index: usize = solved_matrix.columns - 1;
+    /// Runs Gaussian elimination, taking the first entry at or below the current row in the leftmost remaining column
+    /// that negligible doesn't dismiss against the tolerance as each pivot. A column with no pivot has its remaining
+    /// entries set to zero. Only the entries below each pivot are cleared, with the multipliers recorded in the lower
+    /// factor, unless reduce is set, in which case each pivot row is scaled so the pivot is one and the entries above
+    /// it are cleared too
+    fn eliminate(
+        &self,
+        reduce: bool,
+        tolerance: T,
+        negligible: fn(T, T) -> bool,
+    ) -> EchelonForm<T> {
+        let mut operating_matrix: Vec<Vec<T>> = self.clone().matrix;
+        let mut lower: Self = Self::identity_matrix(self.rows);
+        let mut permutation: Vec<usize> = (0..self.rows).collect();
+        let mut pivots: Vec<(usize, usize)> = Vec::new();
+        let mut swap_sign: T = T::one();
+        let mut pivot_product: T = T::one();
+
+        for column in 0..self.columns {
+            let pivot_row: usize = pivots.len();
+            if pivot_row == self.rows {
+                break;
+            }
+
+            let Some(found) = (pivot_row..self.rows)
+                .find(|row| !negligible(operating_matrix[*row][column], tolerance))
+            else {
+                for values in operating_matrix.iter_mut().skip(pivot_row) {
+                    values[column] = T::zero();
+                }
+                continue;
+            };
+            if found != pivot_row {
+                operating_matrix.swap(found, pivot_row);
+                permutation.swap(found, pivot_row);
+                // The multipliers so far move with their rows, but the unit diagonal stays put
+                for column in 0..pivot_row {
+                    let value: T = lower[found][column];
+                    lower.set_value(found, column, lower[pivot_row][column]);
+                    lower.set_value(pivot_row, column, value);
+                }
+                swap_sign = swap_sign.neg();
+            }
+
+            let pivot: T = operating_matrix[pivot_row][column];
+            pivot_product *= pivot;
+            pivots.push((pivot_row, column));
+
+            if reduce {
+                for value in operating_matrix[pivot_row].iter_mut().skip(column) {
+                    *value /= pivot;
+                }
+            }
+
+            let pivot_values: Vec<T> = operating_matrix[pivot_row].clone();
+            for (row, values) in operating_matrix.iter_mut().enumerate() {
+                if values[column].is_zero() || row == pivot_row || (!reduce && row < pivot_row) {
+                    continue;
+                }
+                // The reduced pivot row already has a one in the pivot column
+                let factor: T = if reduce {
+                    values[column]
+                } else {
+                    values[column] / pivot
+                };
+                for (value, pivot_value) in values.iter_mut().zip(&pivot_values).skip(column) {
+                    *value -= *pivot_value * factor;
+                }
+                if !reduce {
+                    values[column] = T::zero();
+                    lower.set_value(row, pivot_row, factor);
+                }
+            }
+        }
+
+        EchelonForm {
+            matrix: Self::from_vector(&operating_matrix),
+            lower,
+            pivots,
+            permutation,
+            swap_sign,
+            pivot_product,
+            tolerance,
+            negligible,
+        }
+    }
+
+    /// Returns the column of the pivot in each non-zero row of a reduced matrix, only looking at the first columns_to_check columns
+    fn pivot_columns(reduced_matrix: &Self, columns_to_check: usize) -> Vec<usize> {
         let zero: T = T::zero();
-        let one: T = T::one();
+        let mut pivot_columns: Vec<usize> = Vec::new();
 
-        let mut x_vector: Vec<T> = Vec::with_capacity(last_column_index);
-        let mut current_row_index: usize = 0;
+        for row in 0..reduced_matrix.rows {
+            if let Some(column) =
+                (0..columns_to_check).find(|column| reduced_matrix[row][*column] != zero)
+            {
+                pivot_columns.push(column);
+            }
+        }
 
-        for column_index in 0..last_column_index {
-            if solved_matrix[current_row_index][column_index] == one {
-                x_vector.push(solved_matrix[current_row_index][last_column_index]);
-                current_row_index += 1;
-            } else {
-                x_vector.push(zero);
+        pivot_columns
+    }
+
+    /// Returns an error unless the given list is a permutation of 0..length
+    fn validate_permutation(permutation: &[usize], length: usize) -> Result<(), MatrixError> {
+        let not_permutation: MatrixError = MatrixError::NotPermutation { length };
+        if permutation.len() != length {
+            return Err(not_permutation);
+        }
+
+        let mut seen: Vec<bool> = vec![false; length];
+        for index in permutation {
+            if *index >= length || seen[*index] {
+                return Err(not_permutation);
             }
+            seen[*index] = true;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the x input vector of a solved (reduced augmented) matrix, with any free variables set to zero.
+    /// Returns None if a pivot lies in the last column, meaning the system is inconsistent
+    fn get_x_vector(solved_matrix: Matrix<T>) -> Option<Vec<T>> {
+        let last_column_index: usize = solved_matrix.columns - 1;
+        let pivot_columns: Vec<usize> = Self::pivot_columns(&solved_matrix, solved_matrix.columns);
+
+        if pivot_columns.contains(&last_column_index) {
+            return None;
+        }
+
+        let mut x_vector: Vec<T> = vec![T::zero(); last_column_index];
+        for (row_index, column_index) in pivot_columns.into_iter().enumerate() {
+            x_vector[column_index] = solved_matrix[row_index][last_column_index];
         }
 
-        x_vector
+        Some(x_vector)
     }
 
     // -----PUBLIC METHODS-----
 
+    /// Returns the number of rows in this matrix
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns in this matrix
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
     /// Gets the value of the matrix at the given indices (0 indexed). Functionally equivalent to Matrix\[row\]\[column\]
     pub fn get_value(&self, row: usize, column: usize) -> T {
         self.matrix[row][column]
@@ -214,107 +671,153 @@ where
         self.matrix[row][column] = value;
     }
 
+    /// Appends the given row to the bottom of this matrix. A matrix with no rows or columns, such as new(0, 0), takes its
+    /// column count from the first row pushed. Errors if the row's length differs from the number of columns
+    pub fn push_row(&mut self, row: &[T]) -> Result<(), MatrixError> {
+        if self.rows == 0 && self.columns == 0 {
+            self.columns = row.len();
+        }
+        if row.len() != self.columns {
+            return Err(MatrixError::RowLengthMismatch {
+                row: self.rows,
+                expected: self.columns,
+                actual: row.len(),
+            });
+        }
+
+        self.matrix.push(row.to_vec());
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Appends the given column to the right of this matrix, which takes O(rows) time. A matrix with no rows or
+    /// columns takes its row count from the first column pushed. Errors if the column's length differs from the number
+    /// of rows
+    pub fn push_column(&mut self, column: &[T]) -> Result<(), MatrixError> {
+        if self.rows == 0 && self.columns == 0 {
+            self.rows = column.len();
+            self.matrix = vec![Vec::new(); column.len()];
+        }
+        if column.len() != self.rows {
+            return Err(self.mismatch("push_column", column.len(), 1));
+        }
+
+        for (row, value) in self.matrix.iter_mut().zip(column) {
+            row.push(*value);
+        }
+        self.columns += 1;
+        Ok(())
+    }
+
+    /// Appends every row of other to the bottom of this matrix. A matrix with no rows or columns takes its column count
+    /// from other. Errors if the matrices have different numbers of columns
+    pub fn extend_rows(&mut self, other: &Self) -> Result<(), MatrixError> {
+        if self.rows == 0 && self.columns == 0 {
+            self.columns = other.columns;
+        }
+        if other.columns != self.columns {
+            return Err(self.mismatch("extend_rows", other.rows, other.columns));
+        }
+
+        self.matrix.extend(other.matrix.iter().cloned());
+        self.rows += other.rows;
+        Ok(())
+    }
+
     /// Calculates the reduced echelon form and determinant of this matrix (determinant is an error if the matrix is non-square)
     pub fn reduced_echelon_and_det(&self) -> (Self, Result<T, &'static str>) {
-        let mut operating_matrix: Vec<Vec<T>> = self.clone().matrix;
+        let reduced: EchelonForm<T> = self.eliminate(true, T::zero(), |value, _| value.is_zero());
+        let determinant: Result<T, &'static str> = reduced.determinant();
+        (reduced.matrix, determinant)
+    }
 
-        let mut current_pivot_row: usize = 0;
-        let mut current_pivot_column: usize = 0;
-        let mut factor: T;
-        let mut determinant: T = T::one();
+    /// Calculates a row echelon form by Gaussian elimination, clearing only the entries below each pivot and leaving the
+    /// pivots unscaled, along with the pivot positions, the row permutation, and the product of the pivots. Pivots are
+    /// the first non-zero entries found, just as in reduced_echelon_form, so both find the same pivot columns. Any
+    /// rounding error left where an entry should cancel counts as a pivot, so floating point matrices should use
+    /// row_echelon_with_tolerance instead
+    pub fn row_echelon(&self) -> EchelonForm<T> {
+        self.eliminate(false, T::zero(), |value, _| value.is_zero())
+    }
 
-        let negative_one: T = T::one().neg();
-        let zero: T = T::zero();
+    /// Calculates and returns the reduced echelon form of this matrix
+    pub fn reduced_echelon_form(&self) -> Self {
+        self.reduced_echelon_and_det().0
+    }
 
-        #[allow(clippy::mut_range_bound)]
-        while self.rows - current_pivot_row > 0 && self.columns - current_pivot_column > 0 {
-            let mut changed: bool = false;
-
-            // Find the next pivot
-            for column in current_pivot_column..self.columns {
-                for row in current_pivot_row..self.rows {
-                    if operating_matrix[row][column] != zero {
-                        // Row swap if necessary
-                        if current_pivot_row != row {
-                            operating_matrix.swap(row, current_pivot_row);
-                            determinant *= negative_one;
-                        }
-                        // Update the column
-                        current_pivot_column = column;
-                        changed = true;
-                        break;
-                    }
-                }
-                if changed {
-                    break;
-                }
-            }
+    /// Calculates an echelon form with fraction-free Bareiss elimination, so integer matrices stay in exact integer
+    /// arithmetic. Pivots aren't normalized to one and entries above them aren't cleared. Every division is exact,
+    /// since each entry is a minor of the original matrix, which also bounds how large entries can grow. Products of
+    /// two minors are formed in T, so integer entries must be small enough for those to fit. Whenever rows are swapped
+    /// the moved-down row is negated, so the determinant is unchanged and the last pivot of a nonsingular square
+    /// matrix is its determinant
+    pub fn integer_echelon_form(&self) -> Self {
+        let mut output: Self = self.clone();
+        let mut previous_pivot: T = T::one();
+        let mut pivot_row: usize = 0;
 
-            // If the pivot didn't change, then we have exhausted all pivots
-            if !changed {
+        for column in 0..self.columns {
+            if pivot_row == self.rows {
                 break;
             }
-
-            // Set the pivot to 1
-            factor = operating_matrix[current_pivot_row][current_pivot_column];
-            for column in current_pivot_column..self.columns {
-                operating_matrix[current_pivot_row][column] /= factor;
+            let Some(found) = (pivot_row..self.rows).find(|row| !output[*row][column].is_zero())
+            else {
+                continue;
+            };
+            if found != pivot_row {
+                output.matrix.swap(pivot_row, found);
+                output.matrix[found]
+                    .iter_mut()
+                    .for_each(|value| *value = value.neg());
             }
-            determinant *= factor;
 
-            // Reduce down all rows above and underneath
-            for row in 0..self.rows {
-                if operating_matrix[row][current_pivot_column] == zero || row == current_pivot_row {
-                    continue;
-                }
-                factor = operating_matrix[row][current_pivot_column];
-                for column in current_pivot_column..self.columns {
-                    let subtraction_factor: T =
-                        operating_matrix[current_pivot_row][column] * factor;
-                    operating_matrix[row][column] -= subtraction_factor;
+            let pivot: T = output[pivot_row][column];
+            let pivot_values: Vec<T> = output.matrix[pivot_row].clone();
+            for row in output.matrix.iter_mut().skip(pivot_row + 1) {
+                let factor: T = row[column];
+                row[column] = T::zero();
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_values).skip(column + 1) {
+                    *value = (*value * pivot - factor * *pivot_value) / previous_pivot;
                 }
             }
 
-            // Force the pivot to update
-            current_pivot_row += 1;
-            current_pivot_column += 1;
-        }
-
-        let det_output: Result<T, &'static str>;
-        // Checks if this matrix is square and has so has a determinant, then checks that this matrix is equal to In
-        if self.rows != self.columns {
-            det_output = Err("The matrix was not square");
-        } else {
-            for (i, row) in operating_matrix.iter().enumerate() {
-                if row[i] == zero {
-                    determinant = zero;
-                    break;
-                }
-            }
-            det_output = Ok(determinant);
+            previous_pivot = pivot;
+            pivot_row += 1;
         }
 
-        (Self::from_vector(&operating_matrix), det_output)
-    }
-
-    /// Calculates and returns the reduced echelon form of this matrix
-    pub fn reduced_echelon_form(&self) -> Self {
-        self.reduced_echelon_and_det().0
+        output
     }
 
-    /// Calculates and returns the determinant if this matrix is square
+    /// Calculates and returns the determinant if this matrix is square. Matrices up to 4x4 use the closed-form cofactor
+    /// expansion, while larger matrices use row reduction
     pub fn determinant(&self) -> T {
         if self.rows != self.columns {
             panic!("This matrix is not square!");
         }
+        if self.rows <= Self::CLOSED_FORM_MAX_SIZE {
+            return self.closed_form_determinant();
+        }
         self.reduced_echelon_and_det().1.unwrap()
     }
 
-    /// Calculates and returns the inverse of this matrix, if this matrix is invertible
+    /// Calculates and returns the inverse of this matrix, if this matrix is invertible. Matrices up to 4x4 use the
+    /// closed-form adjugate divided by the determinant, while larger matrices use Gauss-Jordan elimination
     pub fn inverse(&self) -> Result<Self, &'static str> {
         if self.rows != self.columns {
             panic!("This matrix is not square!");
         }
+        if self.rows <= Self::CLOSED_FORM_MAX_SIZE && self.rows > 0 {
+            return self.closed_form_inverse(|determinant| determinant == T::zero());
+        }
+
+        self.inverse_gauss_jordan()
+    }
+
+    /// Calculates the inverse of this matrix by row reducing it alongside the identity matrix
+    pub fn inverse_gauss_jordan(&self) -> Result<Self, &'static str> {
+        if self.rows != self.columns {
+            panic!("This matrix is not square!");
+        }
 
         let identity_matrix: Self = Self::identity_matrix(self.rows);
 
@@ -329,6 +832,83 @@ where
         Ok(inverse_matrix)
     }
 
+    /// Replaces this matrix with its inverse using Gauss-Jordan elimination on the matrix itself, so the only extra
+    /// storage is the row and value of each pivot. Each step sweeps out one column: the pivot row is divided by the
+    /// pivot, which is replaced by its reciprocal, and the pivot column of every other row is folded into that row.
+    /// The pivot is the first non-zero entry at or below the diagonal, and the row swaps are undone as column swaps at
+    /// the end. Errors if the matrix isn't square or isn't invertible. A non-square matrix is left unchanged, while a
+    /// singular one has its completed steps reversed, which restores it up to rounding
+    pub fn invert_in_place(&mut self) -> Result<(), &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let size: usize = self.rows;
+        let mut pivot_rows: Vec<usize> = Vec::with_capacity(size);
+        let mut pivot_values: Vec<T> = Vec::with_capacity(size);
+
+        for step in 0..size {
+            let Some(found) = (step..size).find(|row| !self.matrix[*row][step].is_zero()) else {
+                // Reverse the steps so far, which redoes each sweep with the recorded pivot and swaps back
+                for undo in (0..step).rev() {
+                    let pivot: T = pivot_values[undo];
+                    for row in 0..size {
+                        if row == undo {
+                            continue;
+                        }
+                        let factor: T = self.matrix[row][undo].neg() * pivot;
+                        for column in 0..size {
+                            if column != undo {
+                                let addition: T = factor * self.matrix[undo][column];
+                                self.matrix[row][column] += addition;
+                            }
+                        }
+                        self.matrix[row][undo] = factor;
+                    }
+                    for value in self.matrix[undo].iter_mut() {
+                        *value *= pivot;
+                    }
+                    self.matrix[undo][undo] = pivot;
+                    self.matrix.swap(undo, pivot_rows[undo]);
+                }
+                return Err("Matrix is not invertible");
+            };
+            self.matrix.swap(step, found);
+            pivot_rows.push(found);
+
+            let pivot: T = self.matrix[step][step];
+            pivot_values.push(pivot);
+            self.matrix[step][step] = T::one();
+            for value in self.matrix[step].iter_mut() {
+                *value /= pivot;
+            }
+
+            for row in 0..size {
+                if row == step {
+                    continue;
+                }
+                let factor: T = self.matrix[row][step];
+                if factor.is_zero() {
+                    continue;
+                }
+                self.matrix[row][step] = T::zero();
+                for column in 0..size {
+                    let subtraction: T = factor * self.matrix[step][column];
+                    self.matrix[row][column] -= subtraction;
+                }
+            }
+        }
+
+        // Swapping rows of A swaps the matching columns of its inverse
+        for (step, found) in pivot_rows.into_iter().enumerate().rev() {
+            for row in self.matrix.iter_mut() {
+                row.swap(step, found);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns a transpose of this matrix
     pub fn transpose(&self) -> Self {
         let mut transpose_matrix: Self = Self::new(self.columns, self.rows);
@@ -342,6 +922,61 @@ where
         transpose_matrix
     }
 
+    /// Returns a copy of the block of this matrix with the given size, whose top-left corner is at (start_row, start_column)
+    pub fn submatrix(
+        &self,
+        start_row: usize,
+        start_column: usize,
+        rows: usize,
+        columns: usize,
+    ) -> Result<Self, &'static str> {
+        if !self.block_fits(start_row, start_column, rows, columns) {
+            return Err("The submatrix exceeds the bounds of this matrix");
+        }
+
+        Ok(self.partition(
+            start_row,
+            start_row + rows,
+            start_column,
+            start_column + columns,
+        ))
+    }
+
+    /// Writes the given block into this matrix such that its top-left corner lands at (start_row, start_column)
+    pub fn set_submatrix(
+        &mut self,
+        start_row: usize,
+        start_column: usize,
+        block: &Self,
+    ) -> Result<(), &'static str> {
+        if !self.block_fits(start_row, start_column, block.rows, block.columns) {
+            return Err("The submatrix exceeds the bounds of this matrix");
+        }
+
+        for row in 0..block.rows {
+            for column in 0..block.columns {
+                self.set_value(start_row + row, start_column + column, block[row][column]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if a block of the given size with its top-left corner at (start_row, start_column) lies inside this
+    /// matrix, without overflowing on large inputs
+    fn block_fits(
+        &self,
+        start_row: usize,
+        start_column: usize,
+        rows: usize,
+        columns: usize,
+    ) -> bool {
+        let end_row: Option<usize> = start_row.checked_add(rows);
+        let end_column: Option<usize> = start_column.checked_add(columns);
+        end_row.is_some_and(|end| end <= self.rows)
+            && end_column.is_some_and(|end| end <= self.columns)
+    }
+
     /// Returns a least squares solution of Ax = b. Uses the ATAx = ATb method.
     pub fn least_squares_solution(&self, b: Vec<T>) -> Result<Vec<T>, &'static str> {
         if b.len() != self.rows {
@@ -357,27 +992,76 @@ where
             .combine(&a_transpose_b_matrix)
             .reduced_echelon_form();
 
-        let last_column_index: usize = solved_matrix.columns - 1;
-        let zero: T = T::zero();
-        for row_index in 0..solved_matrix.rows {
-            if solved_matrix[row_index][last_column_index] == zero {
-                continue;
-            }
+        Self::get_x_vector(solved_matrix).ok_or("The system was inconsistent and there is no solution for b. (In this case, these means an arithmetic problem, probably due to floating point inaccuracy).")
+    }
 
-            let mut check_passed: bool = false;
-            for column_index in 0..last_column_index {
-                if solved_matrix[row_index][column_index] != zero {
-                    check_passed = true;
-                    break;
-                }
-            }
+    /// Returns the ridge (Tikhonov regularized) least squares solution, minimizing |Ax - b|^2 + lambda |x|^2 by solving
+    /// (A^T A + lambda I) x = A^T b. Any positive lambda makes the normal matrix positive definite, so the system stays
+    /// solvable even when the columns of A are nearly or exactly dependent. Errors if b doesn't have one entry per row,
+    /// if lambda is negative, or if the normal equations are inconsistent
+    pub fn ridge_least_squares(&self, b: &[T], lambda: T) -> Result<Vec<T>, &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+        if lambda.is_negative() {
+            return Err("The regularization parameter must not be negative");
+        }
 
-            if !check_passed {
-                return Err("The system was inconsistent and there is no solution for b. (In this case, these means an arithmetic problem, probably due to floating point inaccuracy).");
-            }
+        let transpose: Self = self.transpose();
+        let mut normal_matrix: Self = transpose.clone() * self.clone();
+        for i in 0..self.columns {
+            normal_matrix.matrix[i][i] += lambda;
+        }
+        let normal_b: Self = transpose * Self::matrix_from_list(b, self.rows, 1);
+
+        Self::get_x_vector(normal_matrix.combine(&normal_b).reduced_echelon_form())
+            .ok_or("The system was inconsistent and there is no solution for b.")
+    }
+
+    /// Returns the weighted least squares solution, minimizing the sum of weights\[i\] * (Ax - b)\[i\]^2 by solving the
+    /// normal equations (A^T W A) x = A^T W b where W is the diagonal matrix of weights. Errors if b or the weights
+    /// don't have one entry per row, if a weight is negative, or if the normal equations are inconsistent
+    pub fn weighted_least_squares(&self, b: &[T], weights: &[T]) -> Result<Vec<T>, &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
         }
+        if weights.len() != self.rows {
+            return Err("There must be one weight for each row");
+        }
+        if weights.iter().any(|weight| weight.is_negative()) {
+            return Err("The weights must not be negative");
+        }
+
+        // W A and W b, scaling each row by its weight
+        let mut weighted_matrix: Self = self.clone();
+        for (row, weight) in weights.iter().enumerate() {
+            weighted_matrix.matrix[row]
+                .iter_mut()
+                .for_each(|value| *value *= *weight);
+        }
+        let weighted_b: Vec<T> = b
+            .iter()
+            .zip(weights)
+            .map(|(value, weight)| *value * *weight)
+            .collect();
+
+        let transpose: Self = self.transpose();
+        let normal_matrix: Self = transpose.clone() * weighted_matrix;
+        let normal_b: Self = transpose * Self::matrix_from_list(&weighted_b, self.rows, 1);
+
+        Self::get_x_vector(normal_matrix.combine(&normal_b).reduced_echelon_form())
+            .ok_or("The system was inconsistent and there is no solution for b.")
+    }
+
+    /// Returns the orthogonal projector A (A^T A)^-1 A^T onto the column space of this matrix, so that P b is the closest
+    /// point to b in the column space. Errors if A^T A is singular, meaning the columns are linearly dependent
+    pub fn projection_matrix(&self) -> Result<Self, &'static str> {
+        let transpose: Self = self.transpose();
+        let gram_inverse: Self = (transpose.clone() * self.clone())
+            .inverse()
+            .map_err(|_| "The columns are linearly dependent, so A^T A is singular")?;
 
-        Ok(Self::get_x_vector(solved_matrix))
+        Ok(self.clone() * gram_inverse * transpose)
     }
 
     /// Returns a solution to the given Ax = b equation, or an error if a solution does not exist
@@ -390,235 +1074,4337 @@ where
 
         let solved_matrix: Self = self.combine(&b_matrix).reduced_echelon_form();
 
-        let last_column_index: usize = solved_matrix.columns - 1;
-        let zero: T = T::zero();
-        for row_index in 0..solved_matrix.rows {
-            if solved_matrix[row_index][last_column_index] == zero {
-                continue;
-            }
+        Self::get_x_vector(solved_matrix)
+            .ok_or("The system was inconsistent and there is no solution for b.")
+    }
 
-            let mut check_passed: bool = false;
-            for column_index in 0..last_column_index {
-                if solved_matrix[row_index][column_index] != zero {
-                    check_passed = true;
-                    break;
-                }
-            }
+    /// Returns true if these two matrices are equal, within the given delta
+    pub fn equals(&self, other: &Self, delta: T) -> bool {
+        if self.rows != other.rows || self.columns != other.columns {
+            return false;
+        }
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let difference: T = (self[row][column] - other[row][column]).abs();
+                // is_positive() should exclude zero, but in my testing it doesn't
+                if (difference - delta).is_positive() && !(difference - delta).is_zero() {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns the rank of this matrix, which is the number of non-zero rows in its reduced echelon form
+    pub fn rank(&self) -> usize {
+        let reduced_matrix: Self = self.reduced_echelon_form();
+        let zero: T = T::zero();
+
+        let mut rank: usize = 0;
+        for row in 0..reduced_matrix.rows {
+            if reduced_matrix[row].iter().any(|value| *value != zero) {
+                rank += 1;
+            }
+        }
+
+        rank
+    }
+
+    /// Returns true if the rows of this matrix are linearly independent
+    pub fn rows_independent(&self) -> bool {
+        self.rank() == self.rows
+    }
+
+    /// Returns true if the columns of this matrix are linearly independent
+    pub fn columns_independent(&self) -> bool {
+        self.rank() == self.columns
+    }
+
+    /// Returns the Kronecker product of this matrix and rhs, where each entry of this matrix is replaced by that entry times rhs
+    pub fn kronecker(&self, rhs: &Self) -> Self {
+        let mut output: Self = Self::new(self.rows * rhs.rows, self.columns * rhs.columns);
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                for rhs_row in 0..rhs.rows {
+                    for rhs_column in 0..rhs.columns {
+                        output.set_value(
+                            row * rhs.rows + rhs_row,
+                            column * rhs.columns + rhs_column,
+                            self[row][column] * rhs[rhs_row][rhs_column],
+                        );
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Returns a matrix made of copies of this matrix laid out in a grid of row_reps by column_reps.
+    /// A repetition count of zero produces a matrix with zero rows or columns
+    pub fn tile(&self, row_reps: usize, column_reps: usize) -> Self {
+        let mut output: Self = Self::new(self.rows * row_reps, self.columns * column_reps);
+
+        for row in 0..output.rows {
+            for column in 0..output.columns {
+                output.set_value(row, column, self[row % self.rows][column % self.columns]);
+            }
+        }
+
+        output
+    }
+
+    /// Returns a matrix where each entry of this matrix is expanded into a row_reps by column_reps block of that entry.
+    /// A repetition count of zero produces a matrix with zero rows or columns
+    pub fn repeat_elements(&self, row_reps: usize, column_reps: usize) -> Self {
+        let mut output: Self = Self::new(self.rows * row_reps, self.columns * column_reps);
+
+        for row in 0..output.rows {
+            for column in 0..output.columns {
+                output.set_value(row, column, self[row / row_reps][column / column_reps]);
+            }
+        }
+
+        output
+    }
+
+    /// Returns the sum of each row of this matrix
+    pub fn row_sums(&self) -> Vec<T> {
+        let mut sums: Vec<T> = Vec::with_capacity(self.rows);
+
+        for row in 0..self.rows {
+            let mut sum: T = T::zero();
+            for column in 0..self.columns {
+                sum += self[row][column];
+            }
+            sums.push(sum);
+        }
+
+        sums
+    }
+
+    /// Returns the sum of each column of this matrix
+    pub fn column_sums(&self) -> Vec<T> {
+        let mut sums: Vec<T> = vec![T::zero(); self.columns];
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                sums[column] += self[row][column];
+            }
+        }
+
+        sums
+    }
+
+    /// Returns a matrix with every entry of this matrix raised to the given power. This is element-wise, unlike matrix powers
+    pub fn hadamard_pow(&self, exponent: u32) -> Self {
+        let mut output: Self = Self::new(self.rows, self.columns);
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let value: T = num_traits::pow(self[row][column], exponent as usize);
+                output.set_value(row, column, value);
+            }
+        }
+
+        output
+    }
+
+    /// Calculates the determinant by cofactor expansion along the first row. This never divides, so it is exact for
+    /// integer matrices, but it takes factorial time and should only be used on small matrices
+    pub fn determinant_cofactor(&self) -> Result<T, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        match self.rows {
+            0 => Ok(T::one()),
+            1 => Ok(self[0][0]),
+            2 => Ok(self[0][0] * self[1][1] - self[0][1] * self[1][0]),
+            _ => {
+                let mut determinant: T = T::zero();
+                let mut sign: T = T::one();
+
+                for column in 0..self.columns {
+                    if self[0][column] != T::zero() {
+                        let minor: T = self
+                            .remove_row_and_column(0, column)
+                            .determinant_cofactor()?;
+                        determinant += sign * self[0][column] * minor;
+                    }
+                    sign = sign.neg();
+                }
+
+                Ok(determinant)
+            }
+        }
+    }
+
+    /// Returns the adjugate (transpose of the cofactor matrix) of this matrix, calculated without division
+    pub fn adjugate(&self) -> Result<Self, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let mut adjugate_matrix: Self = Self::square_matrix(self.rows);
+        if self.rows == 1 {
+            adjugate_matrix.set_value(0, 0, T::one());
+            return Ok(adjugate_matrix);
+        }
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let mut cofactor: T = self
+                    .remove_row_and_column(row, column)
+                    .determinant_cofactor()?;
+                if (row + column) % 2 == 1 {
+                    cofactor = cofactor.neg();
+                }
+                adjugate_matrix.set_value(column, row, cofactor);
+            }
+        }
+
+        Ok(adjugate_matrix)
+    }
+
+    /// Calculates the inverse of a unimodular matrix (determinant of 1 or -1) without any division, so integer matrices
+    /// get an exact integer inverse. Errors if the determinant isn't 1 or -1
+    pub fn integer_inverse(&self) -> Result<Self, &'static str> {
+        let determinant: T = self.determinant_cofactor()?;
+
+        if determinant.abs() != T::one() {
+            return Err("The determinant of the matrix was not 1 or -1");
+        }
+
+        // Since the determinant is its own inverse, the inverse is the adjugate scaled by the determinant
+        Ok(self.adjugate()? * determinant)
+    }
+
+    /// Adds two matrices together, or errors if their sizes differ
+    pub fn try_add(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        if self.rows != rhs.rows || self.columns != rhs.columns {
+            return Err(self.mismatch("add", rhs.rows, rhs.columns));
+        }
+
+        let mut output: Self = Self::new(self.rows, self.columns);
+
+        for row_index in 0..self.rows {
+            for column_index in 0..self.columns {
+                let value: T = self[row_index][column_index] + rhs[row_index][column_index];
+                output.set_value(row_index, column_index, value);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Subtracts rhs from this matrix, or errors if their sizes differ
+    pub fn try_sub(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        if self.rows != rhs.rows || self.columns != rhs.columns {
+            return Err(self.mismatch("sub", rhs.rows, rhs.columns));
+        }
+
+        let mut output: Self = Self::new(self.rows, self.columns);
+
+        for row_index in 0..self.rows {
+            for column_index in 0..self.columns {
+                let value: T = self[row_index][column_index] - rhs[row_index][column_index];
+                output.set_value(row_index, column_index, value);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Multiplies two matrices together, or errors if the left hand columns don't equal the right hand rows
+    pub fn try_mul(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        if self.columns != rhs.rows {
+            return Err(self.mismatch("mul", rhs.rows, rhs.columns));
+        }
+
+        let common_size: usize = self.columns;
+
+        let mut output: Self = Self::new(self.rows, rhs.columns);
+
+        for output_row in 0..self.rows {
+            for output_column in 0..rhs.columns {
+                let mut b: Vec<T> = Vec::with_capacity(common_size);
+                for i in 0..common_size {
+                    b.push(rhs[i][output_column]);
+                }
+
+                output.set_value(
+                    output_row,
+                    output_column,
+                    Self::inner_product(&self[output_row], &b),
+                );
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Multiplies this matrix by the given column vector, or errors if the vector length doesn't equal the columns
+    pub fn try_mul_vec(&self, vector: &[T]) -> Result<Vec<T>, MatrixError> {
+        if self.columns != vector.len() {
+            return Err(self.mismatch("mul_vec", vector.len(), 1));
+        }
+
+        let mut output: Vec<T> = Vec::with_capacity(self.rows);
+        for row in 0..self.rows {
+            output.push(Self::inner_product(&self[row], vector));
+        }
+
+        Ok(output)
+    }
+
+    /// Returns the commutator [A, B] = AB - BA, which is zero exactly when the two matrices commute. Errors if the
+    /// matrices aren't square and of the same size
+    pub fn commutator(&self, other: &Self) -> Result<Self, &'static str> {
+        if self.rows != self.columns || other.rows != other.columns || self.rows != other.rows {
+            return Err("Both matrices must be square and of the same size");
+        }
+
+        // The shapes are checked above, so none of these can fail
+        let forward: Self = self.try_mul(other).unwrap();
+        let backward: Self = other.try_mul(self).unwrap();
+        Ok(forward.try_sub(&backward).unwrap())
+    }
+
+    /// Returns true if AB equals BA within the given delta, meaning every entry of the commutator has magnitude at
+    /// most delta. Errors if the matrices aren't square and of the same size
+    pub fn commutes_with(&self, other: &Self, delta: T) -> Result<bool, &'static str> {
+        let commutator: Self = self.commutator(other)?;
+        Ok(commutator.matrix.iter().flatten().all(|value| {
+            let excess: T = value.abs() - delta;
+            !excess.is_positive() || excess.is_zero()
+        }))
+    }
+
+    /// Combines this matrix and rhs side-by-side with rhs on the right, or errors if their row counts differ
+    pub fn try_combine(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        self.try_hstack(rhs)
+    }
+
+    /// Stacks this matrix and rhs horizontally with rhs on the right, or errors if their row counts differ
+    pub fn try_hstack(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        if self.rows != rhs.rows {
+            return Err(self.mismatch("hstack", rhs.rows, rhs.columns));
+        }
+
+        let mut new_matrix: Self = Self::new(self.rows, self.columns + rhs.columns);
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                new_matrix.set_value(row, column, self[row][column]);
+            }
+            for column in 0..rhs.columns {
+                new_matrix.set_value(row, column + self.columns, rhs[row][column]);
+            }
+        }
+
+        Ok(new_matrix)
+    }
+
+    /// Returns the general solution of Ax = b as a particular solution along with a basis for the null space of A,
+    /// so that every solution is the particular solution plus a linear combination of the null space vectors.
+    /// Errors if the system is inconsistent
+    pub fn solve_general(&self, b: &[T]) -> Result<(Vec<T>, Vec<Vec<T>>), &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+
+        let b_matrix: Self = Self::matrix_from_list(b, b.len(), 1);
+        let solved_matrix: Self = self.combine(&b_matrix).reduced_echelon_form();
+
+        let pivot_columns: Vec<usize> = Self::pivot_columns(&solved_matrix, solved_matrix.columns);
+        if pivot_columns.contains(&self.columns) {
+            return Err("The system was inconsistent and there is no solution for b.");
+        }
+
+        // Free variables are set to zero, so each pivot variable takes the value on the right of its row
+        let mut particular_solution: Vec<T> = vec![T::zero(); self.columns];
+        for (row, column) in pivot_columns.iter().enumerate() {
+            particular_solution[*column] = solved_matrix[row][self.columns];
+        }
+
+        // Each free variable gives a null space vector where it is set to one and the other free variables to zero
+        let mut null_space: Vec<Vec<T>> = Vec::new();
+        for free_column in (0..self.columns).filter(|column| !pivot_columns.contains(column)) {
+            let mut null_vector: Vec<T> = vec![T::zero(); self.columns];
+            null_vector[free_column] = T::one();
+            for (row, column) in pivot_columns.iter().enumerate() {
+                null_vector[*column] = solved_matrix[row][free_column].neg();
+            }
+            null_space.push(null_vector);
+        }
+
+        Ok((particular_solution, null_space))
+    }
+
+    /// Returns true if this matrix is square and equal to its transpose, within the given delta
+    pub fn is_symmetric(&self, delta: T) -> bool {
+        self.rows == self.columns && self.equals(&self.transpose(), delta)
+    }
+
+    /// Returns the Kronecker sum A ⊗ I_m + I_n ⊗ B of this n x n matrix A and the m x m matrix rhs (B)
+    pub fn kronecker_sum(&self, rhs: &Self) -> Result<Self, &'static str> {
+        if self.rows != self.columns || rhs.rows != rhs.columns {
+            return Err("Both matrices must be square");
+        }
+
+        let left: Self = self.kronecker(&Self::identity_matrix(rhs.rows));
+        let right: Self = Self::identity_matrix(self.rows).kronecker(rhs);
+
+        // Both terms are (n * m) x (n * m), so this can't fail
+        Ok(left.try_add(&right).unwrap())
+    }
+
+    /// Solves the Sylvester equation AX + XB = C for X, where A is n x n, B is m x m and C is n x m. Stacking the rows
+    /// of X into a vector turns the equation into the nm x nm linear system (A ⊗ I_m + I_n ⊗ B^T) vec(X) = vec(C),
+    /// which is solved with solve, so this is only practical for small matrices. When A and -B share an eigenvalue the
+    /// solution isn't unique and the free entries are set to zero. Errors if the sizes don't match or the system is
+    /// inconsistent
+    pub fn solve_sylvester(a: &Self, b: &Self, c: &Self) -> Result<Self, &'static str> {
+        let system: Self = a.kronecker_sum(&b.transpose())?;
+        if c.rows != a.rows || c.columns != b.rows {
+            return Err("C must have the rows of A and the columns of B");
+        }
+
+        let stacked: Vec<T> = c.matrix.iter().flatten().copied().collect();
+        let solution: Vec<T> = system.solve(stacked)?;
+
+        Ok(Self::matrix_from_list(&solution, c.rows, c.columns))
+    }
+
+    /// Reorders the rows of this matrix in place so that position i receives the row that was at position
+    /// permutation\[i\]. (Equivalently, row permutation\[i\] moves to position i, so the new matrix is P * A where row i
+    /// of P is the standard basis vector e_permutation\[i\].) Uses cycle-following, so no rows are copied. Errors
+    /// unless the list is a permutation of 0..rows
+    pub fn permute_rows_in_place(&mut self, permutation: &[usize]) -> Result<(), MatrixError> {
+        Self::validate_permutation(permutation, self.rows)?;
+
+        let mut placed: Vec<bool> = vec![false; self.rows];
+        for start in 0..self.rows {
+            if placed[start] {
+                continue;
+            }
+
+            // Walk the cycle containing start, pulling each row into the position that receives it
+            let start_row: Vec<T> = std::mem::take(&mut self.matrix[start]);
+            let mut position: usize = start;
+            loop {
+                placed[position] = true;
+                let source: usize = permutation[position];
+                if source == start {
+                    self.matrix[position] = start_row;
+                    break;
+                }
+                self.matrix[position] = std::mem::take(&mut self.matrix[source]);
+                position = source;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reorders the columns of this matrix in place so that position i receives the column that was at position
+    /// permutation\[i\], matching the convention of permute_rows_in_place. Errors unless the list is a permutation of
+    /// 0..columns
+    pub fn permute_columns_in_place(&mut self, permutation: &[usize]) -> Result<(), MatrixError> {
+        Self::validate_permutation(permutation, self.columns)?;
+
+        for row in self.matrix.iter_mut() {
+            let permuted_row: Vec<T> = permutation.iter().map(|source| row[*source]).collect();
+            *row = permuted_row;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the trace of this matrix, which is the sum of its diagonal entries
+    pub fn trace(&self) -> Result<T, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let mut trace: T = T::zero();
+        for i in 0..self.rows {
+            trace += self[i][i];
+        }
+
+        Ok(trace)
+    }
+
+    /// Returns the Frobenius inner product, the sum of a_ij * b_ij over every entry, which equals trace(A^T B). Errors
+    /// if the matrices differ in size
+    pub fn frobenius_inner_product(&self, other: &Self) -> Result<T, &'static str> {
+        if self.rows != other.rows || self.columns != other.columns {
+            return Err("The inner product requires both matrices to have the same size");
+        }
+
+        let mut sum: T = T::zero();
+        for (row, other_row) in self.matrix.iter().zip(&other.matrix) {
+            sum += Self::inner_product(row, other_row);
+        }
+
+        Ok(sum)
+    }
+
+    /// Splits this matrix into its strictly lower triangular, diagonal and strictly upper triangular parts, so that
+    /// A = L + D + U as used by the Jacobi and Gauss-Seidel splittings. Each part has the same size as this matrix
+    pub fn split_ldu(&self) -> (Self, Self, Self) {
+        let mut lower: Self = Self::new(self.rows, self.columns);
+        let mut diagonal: Self = Self::new(self.rows, self.columns);
+        let mut upper: Self = Self::new(self.rows, self.columns);
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let part: &mut Self = match column.cmp(&row) {
+                    cmp::Ordering::Less => &mut lower,
+                    cmp::Ordering::Equal => &mut diagonal,
+                    cmp::Ordering::Greater => &mut upper,
+                };
+                part.set_value(row, column, self[row][column]);
+            }
+        }
+
+        (lower, diagonal, upper)
+    }
+
+    /// Returns this matrix with every entry divided by the trace, so the result has a trace of one
+    pub fn normalize_by_trace(&self) -> Result<Self, &'static str> {
+        let trace: T = self.trace()?;
+
+        if trace == T::zero() {
+            return Err("The trace of the matrix was zero");
+        }
+
+        let mut output: Self = Self::new(self.rows, self.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                output.set_value(row, column, self[row][column] / trace);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Returns true if this matrix is square and every entry off the diagonal is zero
+    pub fn is_diagonal(&self) -> bool {
+        if self.rows != self.columns {
+            return false;
+        }
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if row != column && self[row][column] != T::zero() {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns true if this matrix is square with exactly one entry equal to one in each row and column, and zeros
+    /// everywhere else
+    pub fn is_permutation_matrix(&self) -> bool {
+        self.to_permutation_vector().is_ok()
+    }
+
+    /// Returns the column of the one in each row of a permutation matrix, the inverse of Matrix::permutation. Errors if
+    /// this isn't a permutation matrix
+    pub fn to_permutation_vector(&self) -> Result<Vec<usize>, &'static str> {
+        const NOT_PERMUTATION: &str = "The matrix is not a permutation matrix";
+        if self.rows != self.columns {
+            return Err(NOT_PERMUTATION);
+        }
+
+        let mut permutation: Vec<usize> = Vec::with_capacity(self.rows);
+        for row in 0..self.rows {
+            let mut one_column: Option<usize> = None;
+            for column in 0..self.columns {
+                let value: T = self[row][column];
+                if value.is_one() && one_column.is_none() {
+                    one_column = Some(column);
+                } else if !value.is_zero() {
+                    return Err(NOT_PERMUTATION);
+                }
+            }
+            permutation.push(one_column.ok_or(NOT_PERMUTATION)?);
+        }
+
+        // Each row has a single one, so it's a permutation matrix exactly when no column is used twice
+        Self::validate_permutation(&permutation, self.rows).map_err(|_| NOT_PERMUTATION)?;
+        Ok(permutation)
+    }
+
+    /// Solves Ax = b for a diagonal matrix by dividing each entry of b by the matching diagonal entry.
+    /// Errors if the matrix isn't square, has a non-zero entry off its diagonal, or has a zero on its diagonal
+    pub fn solve_diagonal(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        for row in 0..self.rows {
+            if let Some(column) =
+                (0..self.columns).find(|column| *column != row && !self[row][*column].is_zero())
+            {
+                return Err(MatrixError::NotDiagonal { row, column });
+            }
+        }
+        if b.len() != self.rows {
+            return Err(self.mismatch("solve_diagonal", b.len(), 1));
+        }
+
+        let mut x: Vec<T> = Vec::with_capacity(self.rows);
+        for (i, value) in b.iter().enumerate() {
+            if self[i][i] == T::zero() {
+                return Err(MatrixError::SingularDiagonal { index: i });
+            }
+            x.push(*value / self[i][i]);
+        }
+
+        Ok(x)
+    }
+
+    /// Returns the inverse of a diagonal matrix by inverting each diagonal entry.
+    /// Errors just as solve_diagonal does
+    pub fn inverse_diagonal(&self) -> Result<Self, MatrixError> {
+        let ones: Vec<T> = vec![T::one(); self.rows];
+
+        Ok(Self::from_diagonal(&self.solve_diagonal(&ones)?))
+    }
+
+    /// Returns this matrix with each row multiplied by the matching factor. This is the same as multiplying by the
+    /// diagonal matrix of the factors on the left, without building that matrix. Errors unless there is one factor
+    /// per row
+    pub fn scale_rows(&self, factors: &[T]) -> Result<Self, MatrixError> {
+        if factors.len() != self.rows {
+            return Err(self.mismatch("scale_rows", factors.len(), 1));
+        }
+
+        let mut output: Self = self.clone();
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                output.set_value(row, column, self[row][column] * factors[row]);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Returns this matrix with each column multiplied by the matching factor. This is the same as multiplying by the
+    /// diagonal matrix of the factors on the right, without building that matrix. Errors unless there is one factor
+    /// per column
+    pub fn scale_columns(&self, factors: &[T]) -> Result<Self, MatrixError> {
+        if factors.len() != self.columns {
+            return Err(self.mismatch("scale_columns", factors.len(), 1));
+        }
+
+        let mut output: Self = self.clone();
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                output.set_value(row, column, self[row][column] * factors[column]);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Returns this matrix with each row divided by its sum, so that every row sums to one.
+    /// Errors if any row sums to zero
+    pub fn row_stochastic(&self) -> Result<Self, &'static str> {
+        let sums: Vec<T> = self.row_sums();
+
+        if sums.contains(&T::zero()) {
+            return Err("A row of the matrix sums to zero");
+        }
+
+        // There is one sum per row, so this can't fail
+        let inverse_sums: Vec<T> = sums.into_iter().map(|sum| T::one() / sum).collect();
+        Ok(self.scale_rows(&inverse_sums).unwrap())
+    }
+
+    /// Returns this matrix with each column divided by its sum, so that every column sums to one.
+    /// Errors if any column sums to zero
+    pub fn column_stochastic(&self) -> Result<Self, &'static str> {
+        let sums: Vec<T> = self.column_sums();
+
+        if sums.contains(&T::zero()) {
+            return Err("A column of the matrix sums to zero");
+        }
+
+        // There is one sum per column, so this can't fail
+        let inverse_sums: Vec<T> = sums.into_iter().map(|sum| T::one() / sum).collect();
+        Ok(self.scale_columns(&inverse_sums).unwrap())
+    }
+
+    /// Creates a 3x3 homogeneous matrix that translates 2D points by (tx, ty). Transforms act on column vectors, so
+    /// in a product like a * b the transform b is applied first
+    pub fn translation_2d(tx: T, ty: T) -> Self {
+        let mut translation: Self = Self::identity_matrix(3);
+        translation.set_value(0, 2, tx);
+        translation.set_value(1, 2, ty);
+        translation
+    }
+
+    /// Creates a 3x3 homogeneous matrix that scales 2D points by sx along x and sy along y, about the origin
+    pub fn scaling_2d(sx: T, sy: T) -> Self {
+        Self::from_diagonal(&[sx, sy, T::one()])
+    }
+
+    /// Creates a 3x3 homogeneous matrix that shears 2D points, mapping (x, y) to (x + shx * y, y + shy * x)
+    pub fn shear_2d(shx: T, shy: T) -> Self {
+        let mut shear: Self = Self::identity_matrix(3);
+        shear.set_value(0, 1, shx);
+        shear.set_value(1, 0, shy);
+        shear
+    }
+
+    /// Applies this 3x3 homogeneous transform to a 2D point, dividing through by the resulting homogeneous coordinate.
+    /// Errors if the matrix isn't 3x3 or the homogeneous coordinate is zero
+    pub fn transform_point_2d(&self, p: [T; 2]) -> Result<[T; 2], MatrixError> {
+        if self.rows != 3 || self.columns != 3 {
+            return Err(MatrixError::UnexpectedShape {
+                expected: (3, 3),
+                actual: (self.rows, self.columns),
+            });
+        }
+
+        let transformed: Vec<T> = self.try_mul_vec(&[p[0], p[1], T::one()])?;
+        if transformed[2] == T::zero() {
+            return Err(MatrixError::PointAtInfinity);
+        }
+
+        Ok([
+            transformed[0] / transformed[2],
+            transformed[1] / transformed[2],
+        ])
+    }
+
+    /// Calculates the permanent, the determinant's sum over permutations without the alternating signs, using Ryser's
+    /// formula. This takes O(2^n * n^2) time, so it is only practical for small matrices
+    pub fn permanent(&self) -> Result<T, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+        if self.columns >= usize::BITS as usize {
+            return Err("The matrix is too large to calculate the permanent of");
+        }
+        if self.columns == 0 {
+            return Ok(T::one());
+        }
+
+        // Ryser's formula: perm(A) = (-1)^n * sum over column subsets S of (-1)^|S| * prod_i sum_{j in S} a_ij
+        let mut permanent: T = T::zero();
+        for subset in 1..(1usize << self.columns) {
+            let mut product: T = T::one();
+            for row in &self.matrix {
+                let row_sum: T = row
+                    .iter()
+                    .enumerate()
+                    .filter(|(column, _)| subset & (1 << column) != 0)
+                    .fold(T::zero(), |sum, (_, value)| sum + *value);
+                product *= row_sum;
+            }
+
+            if (self.columns - subset.count_ones() as usize) % 2 == 0 {
+                permanent += product;
+            } else {
+                permanent -= product;
+            }
+        }
+
+        Ok(permanent)
+    }
+
+    /// Calculates the coefficients of the characteristic polynomial det(xI - A) with the Faddeev-LeVerrier algorithm,
+    /// ordered from the leading x^n coefficient (always 1) down to the constant term. The divisions in the algorithm
+    /// are exact, so integer matrices give exact integer coefficients
+    pub fn characteristic_polynomial(&self) -> Result<Vec<T>, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let size: usize = self.rows;
+        let mut coefficients: Vec<T> = vec![T::one()];
+        let mut auxiliary: Self = Self::square_matrix(size);
+        let mut k: T = T::zero();
+
+        for _ in 0..size {
+            k += T::one();
+            auxiliary = self.clone() * auxiliary;
+            for i in 0..size {
+                auxiliary.matrix[i][i] += coefficients[coefficients.len() - 1];
+            }
+
+            let product_trace: T = (self.clone() * auxiliary.clone()).trace()?;
+            coefficients.push(-product_trace / k);
+        }
+
+        Ok(coefficients)
+    }
+
+    /// Checks the Cayley-Hamilton theorem for this matrix, by evaluating its characteristic polynomial at the matrix
+    /// itself with Horner's method and checking every entry of the result is within delta of zero
+    pub fn satisfies_characteristic(&self, delta: T) -> Result<bool, &'static str> {
+        let coefficients: Vec<T> = self.characteristic_polynomial()?;
+
+        let identity: Self = Self::identity_matrix(self.rows);
+        let mut evaluated: Self = Self::square_matrix(self.rows);
+        for coefficient in coefficients {
+            evaluated = self.clone() * evaluated + identity.clone() * coefficient;
+        }
+
+        let zero: Self = Self::square_matrix(self.rows);
+        Ok(evaluated.equals(&zero, delta))
+    }
+
+    /// Calculates the 2D convolution of this matrix with the kernel over the positions where the kernel fits entirely
+    /// inside the matrix, giving a (rows - kernel rows + 1) x (columns - kernel columns + 1) result. This is a true
+    /// convolution, so the kernel is flipped in both directions before sliding it over the matrix. Equivalent to
+    /// convolve_2d in ConvolutionMode::Valid
+    pub fn convolve(&self, kernel: &Self) -> Result<Self, MatrixError> {
+        self.convolve_2d(kernel, ConvolutionMode::Valid, Padding::Zero)
+    }
+
+    /// Calculates the 2D convolution of this matrix with the kernel, flipping the kernel in both directions before
+    /// sliding it over the matrix. The mode sets the output size, and for the Same and Full modes, where the kernel
+    /// overhangs the border, the padding sets the values it sees outside the matrix. In Valid mode the kernel never
+    /// leaves the matrix, so the padding has no effect
+    pub fn convolve_2d(
+        &self,
+        kernel: &Self,
+        mode: ConvolutionMode,
+        padding: Padding,
+    ) -> Result<Self, MatrixError> {
+        self.sliding_window(kernel, mode, padding, true)
+    }
+
+    /// Calculates the 2D cross-correlation of this matrix with the kernel, which is convolve_2d without flipping the
+    /// kernel. The mode and padding behave the same as in convolve_2d
+    pub fn correlate_2d(
+        &self,
+        kernel: &Self,
+        mode: ConvolutionMode,
+        padding: Padding,
+    ) -> Result<Self, MatrixError> {
+        self.sliding_window(kernel, mode, padding, false)
+    }
+
+    /// Returns this matrix with the given number of rows added above and below and columns added left and right, with
+    /// values chosen by the padding mode. A dimension of size zero can only be zero padded
+    pub fn padded(
+        &self,
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        mode: Padding,
+    ) -> Self {
+        let mut output: Self = Self::new(self.rows + top + bottom, self.columns + left + right);
+
+        for row in 0..output.rows {
+            let source_row: Option<usize> =
+                mode.source_index(row as isize - top as isize, self.rows);
+            for column in 0..output.columns {
+                let source_column: Option<usize> =
+                    mode.source_index(column as isize - left as isize, self.columns);
+                if let (Some(source_row), Some(source_column)) = (source_row, source_column) {
+                    output.set_value(row, column, self[source_row][source_column]);
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Returns the matrix of the absolute values of every entry
+    pub fn abs(&self) -> Self {
+        let mut output: Self = Self::new(self.rows, self.columns);
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                output.set_value(row, column, self[row][column].abs());
+            }
+        }
+
+        output
+    }
+
+    /// Returns the matrix of the signs of every entry: -1 for negative, 0 for zero, and 1 for positive. Unlike
+    /// Signed::signum for floats, zeros of either sign map to 0
+    pub fn signum(&self) -> Self {
+        let mut output: Self = Self::new(self.rows, self.columns);
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if !self[row][column].is_zero() {
+                    output.set_value(row, column, self[row][column].signum());
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Creates a labels.len() x num_classes matrix with a one in each row at the column given by that row's label, and
+    /// zeros elsewhere. Errors with the position and value of the first label that isn't less than num_classes
+    pub fn one_hot(labels: &[usize], num_classes: usize) -> Result<Self, MatrixError> {
+        Self::check_indices("label", labels, num_classes)?;
+        let mut output: Self = Self::new(labels.len(), num_classes);
+
+        for (row, label) in labels.iter().enumerate() {
+            output.set_value(row, *label, T::one());
+        }
+
+        Ok(output)
+    }
+
+    /// Creates a rows x columns matrix with ones at the given (row, column) positions and zeros elsewhere. Repeated
+    /// positions are allowed and still give a one. Errors if a position is outside the matrix
+    pub fn indicator(
+        rows: usize,
+        columns: usize,
+        positions: &[(usize, usize)],
+    ) -> Result<Self, MatrixError> {
+        let mut output: Self = Self::new(rows, columns);
+
+        for (position, (row, column)) in positions.iter().enumerate() {
+            if *row >= rows {
+                return Err(MatrixError::IndexOutOfRange {
+                    axis: "row",
+                    position,
+                    index: *row,
+                    bound: rows,
+                });
+            }
+            if *column >= columns {
+                return Err(MatrixError::IndexOutOfRange {
+                    axis: "column",
+                    position,
+                    index: *column,
+                    bound: columns,
+                });
+            }
+            output.set_value(*row, *column, T::one());
+        }
+
+        Ok(output)
+    }
+
+    /// Returns the label of each row of a one-hot matrix, the inverse of one_hot. Errors unless every row has exactly
+    /// one entry equal to one and zeros elsewhere
+    pub fn from_one_hot(&self) -> Result<Vec<usize>, MatrixError> {
+        let mut labels: Vec<usize> = Vec::with_capacity(self.rows);
+
+        for (index, row) in self.matrix.iter().enumerate() {
+            if row.iter().any(|value| !value.is_zero() && !value.is_one()) {
+                return Err(MatrixError::NotOneHot { row: index });
+            }
+
+            let mut ones = row.iter().enumerate().filter(|(_, value)| value.is_one());
+            match (ones.next(), ones.next()) {
+                (Some((label, _)), None) => labels.push(label),
+                _ => return Err(MatrixError::NotOneHot { row: index }),
+            }
+        }
+
+        Ok(labels)
+    }
+
+    /// Returns the matrix alternating the rows of this matrix and other, starting with this one, so it has twice as
+    /// many rows. Errors if the matrices aren't the same shape
+    pub fn interleave_rows(&self, other: &Self) -> Result<Self, &'static str> {
+        if self.rows != other.rows || self.columns != other.columns {
+            return Err("The matrices are not the same shape");
+        }
+
+        let matrix: Vec<Vec<T>> = self
+            .matrix
+            .iter()
+            .zip(&other.matrix)
+            .flat_map(|(row, other_row)| [row.clone(), other_row.clone()])
+            .collect();
+
+        Ok(Self {
+            matrix,
+            rows: 2 * self.rows,
+            columns: self.columns,
+        })
+    }
+
+    /// Splits this matrix into consecutive batches of batch_size rows, where the last batch may be smaller. Panics if
+    /// the batch size is zero
+    pub fn row_batches(&self, batch_size: usize) -> Vec<Self> {
+        if batch_size == 0 {
+            panic!("The batch size must be non-zero!");
+        }
+
+        self.matrix
+            .chunks(batch_size)
+            .map(|rows| Self {
+                matrix: rows.to_vec(),
+                rows: rows.len(),
+                columns: self.columns,
+            })
+            .collect()
+    }
+
+    /// Returns the matrix whose row i is row indices\[i\] of this matrix. Indices may repeat and come in any order, and an
+    /// empty list gives a 0 x columns matrix. Errors with the position and value of the first index that's out of range
+    pub fn select_rows(&self, indices: &[usize]) -> Result<Self, MatrixError> {
+        Self::check_indices("row", indices, self.rows)?;
+
+        Ok(Self {
+            matrix: indices
+                .iter()
+                .map(|index| self.matrix[*index].clone())
+                .collect(),
+            rows: indices.len(),
+            columns: self.columns,
+        })
+    }
+
+    /// Returns the matrix whose column j is column indices\[j\] of this matrix. Indices may repeat and come in any
+    /// order, and an empty list gives a rows x 0 matrix. Errors with the position and value of the first index that's
+    /// out of range
+    pub fn select_columns(&self, indices: &[usize]) -> Result<Self, MatrixError> {
+        Self::check_indices("column", indices, self.columns)?;
+
+        Ok(Self {
+            matrix: self
+                .matrix
+                .iter()
+                .map(|row| indices.iter().map(|index| row[*index]).collect())
+                .collect(),
+            rows: self.rows,
+            columns: indices.len(),
+        })
+    }
+
+    /// Returns the matrix whose entry (i, j) is entry (row_indices\[i\], column_indices\[j\]) of this matrix, which is
+    /// select_rows followed by select_columns. Errors if an index is out of range
+    pub fn gather(
+        &self,
+        row_indices: &[usize],
+        column_indices: &[usize],
+    ) -> Result<Self, MatrixError> {
+        self.select_rows(row_indices)?
+            .select_columns(column_indices)
+    }
+
+    /// Errors with the first edge that has a vertex that isn't below num_vertices, along with its position in the list
+    fn check_edges(num_vertices: usize, edges: &[(usize, usize)]) -> Result<(), MatrixError> {
+        for (position, (source, target)) in edges.iter().enumerate() {
+            if let Some(vertex) = [*source, *target]
+                .into_iter()
+                .find(|vertex| *vertex >= num_vertices)
+            {
+                return Err(MatrixError::IndexOutOfRange {
+                    axis: "vertex",
+                    position,
+                    index: vertex,
+                    bound: num_vertices,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Errors with the first index along the given axis that isn't below bound, along with its position in the list
+    fn check_indices(
+        axis: &'static str,
+        indices: &[usize],
+        bound: usize,
+    ) -> Result<(), MatrixError> {
+        match indices.iter().position(|index| *index >= bound) {
+            Some(position) => Err(MatrixError::IndexOutOfRange {
+                axis,
+                position,
+                index: indices[position],
+                bound,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates the num_vertices x edges.len() oriented incidence matrix of the given (source, target) edges, where
+    /// each column has 1 at its source and -1 at its target. For an undirected graph B * B^T is the graph Laplacian
+    /// regardless of the orientation chosen. Errors if a vertex is out of range, or on a self-loop, which has no
+    /// meaningful oriented column
+    pub fn incidence_from_edges(
+        num_vertices: usize,
+        edges: &[(usize, usize)],
+    ) -> Result<Self, MatrixError> {
+        Self::check_edges(num_vertices, edges)?;
+        let mut incidence: Self = Self::new(num_vertices, edges.len());
+
+        for (column, (source, target)) in edges.iter().enumerate() {
+            if source == target {
+                return Err(MatrixError::SelfLoop { vertex: *source });
+            }
+            incidence.set_value(*source, column, T::one());
+            incidence.set_value(*target, column, T::one().neg());
+        }
+
+        Ok(incidence)
+    }
+
+    /// Creates the num_vertices x num_vertices adjacency matrix of the given (source, target) edges. A directed edge
+    /// only sets the entry at (source, target), while an undirected edge also sets (target, source). Repeated edges add
+    /// up, so the entries count the edges between each pair of vertices, and a self-loop counts once. Errors if a
+    /// vertex is out of range
+    pub fn adjacency_from_edges(
+        num_vertices: usize,
+        edges: &[(usize, usize)],
+        directed: bool,
+    ) -> Result<Self, MatrixError> {
+        Self::check_edges(num_vertices, edges)?;
+        let mut adjacency: Self = Self::square_matrix(num_vertices);
+
+        for (source, target) in edges {
+            adjacency.matrix[*source][*target] += T::one();
+            if !directed && source != target {
+                adjacency.matrix[*target][*source] += T::one();
+            }
+        }
+
+        Ok(adjacency)
+    }
+
+    /// Returns the matrix of distinct rows in order of first occurrence, along with the index in it that each original
+    /// row maps to, so that select_rows with the mapping rebuilds this matrix. Rows are compared with ==, so a float row
+    /// containing NaN never matches anything, including itself
+    pub fn unique_rows(&self) -> (Self, Vec<usize>) {
+        self.group_rows(|a, b| a == b)
+    }
+
+    /// Returns each distinct row with the number of times it appears, in order of first occurrence
+    pub fn row_counts(&self) -> Vec<(Vec<T>, usize)> {
+        let (unique, mapping) = self.unique_rows();
+
+        let mut counts: Vec<usize> = vec![0; unique.rows];
+        for index in mapping {
+            counts[index] += 1;
+        }
+
+        unique.matrix.into_iter().zip(counts).collect()
+    }
+
+    /// Groups rows against representatives, which are the first rows of each group. Each row joins the earliest
+    /// representative that same accepts, or becomes a new representative
+    fn group_rows(&self, same: impl Fn(&[T], &[T]) -> bool) -> (Self, Vec<usize>) {
+        let mut representatives: Vec<Vec<T>> = Vec::new();
+        let mut mapping: Vec<usize> = Vec::with_capacity(self.rows);
+
+        for row in &self.matrix {
+            match representatives
+                .iter()
+                .position(|representative| same(representative, row))
+            {
+                Some(index) => mapping.push(index),
+                None => {
+                    mapping.push(representatives.len());
+                    representatives.push(row.clone());
+                }
+            }
+        }
+
+        let unique: Self = Self {
+            rows: representatives.len(),
+            matrix: representatives,
+            columns: self.columns,
+        };
+        (unique, mapping)
+    }
+}
+
+impl<T> Clone for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Safely clones this matrix
+    fn clone(&self) -> Self {
+        let mut matrix: Vec<Vec<T>> = Vec::with_capacity(self.rows);
+
+        for i in 0..self.rows {
+            matrix.push(self.matrix[i].clone());
+        }
+
+        Self {
+            matrix,
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+}
+
+impl<T> ops::Add for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    type Output = Self;
+
+    /// Adds two matrices together
+    fn add(self, rhs: Self) -> Self {
+        match self.try_add(&rhs) {
+            Ok(output) => output,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<T> ops::AddAssign for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Adds and reassigns two matrices together
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.clone() + rhs;
+    }
+}
+
+impl<T> ops::Sub for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    type Output = Self;
+
+    /// Subtracts the two matrices
+    fn sub(self, rhs: Self) -> Self {
+        match self.try_sub(&rhs) {
+            Ok(output) => output,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<T> ops::SubAssign for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Subtracts and assigns matrices
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.clone() - rhs;
+    }
+}
+
+impl<T> ops::Mul for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    type Output = Self;
+
+    /// Multiplies two matrices together. Abides by standard matrix multiplication rules
+    fn mul(self, rhs: Self) -> Self {
+        match self.try_mul(&rhs) {
+            Ok(output) => output,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<T> ops::Mul<T> for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    type Output = Self;
+
+    /// Scales this matrix by rhs
+    fn mul(self, rhs: T) -> Self {
+        let mut output: Self = Self::new(self.rows, self.columns);
+
+        for row_index in 0..self.rows {
+            for column_index in 0..self.columns {
+                let value: T = self[row_index][column_index] * rhs;
+                output.set_value(row_index, column_index, value);
+            }
+        }
+
+        output
+    }
+}
+
+/*
+Not entirely sure how to genericize this portion, if it's possible at all
+
+impl ops::Mul<Matrix<f64>> for f64 {
+    type Output = Matrix<f64>;
+
+    /// Scales rhs matrix by self
+    fn mul(self, rhs: Matrix<f64>) -> Matrix<f64> {
+        rhs * self
+    }
+}
+*/
+
+impl<T> ops::MulAssign for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Multiplies and assigns matrices
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<T> ops::MulAssign<T> for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Scales and assigns this matrix
+    fn mul_assign(&mut self, rhs: T) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<T> cmp::PartialEq for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.equals(other, T::zero())
+    }
+}
+
+impl<T> ops::Index<usize> for Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    type Output = Vec<T>;
+
+    /// Grabs the indicated row of the matrix. Can then index that row to get a value, ie Matrix\[row\]\[column\]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.matrix[index].as_ref()
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + PartialOrd,
+{
+    /// Returns true if the absolute value of each diagonal entry is at least the sum of the absolute values of the other
+    /// entries in its row, or strictly greater than that sum if strict is set. Non-square matrices are never dominant
+    pub fn is_diagonally_dominant(&self, strict: bool) -> bool {
+        if self.rows != self.columns {
+            return false;
+        }
+
+        for row in 0..self.rows {
+            let mut off_diagonal_sum: T = T::zero();
+            for column in 0..self.columns {
+                if column != row {
+                    off_diagonal_sum += self[row][column].abs();
+                }
+            }
+
+            let diagonal: T = self[row][row].abs();
+            if diagonal < off_diagonal_sum || (strict && diagonal == off_diagonal_sum) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns whether the determinant is less than, equal to, or greater than zero, where any determinant within
+    /// tolerance of zero counts as equal. When every entry and the tolerance are whole numbers, as they always are for
+    /// integer matrices, the determinant is calculated exactly with Bareiss elimination in i128, so it's classified
+    /// exactly however large it gets in T. Otherwise matrices up to 3x3 are evaluated by cofactor expansion and larger
+    /// ones by fraction-free Bareiss elimination in T. Errors if the matrix isn't square, or if an intermediate value
+    /// of the exact calculation overflows i128
+    pub fn determinant_sign(&self, tolerance: T) -> Result<cmp::Ordering, MatrixError>
+    where
+        T: num_traits::NumCast,
+    {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        let whole = |value: T| -> Option<i128> {
+            value
+                .to_i128()
+                .filter(|widened| T::from(*widened) == Some(value))
+        };
+        let widened: Option<Vec<Vec<i128>>> = self
+            .matrix
+            .iter()
+            .map(|row| row.iter().map(|value| whole(*value)).collect())
+            .collect();
+        if let (Some(entries), Some(tolerance)) = (widened, whole(tolerance)) {
+            let determinant: i128 =
+                Self::bareiss_determinant(entries).map_err(|_| MatrixError::Overflow {
+                    op: "determinant_sign",
+                })?;
+            return Ok(if determinant > tolerance {
+                cmp::Ordering::Greater
+            } else if determinant < tolerance.saturating_neg() {
+                cmp::Ordering::Less
+            } else {
+                cmp::Ordering::Equal
+            });
+        }
+
+        // A singular matrix has a zero last row in the integer echelon form, which makes the last pivot zero too
+        let determinant: T = if self.rows <= 3 {
+            self.closed_form_determinant()
+        } else {
+            self.integer_echelon_form()[self.rows - 1][self.columns - 1]
+        };
+
+        if determinant > tolerance {
+            Ok(cmp::Ordering::Greater)
+        } else if determinant < tolerance.neg() {
+            Ok(cmp::Ordering::Less)
+        } else {
+            Ok(cmp::Ordering::Equal)
+        }
+    }
+
+    /// Returns the inverse of an upper triangular matrix by back substitution, one column at a time. The output is
+    /// exactly upper triangular. Errors with the position of the first entry below the diagonal that exceeds the
+    /// tolerance, or the index of the first diagonal entry within the tolerance of zero
+    pub fn inverse_upper_triangular(&self, tolerance: T) -> Result<Self, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        for row in 0..self.rows {
+            for column in 0..row {
+                if self[row][column].abs() > tolerance {
+                    return Err(MatrixError::NotTriangular { row, column });
+                }
+            }
+            if self[row][row].abs() <= tolerance {
+                return Err(MatrixError::SingularDiagonal { index: row });
+            }
+        }
+
+        // Entries below the diagonal are never written, so they stay exactly zero
+        let mut inverse: Self = Self::square_matrix(self.rows);
+        for column in 0..self.columns {
+            inverse.set_value(column, column, T::one() / self[column][column]);
+
+            for row in (0..column).rev() {
+                let mut sum: T = T::zero();
+                for k in row + 1..=column {
+                    sum += self[row][k] * inverse[k][column];
+                }
+                inverse.set_value(row, column, sum.neg() / self[row][row]);
+            }
+        }
+
+        Ok(inverse)
+    }
+
+    /// Returns the inverse of a lower triangular matrix by substitution, one column at a time. The output is exactly
+    /// lower triangular. Errors with the position of an entry above the diagonal that exceeds the tolerance, or the
+    /// index of the first diagonal entry within the tolerance of zero
+    pub fn inverse_lower_triangular(&self, tolerance: T) -> Result<Self, MatrixError> {
+        // The transpose swaps every position, so swap the reported one back
+        let inverse: Self = self
+            .transpose()
+            .inverse_upper_triangular(tolerance)
+            .map_err(|error| match error {
+                MatrixError::NotSquare { rows, columns } => MatrixError::NotSquare {
+                    rows: columns,
+                    columns: rows,
+                },
+                MatrixError::NotTriangular { row, column } => MatrixError::NotTriangular {
+                    row: column,
+                    column: row,
+                },
+                other => other,
+            })?;
+
+        Ok(inverse.transpose())
+    }
+
+    /// Calculates the inverse like inverse, except that matrices up to 4x4 whose determinant is within the given
+    /// threshold of zero are reported as singular
+    pub fn inverse_with_threshold(&self, threshold: T) -> Result<Self, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+        if self.rows <= Self::CLOSED_FORM_MAX_SIZE && self.rows > 0 {
+            return self.closed_form_inverse(|determinant| determinant.abs() <= threshold);
+        }
+
+        self.inverse_gauss_jordan()
+    }
+
+    /// Returns this matrix with every entry clamped into the range [low, high]. Panics if low is greater than high
+    pub fn clamp(&self, low: T, high: T) -> Self {
+        if low > high {
+            panic!("The lower bound is greater than the upper bound!");
+        }
+
+        let mut output: Self = self.clone();
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if self[row][column] < low {
+                    output.set_value(row, column, low);
+                } else if self[row][column] > high {
+                    output.set_value(row, column, high);
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Returns the maximum of each pooling window. Windows start every stride entries, and include_partial chooses
+    /// whether windows overhanging the bottom or right edge are dropped, giving floor((size - pool) / stride) + 1
+    /// outputs per dimension, or pooled over the entries they cover, giving ceil((size - pool) / stride) + 1. Errors if
+    /// a pool size or stride is zero
+    pub fn max_pool(
+        &self,
+        pool_rows: usize,
+        pool_columns: usize,
+        stride_rows: usize,
+        stride_columns: usize,
+        include_partial: bool,
+    ) -> Result<Self, MatrixError> {
+        let (pooled, _) = self.max_pool_with_argmax(
+            pool_rows,
+            pool_columns,
+            stride_rows,
+            stride_columns,
+            include_partial,
+        )?;
+        Ok(pooled)
+    }
+
+    /// Pools like max_pool, and also returns the (row, column) in this matrix that each maximum came from, in the same
+    /// layout as the output, for routing gradients back through the pooling. Ties go to the first entry in row-major
+    /// order
+    #[allow(clippy::type_complexity)]
+    pub fn max_pool_with_argmax(
+        &self,
+        pool_rows: usize,
+        pool_columns: usize,
+        stride_rows: usize,
+        stride_columns: usize,
+        include_partial: bool,
+    ) -> Result<(Self, Vec<Vec<(usize, usize)>>), MatrixError> {
+        let (output_rows, output_columns) = self.pool_output_size(
+            pool_rows,
+            pool_columns,
+            stride_rows,
+            stride_columns,
+            include_partial,
+        )?;
+
+        let mut pooled: Self = Self::new(output_rows, output_columns);
+        let mut positions: Vec<Vec<(usize, usize)>> = Vec::with_capacity(output_rows);
+        for output_row in 0..output_rows {
+            let start_row: usize = output_row * stride_rows;
+            let end_row: usize = (start_row + pool_rows).min(self.rows);
+            let mut position_row: Vec<(usize, usize)> = Vec::with_capacity(output_columns);
+
+            for output_column in 0..output_columns {
+                let start_column: usize = output_column * stride_columns;
+                let end_column: usize = (start_column + pool_columns).min(self.columns);
+
+                let mut best: (usize, usize) = (start_row, start_column);
+                for row in start_row..end_row {
+                    for column in start_column..end_column {
+                        if self[row][column] > self[best.0][best.1] {
+                            best = (row, column);
+                        }
+                    }
+                }
+                pooled.set_value(output_row, output_column, self[best.0][best.1]);
+                position_row.push(best);
+            }
+            positions.push(position_row);
+        }
+
+        Ok((pooled, positions))
+    }
+
+    /// Calculates a row echelon form like row_echelon, but treats every entry within tolerance of zero as zero, both
+    /// when choosing the pivots and when EchelonForm::solve checks the rows without a pivot for consistency
+    pub fn row_echelon_with_tolerance(&self, tolerance: T) -> EchelonForm<T> {
+        self.eliminate(false, tolerance, |value, tolerance| {
+            value.abs() <= tolerance
+        })
+    }
+
+    /// Checks whether Ax = b has a solution by comparing the rank of A with the rank of [A|b], where the ranks come from
+    /// row_echelon_with_tolerance, so entries within delta of zero are never taken as pivots
+    pub fn in_column_space(&self, b: &[T], delta: T) -> Result<bool, &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+
+        // b has an entry for each row, so this can't fail
+        let augmented: Self = self
+            .try_combine(&Self::matrix_from_list(b, self.rows, 1))
+            .unwrap();
+        Ok(self.row_echelon_with_tolerance(delta).rank()
+            == augmented.row_echelon_with_tolerance(delta).rank())
+    }
+
+    /// Returns the order of the rows when sorted by the values in the given column, where entry i is the index of the
+    /// row that belongs at position i. The sort is stable, so tied rows keep their original order in either direction.
+    /// Values that aren't comparable even to themselves, such as NaN, go at the end in their original order. Errors if
+    /// the column is out of range
+    pub fn argsort_column(
+        &self,
+        column: usize,
+        descending: bool,
+    ) -> Result<Vec<usize>, MatrixError> {
+        Self::check_indices("column", &[column], self.columns)?;
+
+        let is_nan = |value: T| value.partial_cmp(&value).is_none();
+        let mut order: Vec<usize> = (0..self.rows).collect();
+        order.sort_by(|a, b| {
+            let (a, b) = (self[*a][column], self[*b][column]);
+            match (is_nan(a), is_nan(b)) {
+                (true, true) => cmp::Ordering::Equal,
+                (true, false) => cmp::Ordering::Greater,
+                (false, true) => cmp::Ordering::Less,
+                (false, false) if descending => b.partial_cmp(&a).unwrap_or(cmp::Ordering::Equal),
+                (false, false) => a.partial_cmp(&b).unwrap_or(cmp::Ordering::Equal),
+            }
+        });
+
+        Ok(order)
+    }
+
+    /// Returns a copy of this matrix with whole rows reordered by the values in the given column, following
+    /// argsort_column. Errors if the column is out of range
+    pub fn sort_rows_by_column(
+        &self,
+        column: usize,
+        descending: bool,
+    ) -> Result<Self, MatrixError> {
+        let order: Vec<usize> = self.argsort_column(column, descending)?;
+        self.select_rows(&order)
+    }
+
+    /// Deduplicates rows like unique_rows, but treats two rows as equal when every pair of entries is within delta.
+    /// Being within delta isn't transitive, so each row is compared only against the first row of each group found so
+    /// far, and joins the earliest one it's close to. Every row is therefore within delta of its group's first row,
+    /// though two rows in the same group may differ by up to twice delta
+    pub fn unique_rows_within(&self, delta: T) -> (Self, Vec<usize>) {
+        self.group_rows(|a, b| a.iter().zip(b).all(|(a, b)| (*a - *b).abs() <= delta))
+    }
+
+    /// Checks that no entry is negative, erroring with the position of the first negative entry in row-major order
+    fn check_non_negative(&self) -> Result<(), MatrixError> {
+        for (row, values) in self.matrix.iter().enumerate() {
+            if let Some(column) = values.iter().position(|value| *value < T::zero()) {
+                return Err(MatrixError::NegativeEntry { row, column });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + num_traits::PrimInt,
+{
+    /// Calculates the determinant exactly with Bareiss elimination in i128, where every division is exact and every
+    /// intermediate value is a minor of the matrix, so it takes cubic rather than factorial time. Errors if an
+    /// intermediate value overflows i128 or the determinant doesn't fit in T
+    pub fn determinant_bareiss(&self) -> Result<T, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let entries: Vec<Vec<i128>> = self
+            .widened_entries("determinant_bareiss")
+            .map_err(|_| "A matrix entry could not be converted")?;
+        let determinant: i128 = Self::bareiss_determinant(entries)?;
+        T::from(determinant).ok_or("The determinant doesn't fit in the element type")
+    }
+
+    /// Calculates the determinant modulo the given prime with Gaussian elimination in modular arithmetic, so
+    /// intermediate values never overflow or lose precision. The result is in the range [0, modulus)
+    pub fn determinant_mod(&self, modulus: i64) -> Result<i64, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+        let modulus: i128 = Self::validate_modulus(modulus)?;
+        let mut reduced: Vec<Vec<i128>> = self
+            .residues(modulus, "determinant_mod")
+            .map_err(|_| "A matrix entry could not be converted")?;
+
+        let mut determinant: i128 = 1;
+        for column in 0..self.columns {
+            let pivot_row: usize = match (column..self.rows).find(|row| reduced[*row][column] != 0)
+            {
+                Some(row) => row,
+                None => return Ok(0),
+            };
+            if pivot_row != column {
+                reduced.swap(pivot_row, column);
+                determinant = (modulus - determinant) % modulus;
+            }
+
+            let pivot: i128 = reduced[column][column];
+            determinant = determinant * pivot % modulus;
+            let pivot_inverse: i128 =
+                Self::modular_inverse(pivot, modulus).ok_or("The modulus is not prime")?;
+
+            let pivot_values: Vec<i128> = reduced[column].clone();
+            for row in reduced.iter_mut().skip(column + 1) {
+                let factor: i128 = row[column] * pivot_inverse % modulus;
+                if factor == 0 {
+                    continue;
+                }
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_values).skip(column) {
+                    *value = (*value - factor * pivot_value).rem_euclid(modulus);
+                }
+            }
+        }
+
+        Ok(determinant as i64)
+    }
+
+    /// Solves Ax = b modulo the given prime with Gauss-Jordan elimination in modular arithmetic. The matrix must be
+    /// square, and the solution is returned with entries in the range [0, modulus). Errors if the matrix is singular
+    /// modulo the modulus, or if a pivot has no modular inverse because the modulus isn't prime
+    pub fn solve_mod(&self, b: &[i64], modulus: i64) -> Result<Vec<i64>, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+
+        let modulus: i128 = Self::validate_modulus(modulus)?;
+        let mut augmented: Vec<Vec<i128>> = self
+            .residues(modulus, "solve_mod")
+            .map_err(|_| "A matrix entry could not be converted")?;
+        for (row, value) in augmented.iter_mut().zip(b) {
+            row.push((*value as i128).rem_euclid(modulus));
+        }
+
+        for column in 0..self.columns {
+            let pivot_row: usize = (column..self.rows)
+                .find(|row| augmented[*row][column] != 0)
+                .ok_or("The matrix is singular modulo the modulus")?;
+            augmented.swap(pivot_row, column);
+
+            let pivot_inverse: i128 = Self::modular_inverse(augmented[column][column], modulus)
+                .ok_or("The modulus is not prime")?;
+            augmented[column]
+                .iter_mut()
+                .for_each(|value| *value = *value * pivot_inverse % modulus);
+
+            let pivot_values: Vec<i128> = augmented[column].clone();
+            for (row_index, row) in augmented.iter_mut().enumerate() {
+                let factor: i128 = row[column];
+                if row_index == column || factor == 0 {
+                    continue;
+                }
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_values) {
+                    *value = (*value - factor * pivot_value).rem_euclid(modulus);
+                }
+            }
+        }
+
+        Ok(augmented
+            .iter()
+            .map(|row| row[self.columns] as i64)
+            .collect())
+    }
+
+    /// Calculates the inverse modulo the given modulus, which doesn't need to be prime, with every entry in
+    /// [0, modulus). Pivots are formed by combining rows with the coefficients of the extended Euclidean algorithm,
+    /// which never needs to divide by a non-unit, and all arithmetic is on residues widened to i128 so nothing
+    /// overflows. Errors with NotInvertibleModulo if the determinant shares a factor with the modulus, with
+    /// InvalidArgument if the modulus is below 2, and with Overflow if it's above i64::MAX, where products of residues
+    /// no longer fit in i128
+    pub fn inverse_mod(&self, modulus: T) -> Result<Self, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        let modulus: i128 = Self::widened_modulus(modulus, "inverse_mod")?;
+        let size: usize = self.rows;
+
+        let mut augmented: Vec<Vec<i128>> = self.residues(modulus, "inverse_mod")?;
+        for (row, values) in augmented.iter_mut().enumerate() {
+            values.extend((0..size).map(|column| i128::from(row == column)));
+        }
+
+        let combine = |a: i128, first: &[i128], b: i128, second: &[i128]| -> Vec<i128> {
+            let (a, b): (i128, i128) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+            first
+                .iter()
+                .zip(second)
+                .map(|(x, y)| (a * x % modulus + b * y % modulus) % modulus)
+                .collect()
+        };
+
+        for column in 0..size {
+            // Fold every entry below into the pivot with the determinant-one transformation [x y; -b/g a/g]
+            for row in column + 1..size {
+                let (a, b): (i128, i128) = (augmented[column][column], augmented[row][column]);
+                if b == 0 {
+                    continue;
+                }
+                let (gcd, x, y): (i128, i128, i128) = Self::extended_gcd(a, b);
+                let pivot_values: Vec<i128> = combine(x, &augmented[column], y, &augmented[row]);
+                augmented[row] = combine(-b / gcd, &augmented[column], a / gcd, &augmented[row]);
+                augmented[column] = pivot_values;
+            }
+
+            // The determinant is the product of the pivots, so it's a unit exactly when every pivot is
+            let pivot_inverse: i128 = Self::modular_inverse(augmented[column][column], modulus)
+                .ok_or(MatrixError::NotInvertibleModulo)?;
+            augmented[column] = combine(pivot_inverse, &augmented[column], 0, &augmented[column]);
+
+            let pivot_values: Vec<i128> = augmented[column].clone();
+            for (row_index, row) in augmented.iter_mut().enumerate() {
+                let factor: i128 = row[column];
+                if row_index != column && factor != 0 {
+                    *row = combine(1, row, -factor, &pivot_values);
+                }
+            }
+        }
+
+        // Every residue is below the modulus, which is a T, so this can't fail
+        let inverse: Vec<Vec<i128>> = augmented.iter().map(|row| row[size..].to_vec()).collect();
+        Ok(Self::narrowed_entries(&inverse).unwrap())
+    }
+
+    /// Multiplies two matrices modulo the given modulus, with every entry in [0, modulus). Each product and partial
+    /// sum is reduced on i128 residues, so large entries don't overflow. Errors if the left hand columns don't equal
+    /// the right hand rows, or for the same moduli as inverse_mod
+    pub fn mul_mod(&self, rhs: &Self, modulus: T) -> Result<Self, MatrixError> {
+        if self.columns != rhs.rows {
+            return Err(self.mismatch("mul_mod", rhs.rows, rhs.columns));
+        }
+        let modulus: i128 = Self::widened_modulus(modulus, "mul_mod")?;
+        let left: Vec<Vec<i128>> = self.residues(modulus, "mul_mod")?;
+        let right: Vec<Vec<i128>> = rhs.residues(modulus, "mul_mod")?;
+
+        let product: Vec<Vec<i128>> = left
+            .iter()
+            .map(|row| {
+                (0..rhs.columns)
+                    .map(|column| {
+                        row.iter().zip(&right).fold(0, |sum, (value, right_row)| {
+                            (sum + value * right_row[column] % modulus) % modulus
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Every residue is below the modulus, which is a T, so this can't fail
+        Ok(Self::narrowed_entries(&product).unwrap())
+    }
+
+    /// Counts the spanning trees of the graph with this adjacency matrix using Kirchhoff's matrix-tree theorem, as the
+    /// determinant of the graph Laplacian with its last row and column removed. Entries above one are parallel edges,
+    /// each of which can be chosen separately. The determinant is calculated exactly with fraction-free Bareiss
+    /// elimination. Errors if the matrix isn't symmetric with a zero diagonal and non-negative entries, or if the count
+    /// doesn't fit in a u64
+    pub fn spanning_tree_count(&self) -> Result<u64, MatrixError> {
+        const OVERFLOW: MatrixError = MatrixError::Overflow {
+            op: "spanning_tree_count",
+        };
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        if self.rows == 0 {
+            return Err(MatrixError::InvalidArgument {
+                name: "graph",
+                requirement: "must have at least one vertex",
+            });
+        }
+
+        let adjacency: Vec<Vec<i128>> = self.widened_entries("spanning_tree_count")?;
+        for (row, adjacency_row) in adjacency.iter().enumerate() {
+            if adjacency_row[row] != 0 {
+                return Err(MatrixError::SelfLoop { vertex: row });
+            }
+            for (column, value) in adjacency_row.iter().enumerate() {
+                if *value < 0 {
+                    return Err(MatrixError::NegativeEntry { row, column });
+                }
+                if *value != adjacency[column][row] {
+                    return Err(MatrixError::NotSymmetric);
+                }
+            }
+        }
+
+        let size: usize = self.rows - 1;
+        let mut reduced_laplacian: Vec<Vec<i128>> = vec![vec![0; size]; size];
+        for (row, laplacian_row) in reduced_laplacian.iter_mut().enumerate() {
+            let mut degree: i128 = 0;
+            for value in &adjacency[row] {
+                degree = degree.checked_add(*value).ok_or(OVERFLOW)?;
+            }
+            for (column, value) in laplacian_row.iter_mut().enumerate() {
+                *value = -adjacency[row][column];
+            }
+            laplacian_row[row] = degree;
+        }
+
+        let count: i128 = Self::bareiss_determinant(reduced_laplacian).map_err(|_| OVERFLOW)?;
+        u64::try_from(count).map_err(|_| OVERFLOW)
+    }
+
+    /// Calculates the row-style Hermite normal form H along with a unimodular U where U * A = H. H is upper triangular
+    /// in the echelon sense, each pivot is positive, the entries above each pivot lie in [0, pivot), and any zero rows
+    /// come last. Only exact integer row operations are used, combining rows with the coefficients of the extended
+    /// Euclidean algorithm, and every step is checked in i128. Errors if an intermediate value overflows i128 or an
+    /// entry of H or U doesn't fit in T
+    pub fn hermite_normal_form(&self) -> Result<(Self, Self), MatrixError> {
+        const OVERFLOW: MatrixError = MatrixError::Overflow {
+            op: "hermite_normal_form",
+        };
+
+        let mut hermite: Vec<Vec<i128>> = self.widened_entries("hermite_normal_form")?;
+        let mut unimodular: Vec<Vec<i128>> =
+            Self::identity_matrix(self.rows).widened_entries("hermite_normal_form")?;
+
+        let mut pivot_row: usize = 0;
+        for column in 0..self.columns {
+            if pivot_row == self.rows {
+                break;
+            }
+
+            // Fold every entry below into the pivot with the 2x2 determinant-one transformation
+            // [x y; -b/g a/g], which leaves the gcd in the pivot and zero below it
+            for row in pivot_row + 1..self.rows {
+                let (a, b): (i128, i128) = (hermite[pivot_row][column], hermite[row][column]);
+                if b == 0 {
+                    continue;
+                }
+                let (gcd, x, y): (i128, i128, i128) = Self::extended_gcd(a, b);
+                let (c, d): (i128, i128) = (-b / gcd, a / gcd);
+                for rows in [&mut hermite, &mut unimodular] {
+                    let new_pivot: Vec<i128> =
+                        Self::combine_rows(x, &rows[pivot_row], y, &rows[row]).ok_or(OVERFLOW)?;
+                    rows[row] =
+                        Self::combine_rows(c, &rows[pivot_row], d, &rows[row]).ok_or(OVERFLOW)?;
+                    rows[pivot_row] = new_pivot;
+                }
+            }
+
+            let pivot: i128 = hermite[pivot_row][column];
+            if pivot == 0 {
+                continue;
+            }
+            if pivot < 0 {
+                for rows in [&mut hermite, &mut unimodular] {
+                    rows[pivot_row] = Self::combine_rows(-1, &rows[pivot_row], 0, &rows[pivot_row])
+                        .ok_or(OVERFLOW)?;
+                }
+            }
+
+            let pivot: i128 = hermite[pivot_row][column];
+            for row in 0..pivot_row {
+                let quotient: i128 = hermite[row][column].div_euclid(pivot);
+                if quotient == 0 {
+                    continue;
+                }
+                for rows in [&mut hermite, &mut unimodular] {
+                    rows[row] = Self::combine_rows(1, &rows[row], -quotient, &rows[pivot_row])
+                        .ok_or(OVERFLOW)?;
+                }
+            }
+            pivot_row += 1;
+        }
+
+        Ok((
+            Self::narrowed_entries(&hermite).ok_or(OVERFLOW)?,
+            Self::narrowed_entries(&unimodular).ok_or(OVERFLOW)?,
+        ))
+    }
+
+    // -----PRIVATE HELPERS-----
+
+    /// Returns the entries of this matrix widened to i128, erroring as an overflow in op if one doesn't fit
+    fn widened_entries(&self, op: &'static str) -> Result<Vec<Vec<i128>>, MatrixError> {
+        self.matrix
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| value.to_i128().ok_or(MatrixError::Overflow { op }))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Converts widened entries back into a matrix, if they all fit in T
+    fn narrowed_entries(entries: &[Vec<i128>]) -> Option<Self> {
+        let rows: Vec<Vec<T>> = entries
+            .iter()
+            .map(|row| row.iter().map(|value| T::from(*value)).collect())
+            .collect::<Option<_>>()?;
+        Some(Self::from_vector(&rows))
+    }
+
+    /// Returns a * first + b * second, or None if any entry overflows
+    fn combine_rows(a: i128, first: &[i128], b: i128, second: &[i128]) -> Option<Vec<i128>> {
+        first
+            .iter()
+            .zip(second)
+            .map(|(x, y)| a.checked_mul(*x)?.checked_add(b.checked_mul(*y)?))
+            .collect()
+    }
+
+    /// Returns (g, x, y) with g = gcd(a, b) >= 0 and a * x + b * y = g
+    fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+        let (mut old_remainder, mut remainder): (i128, i128) = (a, b);
+        let (mut old_x, mut x): (i128, i128) = (1, 0);
+        let (mut old_y, mut y): (i128, i128) = (0, 1);
+
+        while remainder != 0 {
+            let quotient: i128 = old_remainder / remainder;
+            (old_remainder, remainder) = (remainder, old_remainder - quotient * remainder);
+            (old_x, x) = (x, old_x - quotient * x);
+            (old_y, y) = (y, old_y - quotient * y);
+        }
+
+        if old_remainder < 0 {
+            return (-old_remainder, -old_x, -old_y);
+        }
+        (old_remainder, old_x, old_y)
+    }
+
+    /// Checks that the modulus is usable for modular arithmetic, widening it so products of residues can't overflow
+    fn validate_modulus(modulus: i64) -> Result<i128, &'static str> {
+        if modulus < 2 {
+            return Err("The modulus must be at least 2");
+        }
+        Ok(modulus as i128)
+    }
+
+    /// Widens a modulus given in the element type, checking that it's at least 2 and that products of two residues fit
+    /// in i128
+    fn widened_modulus(modulus: T, op: &'static str) -> Result<i128, MatrixError> {
+        let widened: i128 = modulus.to_i128().ok_or(MatrixError::Overflow { op })?;
+        if widened < 2 {
+            return Err(MatrixError::InvalidArgument {
+                name: "modulus",
+                requirement: "must be at least 2",
+            });
+        }
+        if widened > i128::from(i64::MAX) {
+            return Err(MatrixError::Overflow { op });
+        }
+        Ok(widened)
+    }
+
+    /// Returns the entries of this matrix reduced into the range [0, modulus), erroring as an overflow in op if an
+    /// entry doesn't fit in i128
+    fn residues(&self, modulus: i128, op: &'static str) -> Result<Vec<Vec<i128>>, MatrixError> {
+        let mut entries: Vec<Vec<i128>> = self.widened_entries(op)?;
+        entries
+            .iter_mut()
+            .flatten()
+            .for_each(|value| *value = value.rem_euclid(modulus));
+        Ok(entries)
+    }
+
+    /// Calculates the inverse of value modulo the modulus with the extended Euclidean algorithm, if it exists
+    fn modular_inverse(value: i128, modulus: i128) -> Option<i128> {
+        let (mut old_remainder, mut remainder): (i128, i128) = (value, modulus);
+        let (mut old_coefficient, mut coefficient): (i128, i128) = (1, 0);
+
+        while remainder != 0 {
+            let quotient: i128 = old_remainder / remainder;
+            (old_remainder, remainder) = (remainder, old_remainder - quotient * remainder);
+            (old_coefficient, coefficient) =
+                (coefficient, old_coefficient - quotient * coefficient);
+        }
+
+        if old_remainder != 1 {
+            return None;
+        }
+        Some(old_coefficient.rem_euclid(modulus))
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + PartialOrd + num_traits::Bounded,
+{
+    /// Calculates the product in the (min, +) semiring, where entry (i, j) is the minimum over k of
+    /// self\[i\]\[k\] + rhs\[k\]\[j\]. T::max_value() stands for infinity, meaning there is no edge, and infinity plus
+    /// anything stays infinity. Errors if the inner dimensions don't match
+    pub fn min_plus_product(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        self.product_with::<MinPlus>(rhs)
+            .map_err(|_| self.mismatch("min_plus_product", rhs.rows, rhs.columns))
+    }
+
+    /// Calculates the shortest path distances between every pair of vertices of the graph with this weight matrix
+    /// using the Floyd-Warshall algorithm. Entry (i, j) is the weight of the edge from i to j, with T::max_value()
+    /// meaning no edge, and stays T::max_value() in the result when j is unreachable from i. The distance from a
+    /// vertex to itself is zero unless a negative cycle runs through it, which is an error naming the first such
+    /// vertex
+    pub fn all_pairs_shortest_paths(&self) -> Result<Self, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        let infinity: T = T::max_value();
+        let mut distances: Self = self.clone();
+        for vertex in 0..self.rows {
+            if distances[vertex][vertex] > T::zero() {
+                distances.set_value(vertex, vertex, T::zero());
+            }
+        }
+
+        for via in 0..self.rows {
+            for from in 0..self.rows {
+                if distances[from][via] == infinity {
+                    continue;
+                }
+                for to in 0..self.rows {
+                    if distances[via][to] == infinity {
+                        continue;
+                    }
+                    let length: T = distances[from][via] + distances[via][to];
+                    if length < distances[from][to] {
+                        distances.set_value(from, to, length);
+                    }
+                }
+            }
+        }
+
+        let negative: Option<usize> =
+            (0..self.rows).find(|vertex| distances[*vertex][*vertex] < T::zero());
+        if let Some(vertex) = negative {
+            return Err(MatrixError::NegativeCycle { vertex });
+        }
+
+        Ok(distances)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + Send + Sync,
+{
+    /// Returns the sum of each row of this matrix, summing the rows in parallel
+    pub fn par_row_sums(&self) -> Vec<T> {
+        self.matrix
+            .par_iter()
+            .map(|row| row.iter().fold(T::zero(), |sum, value| sum + *value))
+            .collect()
+    }
+
+    /// Returns the sum of each column of this matrix, summing the rows in parallel
+    pub fn par_column_sums(&self) -> Vec<T> {
+        self.matrix
+            .par_iter()
+            .fold(
+                || vec![T::zero(); self.columns],
+                |mut sums, row| {
+                    for (sum, value) in sums.iter_mut().zip(row) {
+                        *sum += *value;
+                    }
+                    sums
+                },
+            )
+            .reduce(
+                || vec![T::zero(); self.columns],
+                |mut sums, other| {
+                    for (sum, value) in sums.iter_mut().zip(other) {
+                        *sum += value;
+                    }
+                    sums
+                },
+            )
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Returns a copy of this matrix with its rows in a uniformly random order drawn from the given generator, so a
+    /// seeded generator gives a reproducible shuffle
+    pub fn shuffle_rows<R: Rng>(&self, rng: &mut R) -> Self {
+        let mut matrix: Vec<Vec<T>> = self.matrix.clone();
+        matrix.shuffle(rng);
+
+        Self {
+            matrix,
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// The most sweeps the eigenvalue iterations used internally by other methods are allowed
+    const INTERNAL_MAX_ITERATIONS: usize = 100;
+
+    /// Returns the Frobenius norm, which is the square root of the sum of the squares of every entry
+    pub fn frobenius_norm(&self) -> T {
+        let mut sum: T = T::zero();
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                sum += self[row][column] * self[row][column];
+            }
+        }
+
+        sum.sqrt()
+    }
+
+    /// Calculates the eigenvalues and eigenvectors of a symmetric matrix with the cyclic Jacobi method.
+    /// Returns the eigenvalues in descending order, along with a matrix whose columns are the matching unit eigenvectors.
+    /// An off-diagonal entry below the machine epsilon times the sum of its two diagonal entries is set to zero rather
+    /// than rotated, and a sweep that rotates nothing ends the iteration even if the tolerance is below what rounding
+    /// allows. Errors if the matrix isn't symmetric within the tolerance, or if the off-diagonal entries don't fall
+    /// below the tolerance within max_iterations sweeps
+    pub fn symmetric_eigen(
+        &self,
+        tolerance: T,
+        max_iterations: usize,
+    ) -> Result<(Vec<T>, Self), &'static str> {
+        if !self.is_symmetric(tolerance) {
+            return Err("The matrix was not symmetric");
+        }
+
+        let size: usize = self.rows;
+        let mut operating_matrix: Self = self.clone();
+        let mut eigenvectors: Self = Self::identity_matrix(size);
+        let two: T = T::one() + T::one();
+
+        let mut converged: bool = false;
+        let mut rotated: bool = true;
+        for _ in 0..=max_iterations {
+            // Once every off-diagonal entry is negligible next to its diagonal pair, no further rotation can change
+            // the result, so that also counts as converged
+            if !rotated {
+                converged = true;
+                break;
+            }
+
+            let mut off_diagonal: T = T::zero();
+            for row in 0..size {
+                for column in 0..size {
+                    if row != column {
+                        off_diagonal +=
+                            operating_matrix[row][column] * operating_matrix[row][column];
+                    }
+                }
+            }
+            if off_diagonal.sqrt() <= tolerance {
+                converged = true;
+                break;
+            }
+
+            // Rotate away each off-diagonal entry in turn
+            rotated = false;
+            for p in 0..size {
+                for q in p + 1..size {
+                    let negligible: T = T::epsilon()
+                        * (num_traits::Float::abs(operating_matrix[p][p])
+                            + num_traits::Float::abs(operating_matrix[q][q]));
+                    if num_traits::Float::abs(operating_matrix[p][q]) <= negligible {
+                        operating_matrix.set_value(p, q, T::zero());
+                        operating_matrix.set_value(q, p, T::zero());
+                        continue;
+                    }
+                    rotated = true;
+
+                    let theta: T = (operating_matrix[q][q] - operating_matrix[p][p])
+                        / (two * operating_matrix[p][q]);
+                    let t: T = num_traits::Float::signum(theta)
+                        / (num_traits::Float::abs(theta) + (theta * theta + T::one()).sqrt());
+                    let c: T = T::one() / (t * t + T::one()).sqrt();
+                    let s: T = t * c;
+
+                    for k in 0..size {
+                        let kp: T = operating_matrix[k][p];
+                        let kq: T = operating_matrix[k][q];
+                        operating_matrix.set_value(k, p, c * kp - s * kq);
+                        operating_matrix.set_value(k, q, s * kp + c * kq);
+                    }
+                    for k in 0..size {
+                        let pk: T = operating_matrix[p][k];
+                        let qk: T = operating_matrix[q][k];
+                        operating_matrix.set_value(p, k, c * pk - s * qk);
+                        operating_matrix.set_value(q, k, s * pk + c * qk);
+                    }
+                    for k in 0..size {
+                        let kp: T = eigenvectors[k][p];
+                        let kq: T = eigenvectors[k][q];
+                        eigenvectors.set_value(k, p, c * kp - s * kq);
+                        eigenvectors.set_value(k, q, s * kp + c * kq);
+                    }
+                }
+            }
+        }
+
+        if !converged {
+            return Err("The eigenvalues did not converge within the maximum number of iterations");
+        }
+
+        let mut order: Vec<usize> = (0..size).collect();
+        order.sort_by(|a, b| {
+            operating_matrix[*b][*b]
+                .partial_cmp(&operating_matrix[*a][*a])
+                .unwrap_or(cmp::Ordering::Equal)
+        });
+
+        let eigenvalues: Vec<T> = order.iter().map(|i| operating_matrix[*i][*i]).collect();
+        let mut sorted_eigenvectors: Self = Self::square_matrix(size);
+        for (new_column, old_column) in order.iter().enumerate() {
+            for row in 0..size {
+                sorted_eigenvectors.set_value(row, new_column, eigenvectors[row][*old_column]);
+            }
+        }
+
+        Ok((eigenvalues, sorted_eigenvectors))
+    }
+
+    /// Returns the principal square root of a symmetric positive semi-definite matrix, calculated as V * sqrt(D) * V^T
+    /// from its eigendecomposition. Eigenvalues down to -tolerance are treated as rounding error and clamped to zero,
+    /// while any more negative eigenvalue is an error. The symmetry check and the eigendecomposition don't use the
+    /// tolerance; they work to the machine epsilon scaled by the size and the Frobenius norm, which is the most
+    /// rounding the Jacobi sweeps can leave behind and also the smallest clamp used. The sweeps run until they stop
+    /// rotating, bounded only by the size of the matrix
+    pub fn sqrt(&self, tolerance: T) -> Result<Self, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        let size: T = T::from(self.rows.max(1)).unwrap();
+        let eigen_tolerance: T = size * T::epsilon() * self.frobenius_norm();
+        if !self.is_symmetric(eigen_tolerance) {
+            return Err(MatrixError::NotSymmetric);
+        }
+        let max_sweeps: usize = Self::INTERNAL_MAX_ITERATIONS.max(self.rows * self.rows);
+        // The symmetry was already checked with the same tolerance, so only the sweeps can fail
+        let (eigenvalues, eigenvectors) = self
+            .symmetric_eigen(eigen_tolerance, max_sweeps)
+            .map_err(|_| MatrixError::NotConverged {
+                iterations: max_sweeps,
+            })?;
+
+        let clamp_tolerance: T = tolerance.max(eigen_tolerance);
+        let mut root_diagonal: Self = Self::square_matrix(self.rows);
+        for (i, eigenvalue) in eigenvalues.into_iter().enumerate() {
+            if eigenvalue < clamp_tolerance.neg() {
+                return Err(MatrixError::NotPositiveSemiDefinite);
+            }
+            root_diagonal.set_value(i, i, eigenvalue.max(T::zero()).sqrt());
+        }
+
+        Ok(eigenvectors.clone() * root_diagonal * eigenvectors.transpose())
+    }
+
+    /// Returns a square root of a general square matrix using the Denman–Beavers iteration, which converges to the
+    /// principal square root for matrices with no eigenvalues on the closed negative real axis. Errors if an iterate
+    /// is singular or the change between iterates doesn't fall to the tolerance within max_iterations
+    pub fn sqrt_denman_beavers(
+        &self,
+        tolerance: T,
+        max_iterations: usize,
+    ) -> Result<Self, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        let half: T = T::one() / (T::one() + T::one());
+        let mut y: Self = self.clone();
+        let mut z: Self = Self::identity_matrix(self.rows);
+
+        for _ in 0..max_iterations {
+            let next_y: Self = (y.clone() + z.inverse().map_err(|_| MatrixError::Singular)?) * half;
+            let next_z: Self = (z + y.inverse().map_err(|_| MatrixError::Singular)?) * half;
+
+            let change: T = (next_y.clone() - y).frobenius_norm();
+            y = next_y;
+            z = next_z;
+
+            if change <= tolerance {
+                return Ok(y);
+            }
+        }
+
+        Err(MatrixError::NotConverged {
+            iterations: max_iterations,
+        })
+    }
+
+    /// Returns the orthogonal matrix nearest to this one in the Frobenius norm, which is the orthogonal factor of its
+    /// polar decomposition. Calculated with Higham's iteration X <- (X + X^-T) / 2, which converges quadratically, so
+    /// one more step is taken once the relative change drops below the square root of the machine epsilon. Errors if
+    /// the matrix isn't square or isn't invertible
+    pub fn nearest_orthogonal(&self) -> Result<Self, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let half: T = T::one() / (T::one() + T::one());
+        let tolerance: T = T::epsilon().sqrt();
+        let mut orthogonal: Self = self.clone();
+
+        for _ in 0..Self::INTERNAL_MAX_ITERATIONS {
+            let next: Self = (orthogonal.clone() + orthogonal.inverse()?.transpose()) * half;
+            let change: T = (next.clone() - orthogonal).frobenius_norm() / next.frobenius_norm();
+            orthogonal = next;
+
+            if change <= tolerance {
+                return Ok((orthogonal.clone() + orthogonal.inverse()?.transpose()) * half);
+            }
+        }
+
+        Err("The polar decomposition did not converge within the maximum number of iterations")
+    }
+
+    /// Returns the matrix exponential e^A, calculated by scaling the matrix down until its norm is below one,
+    /// summing the Taylor series, and then squaring back up
+    pub fn matrix_exp(&self) -> Result<Self, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+
+        let two: T = T::one() + T::one();
+        let mut squarings: i32 = 0;
+        let mut scale: T = T::one();
+        while self.frobenius_norm() / scale > T::one() {
+            scale *= two;
+            squarings += 1;
+        }
+        let scaled_matrix: Self = self.clone() * (T::one() / scale);
+
+        // The terms of the series shrink at least factorially once the norm is below one
+        let mut output: Self = Self::identity_matrix(self.rows);
+        let mut term: Self = Self::identity_matrix(self.rows);
+        for k in 1..30 {
+            term = term * scaled_matrix.clone() * (T::one() / T::from(k).unwrap());
+            output += term.clone();
+        }
+
+        for _ in 0..squarings {
+            output = output.clone() * output;
+        }
+
+        Ok(output)
+    }
+
+    /// Returns the principal logarithm of a diagonalizable 2x2 matrix with positive real eigenvalues. With distinct
+    /// eigenvalues l1 and l2 this is (ln(l1) (A - l2 I) - ln(l2) (A - l1 I)) / (l1 - l2), which is the
+    /// eigendecomposition written in terms of the projections onto each eigenspace
+    pub fn matrix_log_2x2(&self) -> Result<Self, &'static str> {
+        if self.rows != 2 || self.columns != 2 {
+            return Err("The matrix was not 2x2");
+        }
+
+        let two: T = T::one() + T::one();
+        let half_trace: T = (self[0][0] + self[1][1]) / two;
+        let determinant: T = self[0][0] * self[1][1] - self[0][1] * self[1][0];
+        let discriminant: T = half_trace * half_trace - determinant;
+        let threshold: T = T::epsilon() * (half_trace * half_trace + T::one());
+
+        if discriminant < threshold.neg() {
+            return Err("The matrix does not have real eigenvalues");
+        }
+
+        if discriminant <= threshold {
+            // A repeated eigenvalue is only diagonalizable if the matrix is already a multiple of the identity
+            let eigenvalue: T = half_trace;
+            let identity_multiple: Self = Self::identity_matrix(2) * eigenvalue;
+            let scale: T = eigenvalue.abs().max(T::one());
+            if (self.clone() - identity_multiple).frobenius_norm() > T::epsilon() * scale * two {
+                return Err("The matrix is not diagonalizable");
+            }
+            if eigenvalue <= T::zero() {
+                return Err("The matrix does not have positive eigenvalues");
+            }
+            return Ok(Self::identity_matrix(2) * eigenvalue.ln());
+        }
+
+        let root: T = discriminant.sqrt();
+        let larger: T = half_trace + root;
+        let smaller: T = half_trace - root;
+        if smaller <= T::zero() {
+            return Err("The matrix does not have positive eigenvalues");
+        }
+
+        let identity: Self = Self::identity_matrix(2);
+        let larger_part: Self = (self.clone() - identity.clone() * smaller) * larger.ln();
+        let smaller_part: Self = (self.clone() - identity * larger) * smaller.ln();
+
+        Ok((larger_part - smaller_part) * (T::one() / (larger - smaller)))
+    }
+
+    /// Runs power iteration on a square matrix, returning the estimated magnitude of the dominant eigenvalue and the
+    /// matching unit vector. The start vector is uneven so that it is unlikely to be orthogonal to the dominant eigenvector
+    fn power_iteration(
+        &self,
+        tolerance: T,
+        max_iterations: usize,
+    ) -> Result<(T, Vec<T>), MatrixError> {
+        let size: usize = self.rows;
+        let mut vector: Vec<T> = (0..size)
+            .map(|i| T::one() + T::from(i).unwrap() / T::from(size).unwrap())
+            .collect();
+        let norm: T = Self::vector_norm(&vector);
+        vector.iter_mut().for_each(|value| *value /= norm);
+
+        let mut magnitude: T = T::zero();
+        for _ in 0..max_iterations {
+            let mut next_vector: Vec<T> = self.try_mul_vec(&vector)?;
+            let next_magnitude: T = Self::vector_norm(&next_vector);
+
+            if next_magnitude == T::zero() {
+                return Ok((T::zero(), vector));
+            }
+            next_vector
+                .iter_mut()
+                .for_each(|value| *value /= next_magnitude);
+
+            let converged: bool = (next_magnitude - magnitude).abs() <= tolerance;
+            magnitude = next_magnitude;
+            vector = next_vector;
+            if converged {
+                return Ok((magnitude, vector));
+            }
+        }
+
+        Err(MatrixError::NotConverged {
+            iterations: max_iterations,
+        })
+    }
+
+    /// Checks whether this is a 3x3 rotation matrix, orthogonal with determinant 1, to within the square root of the
+    /// machine epsilon
+    fn is_rotation_3d(&self) -> bool {
+        if self.rows != 3 || self.columns != 3 {
+            return false;
+        }
+
+        let tolerance: T = T::epsilon().sqrt();
+        let orthogonality_error: T =
+            (self.clone() * self.transpose() - Self::identity_matrix(3)).frobenius_norm();
+        orthogonality_error <= tolerance && (self.determinant() - T::one()).abs() <= tolerance
+    }
+
+    /// Creates the 3x3 matrix rotating counterclockwise by angle radians about the x, y, or z axis for an axis of 0, 1,
+    /// or 2 respectively
+    fn axis_rotation(axis: usize, angle: T) -> Self {
+        let (sin, cos): (T, T) = angle.sin_cos();
+        let (first, second): (usize, usize) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        let mut rotation: Self = Self::identity_matrix(3);
+        rotation.set_value(first, first, cos);
+        rotation.set_value(first, second, -sin);
+        rotation.set_value(second, first, sin);
+        rotation.set_value(second, second, cos);
+        rotation
+    }
+
+    /// Creates the similarity transform that moves the centroid of the points to the origin and scales them to an
+    /// average distance of sqrt(2) from it, or None if the points all coincide
+    fn point_normalization_2d(points: &[[T; 2]]) -> Option<Self> {
+        let count: T = T::from(points.len()).unwrap();
+        let centroid_x: T = points.iter().fold(T::zero(), |sum, point| sum + point[0]) / count;
+        let centroid_y: T = points.iter().fold(T::zero(), |sum, point| sum + point[1]) / count;
+
+        let mean_distance: T = points.iter().fold(T::zero(), |sum, point| {
+            sum + (point[0] - centroid_x).hypot(point[1] - centroid_y)
+        }) / count;
+        if mean_distance == T::zero() {
+            return None;
+        }
+
+        let scale: T = T::from(std::f64::consts::SQRT_2).unwrap() / mean_distance;
+        Some(Self::scaling_2d(scale, scale) * Self::translation_2d(-centroid_x, -centroid_y))
+    }
+
+    /// Creates a 3x3 matrix from constant f64 entries
+    fn from_float_rows(rows: &[[f64; 3]; 3]) -> Self {
+        let vector: Vec<Vec<T>> = rows
+            .iter()
+            .map(|row| row.iter().map(|value| T::from(*value).unwrap()).collect())
+            .collect();
+        Self::from_vector(&vector)
+    }
+
+    /// Checks this is a valid weighted adjacency matrix, symmetric with non-negative weights, and returns the degree of
+    /// each vertex
+    fn adjacency_degrees(&self) -> Result<Vec<T>, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        if !self.is_symmetric(T::epsilon().sqrt() * self.frobenius_norm()) {
+            return Err(MatrixError::NotSymmetric);
+        }
+        self.check_non_negative()?;
+
+        Ok(self.row_sums())
+    }
+
+    /// Returns the Euclidean norm of the given vector
+    fn vector_norm(vector: &[T]) -> T {
+        vector
+            .iter()
+            .fold(T::zero(), |sum, value| sum + *value * *value)
+            .sqrt()
+    }
+
+    /// Estimates the spectral norm (the largest singular value) by power iteration on A^T A, stopping once successive
+    /// estimates of the singular value differ by about the tolerance. Works for rectangular matrices
+    pub fn spectral_norm(&self, tolerance: T, max_iterations: usize) -> Result<T, MatrixError> {
+        let gram_matrix: Self = self.transpose() * self.clone();
+
+        // The eigenvalue of A^T A is the square of the singular value, and a change of d in sigma changes sigma^2 by
+        // about 2 sigma d. Since sigma is at least the Frobenius norm over the square root of the rank bound, using
+        // that lower bound in place of sigma keeps the error in sigma within the tolerance
+        let rank_bound: T = T::from(self.rows.min(self.columns).max(1)).unwrap();
+        let sigma_lower_bound: T = self.frobenius_norm() / rank_bound.sqrt();
+        let two: T = T::one() + T::one();
+        let estimate: T = gram_matrix
+            .power_iteration(two * sigma_lower_bound * tolerance, max_iterations)?
+            .0;
+
+        Ok(estimate.sqrt())
+    }
+
+    /// Estimates the spectral radius (the largest eigenvalue magnitude) of a square matrix by power iteration, which is
+    /// the quantity convergence analysis of iterative methods needs. Takes the tolerance and iteration budget in the
+    /// same order as spectral_norm. Only the magnitude is returned. Convergence is slow when the two largest
+    /// eigenvalues have close magnitudes, and fails entirely if the dominant eigenvalues are a complex pair
+    pub fn spectral_radius(&self, tolerance: T, max_iterations: usize) -> Result<T, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        Ok(self.power_iteration(tolerance, max_iterations)?.0)
+    }
+
+    /// Returns the lower triangular Cholesky factor L of a symmetric positive-definite matrix, such that A = L L^T.
+    /// Errors if the matrix isn't symmetric or positive-definite
+    pub fn cholesky(&self) -> Result<Self, &'static str> {
+        if !self.is_symmetric(T::epsilon() * self.frobenius_norm()) {
+            return Err("The matrix was not symmetric");
+        }
+
+        let size: usize = self.rows;
+        let mut lower: Self = Self::square_matrix(size);
+
+        for column in 0..size {
+            let mut diagonal: T = self[column][column];
+            for k in 0..column {
+                diagonal -= lower[column][k] * lower[column][k];
+            }
+            if diagonal <= T::zero() {
+                return Err("The matrix was not positive-definite");
+            }
+            let diagonal_root: T = diagonal.sqrt();
+            lower.set_value(column, column, diagonal_root);
+
+            for row in column + 1..size {
+                let mut value: T = self[row][column];
+                for k in 0..column {
+                    value -= lower[row][k] * lower[column][k];
+                }
+                lower.set_value(row, column, value / diagonal_root);
+            }
+        }
+
+        Ok(lower)
+    }
+
+    /// Factors a symmetric matrix, which may be indefinite, as L D L^T with L unit lower triangular and D diagonal,
+    /// returning L and the diagonal of D. Only 1x1 pivots are used and there is no pivoting, so a pivot within
+    /// machine precision of zero is only allowed when the rest of its column is also zero, in which case it is recorded
+    /// as exactly zero. Errors if the matrix isn't symmetric or hits a zero pivot with a non-zero column below it
+    pub fn ldlt(&self) -> Result<(Self, Vec<T>), MatrixError> {
+        let threshold: T = T::epsilon() * self.frobenius_norm();
+        if !self.is_symmetric(threshold) {
+            return Err(MatrixError::NotSymmetric);
+        }
+
+        let size: usize = self.rows;
+        let mut lower: Self = Self::identity_matrix(size);
+        let mut diagonal: Vec<T> = Vec::with_capacity(size);
+
+        for column in 0..size {
+            let mut pivot: T = self[column][column];
+            for (k, value) in diagonal.iter().enumerate() {
+                pivot -= lower[column][k] * lower[column][k] * *value;
+            }
+
+            let mut below: Vec<T> = Vec::with_capacity(size - column - 1);
+            for row in column + 1..size {
+                let mut value: T = self[row][column];
+                for (k, d) in diagonal.iter().enumerate() {
+                    value -= lower[row][k] * lower[column][k] * *d;
+                }
+                below.push(value);
+            }
+
+            if pivot.abs() <= threshold {
+                if below.iter().any(|value| value.abs() > threshold) {
+                    return Err(MatrixError::ZeroPivot { index: column });
+                }
+                diagonal.push(T::zero());
+                continue;
+            }
+            for (offset, value) in below.into_iter().enumerate() {
+                lower.set_value(column + 1 + offset, column, value / pivot);
+            }
+            diagonal.push(pivot);
+        }
+
+        Ok((lower, diagonal))
+    }
+
+    /// Solves Ax = b for a symmetric matrix using its L D L^T factorization. Errors if the factorization fails or if
+    /// D has a zero entry, which means the matrix is singular
+    pub fn ldlt_solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        if b.len() != self.rows {
+            return Err(self.mismatch("ldlt_solve", b.len(), 1));
+        }
+
+        let (lower, diagonal) = self.ldlt()?;
+        if diagonal.iter().any(|value| value.is_zero()) {
+            return Err(MatrixError::Singular);
+        }
+
+        let size: usize = self.rows;
+        let mut solution: Vec<T> = b.to_vec();
+        for row in 0..size {
+            for k in 0..row {
+                solution[row] = solution[row] - lower[row][k] * solution[k];
+            }
+        }
+        for (value, d) in solution.iter_mut().zip(&diagonal) {
+            *value /= *d;
+        }
+        for row in (0..size).rev() {
+            for k in row + 1..size {
+                solution[row] = solution[row] - lower[k][row] * solution[k];
+            }
+        }
+
+        Ok(solution)
+    }
+
+    /// Returns the determinant of a symmetric matrix as the product of the diagonal of its L D L^T factorization
+    pub fn ldlt_determinant(&self) -> Result<T, MatrixError> {
+        let (_, diagonal) = self.ldlt()?;
+        Ok(diagonal
+            .into_iter()
+            .fold(T::one(), |product, value| product * value))
+    }
+
+    /// Returns the inertia of a symmetric matrix as the numbers of positive, negative and zero eigenvalues. By
+    /// Sylvester's law of inertia these match the signs of the diagonal of its L D L^T factorization
+    pub fn inertia(&self) -> Result<(usize, usize, usize), MatrixError> {
+        let (_, diagonal) = self.ldlt()?;
+
+        let positive: usize = diagonal.iter().filter(|value| **value > T::zero()).count();
+        let negative: usize = diagonal.iter().filter(|value| **value < T::zero()).count();
+        Ok((positive, negative, diagonal.len() - positive - negative))
+    }
+
+    /// Returns the symmetric positive-definite square root of a symmetric positive-definite matrix.
+    /// Errors if the matrix isn't symmetric positive-definite, which is checked by attempting a Cholesky factorization
+    pub fn sqrtm_spd(&self) -> Result<Self, &'static str> {
+        self.cholesky()?;
+
+        // A positive-definite matrix is symmetric with positive eigenvalues, so only the sweeps can fail
+        self.sqrt(T::zero())
+            .map_err(|_| "The eigenvalues did not converge within the maximum number of iterations")
+    }
+
+    /// Returns the singular values of this matrix in descending order, calculated as the square roots of the eigenvalues
+    /// of A^T A (or A A^T, whichever is smaller). Works for rectangular matrices. Errors if the eigenvalue sweeps don't
+    /// converge to the tolerance
+    pub fn singular_values(&self, tolerance: T) -> Result<Vec<T>, MatrixError> {
+        let gram_matrix: Self = if self.rows < self.columns {
+            self.clone() * self.transpose()
+        } else {
+            self.transpose() * self.clone()
+        };
+
+        // The Gram matrix is exactly symmetric, so only the sweeps can fail
+        let (eigenvalues, _) = gram_matrix
+            .symmetric_eigen(tolerance, Self::INTERNAL_MAX_ITERATIONS)
+            .map_err(|_| MatrixError::NotConverged {
+                iterations: Self::INTERNAL_MAX_ITERATIONS,
+            })?;
+
+        // Rounding can leave the eigenvalues of a singular matrix slightly negative
+        Ok(eigenvalues
+            .into_iter()
+            .map(|eigenvalue| eigenvalue.max(T::zero()).sqrt())
+            .collect())
+    }
+
+    /// Returns the nuclear norm, which is the sum of the singular values
+    pub fn nuclear_norm(&self, tolerance: T) -> Result<T, MatrixError> {
+        Ok(self
+            .singular_values(tolerance)?
+            .into_iter()
+            .fold(T::zero(), |sum, value| sum + value))
+    }
+
+    /// Returns the Schatten p-norm, which is the p-norm of the singular values. A p of infinity gives the spectral norm,
+    /// and a p of 1 and 2 give the nuclear and Frobenius norms respectively. Errors if p is below 1
+    pub fn schatten_norm(&self, p: T, tolerance: T) -> Result<T, MatrixError> {
+        if p < T::one() {
+            return Err(MatrixError::InvalidArgument {
+                name: "Schatten exponent",
+                requirement: "must be at least 1",
+            });
+        }
+
+        let singular_values: Vec<T> = self.singular_values(tolerance)?;
+
+        if p.is_infinite() {
+            return Ok(singular_values.first().copied().unwrap_or(T::zero()));
+        }
+
+        Ok(singular_values
+            .into_iter()
+            .fold(T::zero(), |sum, value| sum + value.powf(p))
+            .powf(T::one() / p))
+    }
+
+    /// Returns the Euclidean norm of b - Ax. Callers check that x has an entry for each column, so the product can't
+    /// fail
+    fn residual_norm(&self, x: &[T], b: &[T]) -> T {
+        let product: Vec<T> = self.try_mul_vec(x).unwrap();
+        let residual: Vec<T> = b.iter().zip(product).map(|(b, ax)| *b - ax).collect();
+
+        Self::vector_norm(&residual)
+    }
+
+    /// Solves Ax = b with one LU factorization and then improves the answer with iterative refinement: each step
+    /// computes the residual r = b - Ax against this matrix, solves A d = r with the same factorization, and moves x
+    /// to x + d. Stops after max_refinements steps, once the residual norm drops below tolerance, or as soon as a step
+    /// fails to reduce it, and returns the iterate with the smallest residual. Errors if the matrix is singular
+    pub fn solve_refined(
+        &self,
+        b: &[T],
+        max_refinements: usize,
+        tolerance: T,
+    ) -> Result<Vec<T>, MatrixError> {
+        if b.len() != self.rows {
+            return Err(self.mismatch("solve_refined", b.len(), 1));
+        }
+
+        let solver: LinearSolver<T> = self.factorize()?;
+        let mut x: Vec<T> = solver.solve(b)?;
+        let mut residual_norm: T = self.residual_norm(&x, b);
+
+        for _ in 0..max_refinements {
+            if residual_norm < tolerance {
+                break;
+            }
+
+            let product: Vec<T> = self.try_mul_vec(&x)?;
+            let residual: Vec<T> = b.iter().zip(product).map(|(b, ax)| *b - ax).collect();
+            let correction: Vec<T> = solver.solve(&residual)?;
+            let refined: Vec<T> = x.iter().zip(correction).map(|(x, d)| *x + d).collect();
+
+            let refined_norm: T = self.residual_norm(&refined, b);
+            if refined_norm >= residual_norm {
+                break;
+            }
+            x = refined;
+            residual_norm = refined_norm;
+        }
+
+        Ok(x)
+    }
+
+    /// Solves Ax = b with Jacobi iteration, returning once the residual norm |b - Ax| drops below tolerance.
+    /// The matrix must be diagonally dominant, which guarantees convergence when the dominance is strict
+    pub fn solve_jacobi(
+        &self,
+        b: &[T],
+        iterations: usize,
+        tolerance: T,
+    ) -> Result<Vec<T>, &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+        if !self.is_diagonally_dominant(false) {
+            return Err(
+                "The matrix was not diagonally dominant, so the iteration may not converge",
+            );
+        }
+
+        let mut x: Vec<T> = vec![T::zero(); self.columns];
+        for _ in 0..iterations {
+            if self.residual_norm(&x, b) < tolerance {
+                return Ok(x);
+            }
+
+            let mut next_x: Vec<T> = Vec::with_capacity(self.columns);
+            for row in 0..self.rows {
+                let mut value: T = b[row];
+                for (column, x_value) in x.iter().enumerate() {
+                    if column != row {
+                        value -= self[row][column] * *x_value;
+                    }
+                }
+                next_x.push(value / self[row][row]);
+            }
+            x = next_x;
+        }
+
+        if self.residual_norm(&x, b) < tolerance {
+            return Ok(x);
+        }
+
+        Err("The iteration did not converge within the maximum number of iterations")
+    }
+
+    /// Solves Ax = b with Gauss-Seidel iteration, returning once the residual norm |b - Ax| drops below tolerance.
+    /// Unlike Jacobi iteration, each entry is updated in place and used immediately, which usually converges faster.
+    /// The matrix must be diagonally dominant, which guarantees convergence when the dominance is strict
+    pub fn solve_gauss_seidel(
+        &self,
+        b: &[T],
+        iterations: usize,
+        tolerance: T,
+    ) -> Result<Vec<T>, &'static str> {
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+        if !self.is_diagonally_dominant(false) {
+            return Err(
+                "The matrix was not diagonally dominant, so the iteration may not converge",
+            );
+        }
+
+        let mut x: Vec<T> = vec![T::zero(); self.columns];
+        for _ in 0..iterations {
+            if self.residual_norm(&x, b) < tolerance {
+                return Ok(x);
+            }
+
+            for row in 0..self.rows {
+                let mut value: T = b[row];
+                for (column, x_value) in x.iter().enumerate() {
+                    if column != row {
+                        value -= self[row][column] * *x_value;
+                    }
+                }
+                x[row] = value / self[row][row];
+            }
+        }
+
+        if self.residual_norm(&x, b) < tolerance {
+            return Ok(x);
+        }
+
+        Err("The iteration did not converge within the maximum number of iterations")
+    }
+
+    /// Creates a 3x3 homogeneous matrix that rotates 2D points counterclockwise by theta radians around the center
+    /// (cx, cy). This is equivalent to translation_2d(cx, cy) * rotation * translation_2d(-cx, -cy)
+    pub fn rotation_about_2d(theta: T, cx: T, cy: T) -> Self {
+        let (sin, cos): (T, T) = theta.sin_cos();
+
+        Self::from_vector(&[
+            vec![cos, -sin, cx - cos * cx + sin * cy],
+            vec![sin, cos, cy - sin * cx - cos * cy],
+            vec![T::zero(), T::zero(), T::one()],
+        ])
+    }
+
+    /// Calculates the stationary distribution of a row-stochastic matrix, the left eigenvector for eigenvalue 1
+    /// normalized to sum to one. Uses power iteration on the lazy chain (P + I) / 2, which shares its stationary
+    /// distribution with P but also converges for periodic chains, and stops once the distribution changes by at most
+    /// the tolerance in the 1-norm. Errors if any entry is negative, if a row doesn't sum to one within the square root
+    /// of the machine epsilon, or if the iteration doesn't converge
+    pub fn stationary_distribution(
+        &self,
+        iterations: usize,
+        tolerance: T,
+    ) -> Result<Vec<T>, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+        if self.matrix.iter().flatten().any(|value| *value < T::zero()) {
+            return Err("The matrix has a negative entry");
+        }
+        // Validation is independent of the convergence tolerance, so a loose tolerance doesn't accept a matrix that
+        // isn't really stochastic, and a tight one doesn't reject rows that only differ from one by rounding
+        let stochastic_tolerance: T = T::epsilon().sqrt();
+        if self
+            .row_sums()
+            .iter()
+            .any(|sum| (*sum - T::one()).abs() > stochastic_tolerance)
+        {
+            return Err("The matrix is not row-stochastic");
+        }
+
+        let size: usize = self.rows;
+        let half: T = T::from(0.5).unwrap();
+        let mut distribution: Vec<T> = vec![T::one() / T::from(size).unwrap(); size];
+
+        for _ in 0..iterations {
+            let mut next_distribution: Vec<T> =
+                distribution.iter().map(|value| *value * half).collect();
+            for (row, weight) in self.matrix.iter().zip(&distribution) {
+                for (next_value, value) in next_distribution.iter_mut().zip(row) {
+                    *next_value += half * *weight * *value;
+                }
+            }
+
+            let total: T = next_distribution
+                .iter()
+                .fold(T::zero(), |sum, value| sum + *value);
+            next_distribution
+                .iter_mut()
+                .for_each(|value| *value /= total);
+
+            let change: T = next_distribution
+                .iter()
+                .zip(&distribution)
+                .fold(T::zero(), |sum, (next, previous)| {
+                    sum + (*next - *previous).abs()
+                });
+            distribution = next_distribution;
+            if change <= tolerance {
+                return Ok(distribution);
+            }
+        }
+
+        Err("The power iteration did not converge within the maximum number of iterations")
+    }
+
+    /// Creates a 4x4 perspective projection matrix from a vertical field of view in radians, using the OpenGL
+    /// convention: a right-handed view space with the camera looking down -z, and depth mapped so that the near plane
+    /// lands at -1 and the far plane at 1 after the divide by w. Errors unless 0 < fov_y < pi, aspect > 0, and
+    /// 0 < near < far
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T) -> Result<Self, MatrixError> {
+        if fov_y <= T::zero() || fov_y >= T::from(std::f64::consts::PI).unwrap() {
+            return Err(MatrixError::InvalidArgument {
+                name: "field of view",
+                requirement: "must be between 0 and pi",
+            });
+        }
+        if aspect <= T::zero() {
+            return Err(MatrixError::InvalidArgument {
+                name: "aspect ratio",
+                requirement: "must be positive",
+            });
+        }
+        if near <= T::zero() || near >= far {
+            return Err(MatrixError::InvalidArgument {
+                name: "clipping planes",
+                requirement: "must satisfy 0 < near < far",
+            });
+        }
+
+        let two: T = T::one() + T::one();
+        let focal_length: T = T::one() / (fov_y / two).tan();
+        let mut projection: Self = Self::square_matrix(4);
+        projection.set_value(0, 0, focal_length / aspect);
+        projection.set_value(1, 1, focal_length);
+        projection.set_value(2, 2, (far + near) / (near - far));
+        projection.set_value(2, 3, two * far * near / (near - far));
+        projection.set_value(3, 2, -T::one());
+
+        Ok(projection)
+    }
+
+    /// Creates a 4x4 orthographic projection matrix mapping the given box to [-1, 1] on every axis, in the same
+    /// convention as perspective: the camera looks down -z and near and far are distances along that direction.
+    /// Errors if either extent is zero or near >= far
+    pub fn orthographic(
+        left: T,
+        right: T,
+        bottom: T,
+        top: T,
+        near: T,
+        far: T,
+    ) -> Result<Self, MatrixError> {
+        if left == right || bottom == top {
+            return Err(MatrixError::InvalidArgument {
+                name: "projection box",
+                requirement: "must have a non-zero width and height",
+            });
+        }
+        if near >= far {
+            return Err(MatrixError::InvalidArgument {
+                name: "clipping planes",
+                requirement: "must satisfy near < far",
+            });
+        }
+
+        let two: T = T::one() + T::one();
+        let mut projection: Self = Self::identity_matrix(4);
+        projection.set_value(0, 0, two / (right - left));
+        projection.set_value(1, 1, two / (top - bottom));
+        projection.set_value(2, 2, -two / (far - near));
+        projection.set_value(0, 3, -(right + left) / (right - left));
+        projection.set_value(1, 3, -(top + bottom) / (top - bottom));
+        projection.set_value(2, 3, -(far + near) / (far - near));
+
+        Ok(projection)
+    }
+
+    /// Creates a 4x4 view matrix that moves world coordinates into the frame of a camera at eye looking toward target,
+    /// with up as a hint for the vertical direction. Uses the same convention as perspective and orthographic, so
+    /// after the transform the camera sits at the origin looking down -z with +y up. Errors if eye equals target or
+    /// if the view direction is parallel to up
+    pub fn look_at(eye: [T; 3], target: [T; 3], up: [T; 3]) -> Result<Self, MatrixError> {
+        let cross = |a: [T; 3], b: [T; 3]| -> [T; 3] {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let dot = |a: [T; 3], b: [T; 3]| -> T { a[0] * b[0] + a[1] * b[1] + a[2] * b[2] };
+
+        let direction: [T; 3] = [target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]];
+        let direction_norm: T = Self::vector_norm(&direction);
+        if direction_norm == T::zero() {
+            return Err(MatrixError::InvalidArgument {
+                name: "target",
+                requirement: "must differ from the eye position",
+            });
+        }
+        let forward: [T; 3] = direction.map(|value| value / direction_norm);
+
+        let up_norm: T = Self::vector_norm(&up);
+        let side: [T; 3] = cross(forward, up);
+        let side_norm: T = Self::vector_norm(&side);
+        if side_norm <= T::epsilon() * up_norm {
+            return Err(MatrixError::InvalidArgument {
+                name: "up vector",
+                requirement: "must not be parallel to the view direction",
+            });
+        }
+        let side: [T; 3] = side.map(|value| value / side_norm);
+        let true_up: [T; 3] = cross(side, forward);
+
+        let mut view: Self = Self::identity_matrix(4);
+        for column in 0..3 {
+            view.set_value(0, column, side[column]);
+            view.set_value(1, column, true_up[column]);
+            view.set_value(2, column, -forward[column]);
+        }
+        view.set_value(0, 3, -dot(side, eye));
+        view.set_value(1, 3, -dot(true_up, eye));
+        view.set_value(2, 3, dot(forward, eye));
+
+        Ok(view)
+    }
+
+    /// Creates the 3x3 rotation matrix for the quaternion with components in [w, x, y, z] order, where w is the
+    /// scalar part. The quaternion is normalized first, so it doesn't need to be a unit quaternion. Errors on the zero
+    /// quaternion
+    pub fn from_quaternion(q: [T; 4]) -> Result<Self, MatrixError> {
+        let norm: T = Self::vector_norm(&q);
+        if norm == T::zero() {
+            return Err(MatrixError::InvalidArgument {
+                name: "quaternion",
+                requirement: "must not be zero",
+            });
+        }
+
+        let [w, x, y, z]: [T; 4] = q.map(|value| value / norm);
+        let one: T = T::one();
+        let two: T = one + one;
+
+        Ok(Self::from_vector(&[
+            vec![
+                one - two * (y * y + z * z),
+                two * (x * y - w * z),
+                two * (x * z + w * y),
+            ],
+            vec![
+                two * (x * y + w * z),
+                one - two * (x * x + z * z),
+                two * (y * z - w * x),
+            ],
+            vec![
+                two * (x * z - w * y),
+                two * (y * z + w * x),
+                one - two * (x * x + y * y),
+            ],
+        ]))
+    }
+
+    /// Calculates the unit quaternion, in [w, x, y, z] order, of this 3x3 rotation matrix. Since q and -q represent
+    /// the same rotation, the quaternion whose first non-zero component is positive is returned, which is a positive w
+    /// except for 180 degree rotations where w is zero. The square root is taken of the largest
+    /// of the trace and diagonal entries, which stays accurate for 180 degree rotations where the trace is -1. Errors
+    /// if this matrix isn't a rotation
+    pub fn to_quaternion(&self) -> Result<[T; 4], MatrixError> {
+        if !self.is_rotation_3d() {
+            return Err(MatrixError::NotRotation);
+        }
+
+        let m: &Vec<Vec<T>> = &self.matrix;
+        let one: T = T::one();
+        let two: T = one + one;
+        let four: T = two + two;
+        let trace: T = m[0][0] + m[1][1] + m[2][2];
+
+        let quaternion: [T; 4] = if trace >= m[0][0] && trace >= m[1][1] && trace >= m[2][2] {
+            let s: T = (one + trace).sqrt() * two;
+            [
+                s / four,
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+            ]
+        } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+            let s: T = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * two;
+            [
+                (m[2][1] - m[1][2]) / s,
+                s / four,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+            ]
+        } else if m[1][1] >= m[2][2] {
+            let s: T = (one + m[1][1] - m[0][0] - m[2][2]).sqrt() * two;
+            [
+                (m[0][2] - m[2][0]) / s,
+                (m[0][1] + m[1][0]) / s,
+                s / four,
+                (m[1][2] + m[2][1]) / s,
+            ]
+        } else {
+            let s: T = (one + m[2][2] - m[0][0] - m[1][1]).sqrt() * two;
+            [
+                (m[1][0] - m[0][1]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                s / four,
+            ]
+        };
+
+        let norm: T = Self::vector_norm(&quaternion);
+        let leading: T = quaternion
+            .iter()
+            .copied()
+            .find(|value| !value.is_zero())
+            .unwrap_or(norm);
+        let sign: T = if leading < T::zero() { -norm } else { norm };
+        Ok(quaternion.map(|value| value / sign))
+    }
+
+    /// Creates the 3x3 rotation matrix for the given Euler angles in radians, where angles\[n\] is the rotation about
+    /// the nth axis of the order, and the axis rotations are multiplied in the order they're listed
+    pub fn from_euler_angles(order: EulerOrder, angles: [T; 3]) -> Self {
+        let axes: [usize; 3] = order.axes();
+
+        Self::axis_rotation(axes[0], angles[0])
+            * Self::axis_rotation(axes[1], angles[1])
+            * Self::axis_rotation(axes[2], angles[2])
+    }
+
+    /// Calculates the Euler angles in radians of this 3x3 rotation matrix in the given order, the inverse of
+    /// from_euler_angles. The middle angle is in [-pi/2, pi/2] and the others are in [-pi, pi]. At gimbal lock, when
+    /// the middle angle is +-pi/2, only the sum or difference of the other two angles is determined, so the last angle
+    /// is set to zero. Errors if this matrix isn't a rotation
+    pub fn to_euler_angles(&self, order: EulerOrder) -> Result<[T; 3], MatrixError> {
+        if !self.is_rotation_3d() {
+            return Err(MatrixError::NotRotation);
+        }
+
+        let [i, j, k]: [usize; 3] = order.axes();
+        // Odd axis orders flip the sign of the off-diagonal terms
+        let sign: T = if (j + 3 - i) % 3 == 1 {
+            T::one()
+        } else {
+            -T::one()
+        };
+        let m: &Vec<Vec<T>> = &self.matrix;
+
+        let middle_cosine: T = (m[i][i] * m[i][i] + m[i][j] * m[i][j]).sqrt();
+        let middle: T = (sign * m[i][k]).atan2(middle_cosine);
+
+        if middle_cosine <= T::epsilon().sqrt() {
+            let first: T = (sign * m[k][j]).atan2(m[j][j]);
+            return Ok([first, middle, T::zero()]);
+        }
+
+        let first: T = (-sign * m[j][k]).atan2(m[k][k]);
+        let last: T = (-sign * m[i][j]).atan2(m[i][i]);
+        Ok([first, middle, last])
+    }
+
+    /// Estimates the 3x3 homography mapping each src point onto the matching dst point with the normalized direct
+    /// linear transform. Both point sets are translated and scaled to have their centroid at the origin and an average
+    /// distance of sqrt(2) from it, the homography is taken from the null space of the 2n x 9 design matrix (the
+    /// smallest eigenvector of A^T A), and the result is denormalized and scaled so the bottom-right entry is 1.
+    /// With more than four points this is a least-squares fit. Errors if there are fewer than four correspondences,
+    /// the point lists differ in length, the points are degenerate (for example three of four are collinear), or the
+    /// homography sends the origin to infinity
+    pub fn homography_from_points(src: &[[T; 2]], dst: &[[T; 2]]) -> Result<Self, MatrixError> {
+        const DEGENERATE: MatrixError = MatrixError::InvalidArgument {
+            name: "point configuration",
+            requirement: "must not be degenerate",
+        };
+        if src.len() != dst.len() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "homography_from_points",
+                left: (src.len(), 2),
+                right: (dst.len(), 2),
+            });
+        }
+        if src.len() < 4 {
+            return Err(MatrixError::InvalidArgument {
+                name: "point lists",
+                requirement: "must hold at least 4 points",
+            });
+        }
+
+        let src_normalization: Self = Self::point_normalization_2d(src).ok_or(DEGENERATE)?;
+        let dst_normalization: Self = Self::point_normalization_2d(dst).ok_or(DEGENERATE)?;
+
+        let mut design_matrix: Self = Self::new(2 * src.len(), 9);
+        for (i, (src_point, dst_point)) in src.iter().zip(dst).enumerate() {
+            let [x, y]: [T; 2] = src_normalization.transform_point_2d(*src_point)?;
+            let [u, v]: [T; 2] = dst_normalization.transform_point_2d(*dst_point)?;
+
+            design_matrix.matrix[2 * i] = vec![
+                -x,
+                -y,
+                -T::one(),
+                T::zero(),
+                T::zero(),
+                T::zero(),
+                u * x,
+                u * y,
+                u,
+            ];
+            design_matrix.matrix[2 * i + 1] = vec![
+                T::zero(),
+                T::zero(),
+                T::zero(),
+                -x,
+                -y,
+                -T::one(),
+                v * x,
+                v * y,
+                v,
+            ];
+        }
+
+        let gram_matrix: Self = design_matrix.transpose() * design_matrix;
+        // The Gram matrix is exactly symmetric, so only the sweeps can fail
+        let (eigenvalues, eigenvectors) = gram_matrix
+            .symmetric_eigen(
+                T::epsilon() * gram_matrix.frobenius_norm(),
+                Self::INTERNAL_MAX_ITERATIONS,
+            )
+            .map_err(|_| MatrixError::NotConverged {
+                iterations: Self::INTERNAL_MAX_ITERATIONS,
+            })?;
+
+        // A unique solution needs a one-dimensional null space, so the second smallest eigenvalue must be non-zero
+        if eigenvalues[7] <= T::epsilon().sqrt() * eigenvalues[0] {
+            return Err(DEGENERATE);
+        }
+
+        let mut normalized_homography: Self = Self::square_matrix(3);
+        for i in 0..9 {
+            normalized_homography.set_value(i / 3, i % 3, eigenvectors[i][8]);
+        }
+        // Collinear points mapped to non-collinear ones can only be fit by a singular, rank-deficient transform
+        if normalized_homography.determinant().abs() <= T::epsilon().sqrt() {
+            return Err(DEGENERATE);
+        }
+
+        // The normalization only fails to invert if its scale underflows, which spread out points can cause
+        let homography: Self = dst_normalization.inverse().map_err(|_| DEGENERATE)?
+            * normalized_homography
+            * src_normalization;
+        let scale: T = homography[2][2];
+        if scale.abs() <= T::epsilon() * homography.frobenius_norm() {
+            return Err(MatrixError::PointAtInfinity);
+        }
+
+        Ok(homography * (T::one() / scale))
+    }
+
+    /// Approximates a symmetric matrix by the rank k matrix sum(lambda_i * v_i * v_i^T) over its k eigenpairs of
+    /// largest magnitude, found one at a time by power iteration with deflation. Each power iteration stops once the
+    /// eigenvector changes by less than the square root of the machine epsilon, and errors if that takes more than the
+    /// given number of iterations
+    pub fn low_rank_approx_symmetric(
+        &self,
+        k: usize,
+        iterations: usize,
+    ) -> Result<Self, &'static str> {
+        if !self.is_symmetric(T::epsilon().sqrt() * self.frobenius_norm()) {
+            return Err("The matrix was not symmetric");
+        }
+        if k > self.rows {
+            return Err("The rank is larger than the size of the matrix");
+        }
+
+        let size: usize = self.rows;
+        let tolerance: T = T::epsilon().sqrt();
+        let mut deflated: Self = self.clone();
+        let mut approximation: Self = Self::square_matrix(size);
+
+        for _ in 0..k {
+            let mut vector: Vec<T> = (0..size)
+                .map(|i| T::one() + T::from(i).unwrap() / T::from(size).unwrap())
+                .collect();
+            let norm: T = Self::vector_norm(&vector);
+            vector.iter_mut().for_each(|value| *value /= norm);
+
+            let mut converged: bool = false;
+            for _ in 0..iterations {
+                // The vector has an entry for each column, so this can't fail
+                let mut next_vector: Vec<T> = deflated.try_mul_vec(&vector).unwrap();
+                let next_norm: T = Self::vector_norm(&next_vector);
+                if next_norm == T::zero() {
+                    // The remaining matrix is zero, so every later eigenvalue is zero too
+                    return Ok(approximation);
+                }
+                next_vector.iter_mut().for_each(|value| *value /= next_norm);
+
+                // A negative eigenvalue flips the vector every iteration, so compare up to sign
+                let difference: Vec<T> = next_vector
+                    .iter()
+                    .zip(&vector)
+                    .map(|(a, b)| *a - *b)
+                    .collect();
+                let sum: Vec<T> = next_vector
+                    .iter()
+                    .zip(&vector)
+                    .map(|(a, b)| *a + *b)
+                    .collect();
+                vector = next_vector;
+                if Self::vector_norm(&difference).min(Self::vector_norm(&sum)) <= tolerance {
+                    converged = true;
+                    break;
+                }
+            }
+            if !converged {
+                return Err(
+                    "The power iteration did not converge within the maximum number of iterations",
+                );
+            }
+
+            let eigenvalue: T =
+                Self::inner_product(&vector, &deflated.try_mul_vec(&vector).unwrap());
+            let column: Self = Self::matrix_from_list(&vector, size, 1);
+            let component: Self = column.clone() * column.transpose() * eigenvalue;
+            deflated -= component.clone();
+            approximation += component;
+        }
+
+        Ok(approximation)
+    }
+
+    /// Creates a size x size Gaussian blur kernel with standard deviation sigma, built as the outer product of the
+    /// sampled 1D Gaussian profile with itself. The profile is normalized after truncation to the kernel size, so the
+    /// kernel sums to 1. Errors if the size isn't odd or sigma isn't positive
+    pub fn gaussian_kernel(size: usize, sigma: T) -> Result<Self, MatrixError> {
+        if size % 2 == 0 {
+            return Err(MatrixError::InvalidArgument {
+                name: "kernel size",
+                requirement: "must be odd",
+            });
+        }
+        if sigma <= T::zero() {
+            return Err(MatrixError::InvalidArgument {
+                name: "standard deviation",
+                requirement: "must be positive",
+            });
+        }
+
+        let center: T = T::from(size / 2).unwrap();
+        let two: T = T::one() + T::one();
+        let mut profile: Vec<T> = (0..size)
+            .map(|i| {
+                let offset: T = T::from(i).unwrap() - center;
+                (-(offset * offset) / (two * sigma * sigma)).exp()
+            })
+            .collect();
+        let total: T = profile.iter().fold(T::zero(), |sum, value| sum + *value);
+        profile.iter_mut().for_each(|value| *value /= total);
+
+        let column: Self = Self::matrix_from_list(&profile, size, 1);
+        Ok(column.clone() * column.transpose())
+    }
+
+    /// Creates the 3x3 Sobel operator for the derivative along the columns. Convolving with it gives 8 times the rate
+    /// of increase from left to right
+    pub fn sobel_x() -> Self {
+        Self::from_float_rows(&[[1.0, 0.0, -1.0], [2.0, 0.0, -2.0], [1.0, 0.0, -1.0]])
+    }
+
+    /// Creates the 3x3 Sobel operator for the derivative along the rows. Convolving with it gives 8 times the rate of
+    /// increase from top to bottom
+    pub fn sobel_y() -> Self {
+        Self::from_float_rows(&[[1.0, 2.0, 1.0], [0.0, 0.0, 0.0], [-1.0, -2.0, -1.0]])
+    }
+
+    /// Creates the 3x3 discrete Laplacian kernel, using the four direct neighbours
+    pub fn laplacian_kernel() -> Self {
+        Self::from_float_rows(&[[0.0, 1.0, 0.0], [1.0, -4.0, 1.0], [0.0, 1.0, 0.0]])
+    }
+
+    /// Creates a size x size mean filter kernel, with every entry 1 / size^2. Errors if the size is zero
+    pub fn box_kernel(size: usize) -> Result<Self, MatrixError> {
+        if size == 0 {
+            return Err(MatrixError::InvalidArgument {
+                name: "kernel size",
+                requirement: "must be positive",
+            });
+        }
+
+        let value: T = T::one() / T::from(size * size).unwrap();
+        Ok(Self::from_vector(&vec![vec![value; size]; size]))
+    }
+
+    /// Returns the mean of each pooling window, with the same windows as max_pool. Partial windows are averaged over
+    /// only the entries they cover. Errors if a pool size or stride is zero
+    pub fn average_pool(
+        &self,
+        pool_rows: usize,
+        pool_columns: usize,
+        stride_rows: usize,
+        stride_columns: usize,
+        include_partial: bool,
+    ) -> Result<Self, MatrixError> {
+        let (output_rows, output_columns) = self.pool_output_size(
+            pool_rows,
+            pool_columns,
+            stride_rows,
+            stride_columns,
+            include_partial,
+        )?;
+
+        let mut pooled: Self = Self::new(output_rows, output_columns);
+        for output_row in 0..output_rows {
+            let start_row: usize = output_row * stride_rows;
+            let end_row: usize = (start_row + pool_rows).min(self.rows);
+
+            for output_column in 0..output_columns {
+                let start_column: usize = output_column * stride_columns;
+                let end_column: usize = (start_column + pool_columns).min(self.columns);
+
+                let mut sum: T = T::zero();
+                for row in &self.matrix[start_row..end_row] {
+                    for value in &row[start_column..end_column] {
+                        sum += *value;
+                    }
+                }
+                let count: T =
+                    T::from((end_row - start_row) * (end_column - start_column)).unwrap();
+                pooled.set_value(output_row, output_column, sum / count);
+            }
+        }
+
+        Ok(pooled)
+    }
+
+    /// Returns the N x N matrix of Euclidean distances between every pair of the N rows of this matrix, which is
+    /// symmetric with a zero diagonal. Errors if the matrix has no rows
+    pub fn pairwise_distance_rows(&self) -> Result<Self, &'static str> {
+        if self.rows == 0 {
+            return Err("The matrix has no rows");
+        }
+
+        let mut distances: Self = Self::square_matrix(self.rows);
+        for i in 0..self.rows {
+            for j in i + 1..self.rows {
+                let difference: Vec<T> = self.matrix[i]
+                    .iter()
+                    .zip(&self.matrix[j])
+                    .map(|(a, b)| *a - *b)
+                    .collect();
+                let distance: T = Self::vector_norm(&difference);
+                distances.set_value(i, j, distance);
+                distances.set_value(j, i, distance);
+            }
+        }
 
-            if !check_passed {
-                return Err("The system was inconsistent and there is no solution for b.");
+        Ok(distances)
+    }
+
+    /// Calculates the PageRank scores of the graph with this adjacency matrix, where entry (i, j) is the weight of
+    /// the link from node i to node j. Each row is normalized into transition probabilities, and nodes with no
+    /// outgoing links spread their score uniformly over every node. Each step follows a link with probability damping
+    /// and jumps to a uniformly random node otherwise, and the iteration stops once the scores change by at most the
+    /// tolerance in the L1 norm. The scores sum to 1. Errors if the matrix isn't square, has a negative entry, the
+    /// damping isn't strictly between 0 and 1, or the iteration doesn't converge within max_iterations
+    pub fn page_rank(
+        &self,
+        damping: T,
+        tolerance: T,
+        max_iterations: usize,
+    ) -> Result<Vec<T>, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        self.check_non_negative()?;
+        if damping <= T::zero() || damping >= T::one() {
+            return Err(MatrixError::InvalidArgument {
+                name: "damping factor",
+                requirement: "must be strictly between 0 and 1",
+            });
+        }
+
+        let size: usize = self.rows;
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+
+        let uniform: T = T::one() / T::from(size).unwrap();
+        let out_weights: Vec<T> = self.row_sums();
+        let mut scores: Vec<T> = vec![uniform; size];
+
+        for _ in 0..max_iterations {
+            let dangling_score: T = scores
+                .iter()
+                .zip(&out_weights)
+                .filter(|(_, weight)| weight.is_zero())
+                .fold(T::zero(), |sum, (score, _)| sum + *score);
+
+            let base: T = (T::one() - damping) * uniform + damping * dangling_score * uniform;
+            let mut next_scores: Vec<T> = vec![base; size];
+            for ((row, score), weight) in self.matrix.iter().zip(&scores).zip(&out_weights) {
+                if weight.is_zero() {
+                    continue;
+                }
+                let share: T = damping * *score / *weight;
+                for (next_score, link) in next_scores.iter_mut().zip(row) {
+                    *next_score += share * *link;
+                }
+            }
+
+            let change: T = next_scores
+                .iter()
+                .zip(&scores)
+                .fold(T::zero(), |sum, (next, previous)| {
+                    sum + (*next - *previous).abs()
+                });
+            scores = next_scores;
+            if change <= tolerance {
+                return Ok(scores);
             }
         }
 
-        Ok(Self::get_x_vector(solved_matrix))
+        Err(MatrixError::NotConverged {
+            iterations: max_iterations,
+        })
     }
 
-    /// Returns true if these two matrices are equal, within the given delta
-    pub fn equals(&self, other: &Self, delta: T) -> bool {
-        if self.rows != other.rows || self.columns != other.columns {
-            return false;
-        }
+    /// Calculates the graph Laplacian L = D - A of this weighted adjacency matrix, where D is the diagonal matrix of
+    /// vertex degrees (the row sums). Errors if the matrix isn't symmetric to within the square root of the machine
+    /// epsilon relative to its Frobenius norm, or has a negative weight
+    pub fn graph_laplacian(&self) -> Result<Self, MatrixError> {
+        let degrees: Vec<T> = self.adjacency_degrees()?;
+
+        let mut laplacian: Self = Self::from_diagonal(&degrees);
+        laplacian -= self.clone();
+        Ok(laplacian)
+    }
+
+    /// Calculates the symmetric normalized Laplacian I - D^-1/2 A D^-1/2 of this weighted adjacency matrix. Isolated
+    /// vertices, with degree zero, keep the row and column of the identity instead of dividing by zero. Errors in the
+    /// same cases as graph_laplacian
+    pub fn normalized_laplacian(&self) -> Result<Self, MatrixError> {
+        let degrees: Vec<T> = self.adjacency_degrees()?;
+        let inverse_roots: Vec<T> = degrees
+            .iter()
+            .map(|degree| {
+                if degree.is_zero() {
+                    T::zero()
+                } else {
+                    T::one() / degree.sqrt()
+                }
+            })
+            .collect();
 
+        let mut laplacian: Self = Self::identity_matrix(self.rows);
         for row in 0..self.rows {
             for column in 0..self.columns {
-                let difference: T =
-                    num_traits::sign::abs_sub(self[row][column], other[row][column]);
-                // is_positive() should exclude zero, but in my testing it doesn't
-                if (difference - delta).is_positive() && !(difference - delta).is_zero() {
-                    return false;
-                }
+                let value: T = laplacian[row][column]
+                    - inverse_roots[row] * self[row][column] * inverse_roots[column];
+                laplacian.set_value(row, column, value);
             }
         }
 
-        true
+        Ok(laplacian)
     }
-}
 
-impl<T> Clone for Matrix<T>
-where
-    T: MatrixCompatible,
-{
-    /// Safely clones this matrix
-    fn clone(&self) -> Self {
-        let mut matrix: Vec<Vec<T>> = Vec::with_capacity(self.rows);
+    /// Estimates the rank as the number of pivots from Gaussian elimination with complete pivoting whose magnitude
+    /// is greater than ratio times the largest pivot. Since it only compares pivots to each other, scaling the matrix
+    /// doesn't change the estimate
+    pub fn rank_by_pivot_ratio(&self, ratio: T) -> usize {
+        let mut reduced: Vec<Vec<T>> = self.matrix.clone();
+        let steps: usize = self.rows.min(self.columns);
+        let mut pivots: Vec<T> = Vec::with_capacity(steps);
 
-        for i in 0..self.rows {
-            matrix.push(self.matrix[i].clone());
+        for step in 0..steps {
+            // Complete pivoting: move the largest remaining entry into the pivot position
+            let mut pivot_position: (usize, usize) = (step, step);
+            for (row, values) in reduced.iter().enumerate().skip(step) {
+                for (column, value) in values.iter().enumerate().skip(step) {
+                    if value.abs() > reduced[pivot_position.0][pivot_position.1].abs() {
+                        pivot_position = (row, column);
+                    }
+                }
+            }
+
+            let pivot: T = reduced[pivot_position.0][pivot_position.1];
+            if pivot.is_zero() {
+                break;
+            }
+            reduced.swap(step, pivot_position.0);
+            reduced
+                .iter_mut()
+                .for_each(|row| row.swap(step, pivot_position.1));
+            pivots.push(pivot.abs());
+
+            let pivot_row: Vec<T> = reduced[step].clone();
+            for row in reduced.iter_mut().skip(step + 1) {
+                let factor: T = row[step] / pivot;
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(step) {
+                    *value -= factor * *pivot_value;
+                }
+            }
         }
 
-        Self {
-            matrix,
-            rows: self.rows,
-            columns: self.columns,
+        match pivots.first() {
+            Some(largest) => {
+                let threshold: T = ratio * *largest;
+                pivots.iter().filter(|pivot| **pivot > threshold).count()
+            }
+            None => 0,
         }
     }
-}
 
-impl<T> ops::Add for Matrix<T>
-where
-    T: MatrixCompatible,
-{
-    type Output = Self;
+    /// Calculates the angle in radians between two vectors, in [0, pi]. Errors if the lengths differ or either vector is
+    /// zero
+    pub fn vector_angle(a: &[T], b: &[T]) -> Result<T, &'static str> {
+        if a.len() != b.len() {
+            return Err("The vectors must have the same length");
+        }
 
-    /// Adds two matrices together
-    fn add(self, rhs: Self) -> Self {
-        if self.rows != rhs.rows || self.columns != rhs.columns {
-            panic!("Matrix size mismatch!");
+        let norm_a: T = Self::vector_norm(a);
+        let norm_b: T = Self::vector_norm(b);
+        if norm_a.is_zero() || norm_b.is_zero() {
+            return Err("The angle is undefined for a zero vector");
         }
 
-        let mut output: Self = Self::new(self.rows, self.columns);
+        // acos of the normalized dot product loses half its digits near 0 and pi, so this uses Kahan's
+        // 2 atan2(||a|b| - |b|a||, ||a|b| + |b|a||), which stays accurate across the whole range
+        let difference: Vec<T> = (0..a.len())
+            .map(|i| a[i] * norm_b - b[i] * norm_a)
+            .collect();
+        let sum: Vec<T> = (0..a.len())
+            .map(|i| a[i] * norm_b + b[i] * norm_a)
+            .collect();
+        let two: T = T::one() + T::one();
+        Ok(two * Self::vector_norm(&difference).atan2(Self::vector_norm(&sum)))
+    }
 
-        for row_index in 0..self.rows {
-            for column_index in 0..self.columns {
-                let value: T = self[row_index][column_index] + rhs[row_index][column_index];
-                output.set_value(row_index, column_index, value);
+    /// Returns a matrix whose columns are an orthonormal basis for the column space, using modified Gram-Schmidt. A
+    /// column is dropped as linearly dependent when what's left of it after removing the earlier directions is within
+    /// the square root of the machine epsilon of its original norm. Errors if every column is dropped
+    pub fn gram_schmidt(&self) -> Result<Self, &'static str> {
+        let tolerance: T = T::epsilon().sqrt();
+        let mut basis: Vec<Vec<T>> = Vec::new();
+
+        for column in 0..self.columns {
+            let mut vector: Vec<T> = (0..self.rows).map(|row| self[row][column]).collect();
+            let original_norm: T = Self::vector_norm(&vector);
+
+            // Each projection uses the partially orthogonalized vector, which is what keeps the modified form stable
+            for direction in &basis {
+                let projection: T = Self::inner_product(direction, &vector);
+                for (value, direction_value) in vector.iter_mut().zip(direction) {
+                    *value -= projection * *direction_value;
+                }
+            }
+
+            let norm: T = Self::vector_norm(&vector);
+            if norm <= tolerance * original_norm || norm.is_zero() {
+                continue;
             }
+            basis.push(vector.iter().map(|value| *value / norm).collect());
         }
 
-        output
+        if basis.is_empty() {
+            return Err("The column space only contains the zero vector");
+        }
+
+        let mut output: Self = Self::new(self.rows, basis.len());
+        for (column, direction) in basis.iter().enumerate() {
+            for (row, value) in direction.iter().enumerate() {
+                output.set_value(row, column, *value);
+            }
+        }
+
+        Ok(output)
     }
-}
 
-impl<T> ops::AddAssign for Matrix<T>
-where
-    T: MatrixCompatible,
-{
-    /// Adds and reassigns two matrices together
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.clone() + rhs;
+    /// Returns the tolerance below which a pivot is treated as rounding error: the machine epsilon scaled by the larger
+    /// dimension and the largest entry magnitude
+    fn elimination_tolerance(&self) -> T {
+        let largest: T = self
+            .matrix
+            .iter()
+            .flatten()
+            .fold(T::zero(), |largest, value| largest.max(value.abs()));
+        T::from(self.rows.max(self.columns).max(1)).unwrap() * T::epsilon() * largest
     }
-}
 
-impl<T> ops::Sub for Matrix<T>
-where
-    T: MatrixCompatible,
-{
-    type Output = Self;
+    /// Solves Ax = b through the row echelon form and reports how far the answer can be trusted: the residual norm
+    /// |b - Ax| against this matrix, the 1-norm condition number |A| |A^-1| (with A^-1 built from the same
+    /// elimination), the smallest pivot magnitude, and the pivot growth, the largest entry of the echelon matrix
+    /// relative to the largest entry of A. The system is flagged as ill-conditioned when the condition number exceeds
+    /// the reciprocal of the square root of the machine epsilon, meaning about half the digits of the solution may be
+    /// lost. Errors if the matrix isn't square or is singular, where a pivot no larger than the machine epsilon times
+    /// the size and the largest entry magnitude counts as zero
+    pub fn solve_with_diagnostics(&self, b: &[T]) -> Result<SolveReport<T>, MatrixError> {
+        if self.rows != self.columns {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        if b.len() != self.rows {
+            return Err(self.mismatch("solve_with_diagnostics", b.len(), 1));
+        }
 
-    /// Subtracts the two matrices. Equivalent to self + rhs * -1.0 for f64
-    fn sub(self, rhs: Self) -> Self {
-        let negative_rhs: Self = rhs * T::one().neg();
-        self + negative_rhs
-    }
-}
+        let echelon: EchelonForm<T> = self.row_echelon_with_tolerance(self.elimination_tolerance());
+        if echelon.rank() < self.rows {
+            return Err(MatrixError::Singular);
+        }
+        let solution: Vec<T> = echelon.solve(b)?;
+        let residual_norm: T = self.residual_norm(&solution, b);
 
-impl<T> ops::SubAssign for Matrix<T>
-where
-    T: MatrixCompatible,
-{
-    /// Subtracts and assigns matrices
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = self.clone() - rhs;
+        // Each column of the inverse solves against a column of the identity
+        let mut inverse_norm: T = T::zero();
+        for column in 0..self.columns {
+            let mut unit: Vec<T> = vec![T::zero(); self.rows];
+            unit[column] = T::one();
+            let column_sum: T = echelon
+                .solve(&unit)?
+                .iter()
+                .fold(T::zero(), |sum, value| sum + value.abs());
+            inverse_norm = inverse_norm.max(column_sum);
+        }
+        let matrix_norm: T = (0..self.columns)
+            .map(|column| (0..self.rows).fold(T::zero(), |sum, row| sum + self[row][column].abs()))
+            .fold(T::zero(), T::max);
+        let condition_estimate: T = matrix_norm * inverse_norm;
+
+        let smallest_pivot: T = echelon
+            .pivots()
+            .iter()
+            .map(|(row, column)| echelon.matrix()[*row][*column].abs())
+            .fold(T::infinity(), T::min);
+        let largest_entry = |matrix: &Self| -> T {
+            matrix
+                .matrix
+                .iter()
+                .flatten()
+                .fold(T::zero(), |largest, value| largest.max(value.abs()))
+        };
+        let pivot_growth: T = largest_entry(echelon.matrix()) / largest_entry(self);
+
+        Ok(SolveReport {
+            solution,
+            residual_norm,
+            condition_estimate,
+            smallest_pivot,
+            pivot_growth,
+            ill_conditioned: condition_estimate > T::epsilon().sqrt().recip(),
+        })
     }
-}
 
-impl<T> ops::Mul for Matrix<T>
-where
-    T: MatrixCompatible,
-{
-    type Output = Self;
+    /// Returns the sign of the determinant (-1, 0, or 1) and the natural log of its absolute value, summing the logs
+    /// of the pivot magnitudes from the row echelon form so a determinant far outside the range of T still has a finite
+    /// log. A pivot no larger than the machine epsilon times the size and the largest entry magnitude counts as zero,
+    /// and a singular matrix gives a sign of 0 and a log of negative infinity. Errors if the matrix isn't square
+    pub fn log_determinant(&self) -> Result<(T, T), &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
 
-    /// Multiplies two matrices together. Abides by standard matrix multiplication rules
-    fn mul(self, rhs: Self) -> Self {
-        if self.columns != rhs.rows {
-            panic!("Left hand columns must equal right hand rows!");
+        let echelon: EchelonForm<T> = self.row_echelon_with_tolerance(self.elimination_tolerance());
+        if echelon.rank() < self.rows {
+            return Ok((T::zero(), T::neg_infinity()));
         }
 
-        let common_size: usize = self.columns;
+        let mut sign: T = echelon.swap_sign;
+        let mut log_magnitude: T = T::zero();
+        for (row, column) in echelon.pivots() {
+            let pivot: T = echelon.matrix()[*row][*column];
+            if pivot < T::zero() {
+                sign = sign.neg();
+            }
+            log_magnitude += pivot.abs().ln();
+        }
 
-        let mut output: Self = Self::new(self.rows, rhs.columns);
+        Ok((sign, log_magnitude))
+    }
 
-        for output_row in 0..self.rows {
-            for output_column in 0..rhs.columns {
-                let mut a: Vec<T> = Vec::with_capacity(common_size);
-                for i in 0..common_size {
-                    a.push(self[output_row][i]);
-                }
+    /// Scales the rows and then the columns so the largest magnitude in each is close to one, returning
+    /// D_r A D_c along with the row scales (the diagonal of D_r) and the column scales (the diagonal of D_c). Every scale
+    /// is a power of two, so scaling never rounds. Zero rows and columns get a scale of one
+    pub fn equilibrate(&self) -> (Self, Vec<T>, Vec<T>) {
+        let power_of_two_scale = |largest: T| -> T {
+            if largest.is_zero() {
+                return T::one();
+            }
+            let exponent: i32 = largest.log2().round().to_i32().unwrap_or(0);
+            (T::one() + T::one()).powi(-exponent)
+        };
 
-                let mut b: Vec<T> = Vec::with_capacity(common_size);
-                for i in 0..common_size {
-                    b.push(rhs[i][output_column]);
-                }
+        let row_scales: Vec<T> = self
+            .matrix
+            .iter()
+            .map(|row| {
+                power_of_two_scale(
+                    row.iter()
+                        .fold(T::zero(), |largest, value| largest.max(value.abs())),
+                )
+            })
+            .collect();
+        let column_scales: Vec<T> = (0..self.columns)
+            .map(|column| {
+                power_of_two_scale((0..self.rows).fold(T::zero(), |largest, row| {
+                    largest.max((self[row][column] * row_scales[row]).abs())
+                }))
+            })
+            .collect();
 
-                output.set_value(output_row, output_column, Self::inner_product(&a, &b));
+        let mut scaled: Self = self.clone();
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                scaled.set_value(
+                    row,
+                    column,
+                    self[row][column] * row_scales[row] * column_scales[column],
+                );
             }
         }
 
-        output
+        (scaled, row_scales, column_scales)
+    }
+
+    /// Solves Ax = b by equilibrating A, solving (D_r A D_c) y = D_r b with a partially pivoted LU factorization, and
+    /// returning x = D_c y. Scaling first keeps badly scaled rows from misleading the choice of pivots. Errors if the
+    /// matrix is singular
+    pub fn solve_equilibrated(&self, b: &[T]) -> Result<Vec<T>, &'static str> {
+        if self.rows != self.columns {
+            return Err("The matrix was not square");
+        }
+        if b.len() != self.rows {
+            return Err("Your b vector is not the correct length!");
+        }
+
+        let (scaled, row_scales, column_scales): (Self, Vec<T>, Vec<T>) = self.equilibrate();
+        let scaled_b: Vec<T> = b
+            .iter()
+            .zip(&row_scales)
+            .map(|(value, scale)| *value * *scale)
+            .collect();
+        let solver: LinearSolver<T> = scaled.factorize().map_err(|_| "Matrix is not invertible")?;
+        // scaled_b has an entry for each row, so this can't fail
+        let y: Vec<T> = solver.solve(&scaled_b).unwrap();
+
+        Ok(y.iter()
+            .zip(&column_scales)
+            .map(|(value, scale)| *value * *scale)
+            .collect())
+    }
+
+    /// Samples the boundary of the numerical range (field of values) {x* A x : |x| = 1} of a real 2x2 matrix, as
+    /// (real, imaginary) points at evenly spaced angles. By the elliptical range theorem the range is an ellipse with
+    /// foci at the eigenvalues and minor axis sqrt(|A|_F^2 - |λ1|^2 - |λ2|^2), which degenerates to the segment between
+    /// the eigenvalues for normal matrices. Errors if the matrix isn't 2x2 or no samples are requested
+    pub fn numerical_range_2x2(&self, samples: usize) -> Result<Vec<(T, T)>, &'static str> {
+        if self.rows != 2 || self.columns != 2 {
+            return Err("The matrix must be 2x2");
+        }
+        if samples == 0 {
+            return Err("At least one sample is needed");
+        }
+
+        let two: T = T::one() + T::one();
+        let half_trace: T = (self[0][0] + self[1][1]) / two;
+        let determinant: T = self[0][0] * self[1][1] - self[0][1] * self[1][0];
+        let discriminant: T = half_trace * half_trace - determinant;
+
+        // The eigenvalues are half_trace ± sqrt(discriminant), which are a complex conjugate pair separated vertically
+        // when the discriminant is negative
+        let (eigenvalue_magnitudes, focal_direction) = if discriminant >= T::zero() {
+            (
+                two * (half_trace * half_trace + discriminant),
+                (T::one(), T::zero()),
+            )
+        } else {
+            (two * determinant, (T::zero(), T::one()))
+        };
+        let focal_distance: T = discriminant.abs().sqrt();
+        let frobenius_squared: T = self
+            .matrix
+            .iter()
+            .flatten()
+            .fold(T::zero(), |sum, value| sum + *value * *value);
+        let semi_minor: T = (frobenius_squared - eigenvalue_magnitudes)
+            .max(T::zero())
+            .sqrt()
+            / two;
+        let semi_major: T = (semi_minor * semi_minor + focal_distance * focal_distance).sqrt();
+
+        let step: T = two * T::from(std::f64::consts::PI).unwrap() / T::from(samples).unwrap();
+        let points: Vec<(T, T)> = (0..samples)
+            .map(|sample| {
+                let angle: T = step * T::from(sample).unwrap();
+                let (major, minor) = (semi_major * angle.cos(), semi_minor * angle.sin());
+                (
+                    half_trace + major * focal_direction.0 - minor * focal_direction.1,
+                    major * focal_direction.1 + minor * focal_direction.0,
+                )
+            })
+            .collect();
+
+        Ok(points)
     }
 }
 
-impl<T> ops::Mul<T> for Matrix<T>
+/// Returns Greater if the points a, b, c make a counter-clockwise (left) turn, Less if they make a clockwise (right)
+/// turn, and Equal if they are collinear. Exact for integer coordinates, erroring only if the exact determinant
+/// overflows i128
+pub fn orient_2d<T>(a: [T; 2], b: [T; 2], c: [T; 2]) -> Result<cmp::Ordering, MatrixError>
 where
-    T: MatrixCompatible,
+    T: MatrixCompatible + PartialOrd + num_traits::NumCast,
 {
-    type Output = Self;
+    let matrix: Matrix<T> =
+        Matrix::square_matrix_from_list(&[b[0] - a[0], b[1] - a[1], c[0] - a[0], c[1] - a[1]]);
 
-    /// Scales this matrix by rhs
-    fn mul(self, rhs: T) -> Self {
-        let mut output: Self = Self::new(self.rows, self.columns);
+    matrix.determinant_sign(T::zero())
+}
 
-        for row_index in 0..self.rows {
-            for column_index in 0..self.columns {
-                let value: T = self[row_index][column_index] * rhs;
-                output.set_value(row_index, column_index, value);
-            }
+/// Returns Greater if b - a, c - a, d - a form a right-handed set (so d lies on the side of the plane through a, b, c
+/// from which a, b, c appear clockwise), Less if they form a left-handed set, and Equal if the four points are coplanar.
+/// Exact for integer coordinates, erroring only if the exact determinant overflows i128
+pub fn orient_3d<T>(
+    a: [T; 3],
+    b: [T; 3],
+    c: [T; 3],
+    d: [T; 3],
+) -> Result<cmp::Ordering, MatrixError>
+where
+    T: MatrixCompatible + PartialOrd + num_traits::NumCast,
+{
+    let mut list: Vec<T> = Vec::with_capacity(9);
+    for point in [b, c, d] {
+        for axis in 0..3 {
+            list.push(point[axis] - a[axis]);
         }
+    }
+    let matrix: Matrix<T> = Matrix::square_matrix_from_list(&list);
 
-        output
+    matrix.determinant_sign(T::zero())
+}
+
+/// Returns the inverse of the given permutation, such that inverse\[permutation\[i\]\] = i
+pub fn inverse_permutation(permutation: &[usize]) -> Vec<usize> {
+    let mut inverse: Vec<usize> = vec![0; permutation.len()];
+
+    for (index, target) in permutation.iter().enumerate() {
+        inverse[*target] = index;
     }
+
+    inverse
 }
 
-/*
-Not entirely sure how to genericize this portion, if it's possible at all
+/// The six Tait-Bryan axis orders for Euler angles. An order such as Xyz means the rotation
+/// Rx(angles\[0\]) * Ry(angles\[1\]) * Rz(angles\[2\]): intrinsic rotations about x, then the new y, then the new z,
+/// which is the same as extrinsic rotations about the fixed z, then y, then x
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EulerOrder {
+    Xyz,
+    Xzy,
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx,
+}
 
-impl ops::Mul<Matrix<f64>> for f64 {
-    type Output = Matrix<f64>;
+impl EulerOrder {
+    /// Returns the indices of the three axes in the order they appear in the rotation product
+    fn axes(self) -> [usize; 3] {
+        match self {
+            EulerOrder::Xyz => [0, 1, 2],
+            EulerOrder::Xzy => [0, 2, 1],
+            EulerOrder::Yxz => [1, 0, 2],
+            EulerOrder::Yzx => [1, 2, 0],
+            EulerOrder::Zxy => [2, 0, 1],
+            EulerOrder::Zyx => [2, 1, 0],
+        }
+    }
+}
 
-    /// Scales rhs matrix by self
-    fn mul(self, rhs: Matrix<f64>) -> Matrix<f64> {
-        rhs * self
+/// The output size of a 2D convolution or correlation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolutionMode {
+    /// Only the positions where the kernel fits entirely inside the matrix
+    Valid,
+    /// The same size as the input, with the kernel centered on each entry
+    Same,
+    /// Every position where the kernel overlaps the matrix at all
+    Full,
+}
+
+/// How values outside a matrix are filled in when padding it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Zeros outside the matrix
+    Zero,
+    /// The nearest edge value, so [a b c] extends as a a [a b c] c c
+    Replicate,
+    /// Mirrored about the edge without repeating it, so [a b c] extends as c b [a b c] b a
+    Reflect,
+    /// Wrapped around circularly, so [a b c] extends as b c [a b c] a b
+    Wrap,
+}
+
+impl Padding {
+    /// Returns the index inside a dimension of the given size whose value appears at the given index in the padding,
+    /// or None if the padding is zero there
+    fn source_index(self, index: isize, size: usize) -> Option<usize> {
+        if index >= 0 && (index as usize) < size {
+            return Some(index as usize);
+        }
+        if size == 0 {
+            return None;
+        }
+
+        let size: isize = size as isize;
+        match self {
+            Padding::Zero => None,
+            Padding::Replicate => Some(index.clamp(0, size - 1) as usize),
+            Padding::Reflect => {
+                if size == 1 {
+                    return Some(0);
+                }
+                let period: isize = 2 * (size - 1);
+                let position: isize = index.rem_euclid(period);
+                Some(if position < size {
+                    position
+                } else {
+                    period - position
+                } as usize)
+            }
+            Padding::Wrap => Some(index.rem_euclid(size) as usize),
+        }
     }
 }
-*/
 
-impl<T> ops::MulAssign for Matrix<T>
+/// A row echelon form along with the bookkeeping from the elimination that found it
+#[derive(Debug, Clone)]
+pub struct EchelonForm<T>
 where
     T: MatrixCompatible,
 {
-    /// Multiplies and assigns matrices
-    fn mul_assign(&mut self, rhs: Self) {
-        *self = self.clone() * rhs;
-    }
+    matrix: Matrix<T>,
+    lower: Matrix<T>,
+    pivots: Vec<(usize, usize)>,
+    permutation: Vec<usize>,
+    swap_sign: T,
+    pivot_product: T,
+    tolerance: T,
+    negligible: fn(T, T) -> bool,
 }
 
-impl<T> ops::MulAssign<T> for Matrix<T>
+impl<T> EchelonForm<T>
 where
     T: MatrixCompatible,
 {
-    /// Scales and assigns this matrix
-    fn mul_assign(&mut self, rhs: T) {
-        *self = self.clone() * rhs;
+    /// Returns the echelon matrix
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    /// Returns the unit lower triangular matrix of elimination multipliers L, so that P A = L U where U is the echelon
+    /// matrix and P is the permutation matrix of the row swaps
+    pub fn lower(&self) -> &Matrix<T> {
+        &self.lower
+    }
+
+    /// Returns the (row, column) of each pivot, from the top row down
+    pub fn pivots(&self) -> &[(usize, usize)] {
+        &self.pivots
+    }
+
+    /// Returns the row permutation applied by the row swaps, where row i of the echelon matrix came from row
+    /// permutation\[i\] of the original matrix
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Returns the product of the pivots, not counting the sign changes from row swaps
+    pub fn pivot_product(&self) -> T {
+        self.pivot_product
+    }
+
+    /// Returns the number of pivots
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Returns the determinant of the original matrix, which is the product of the pivots with a sign change for each
+    /// row swap, or zero if there are fewer pivots than rows. Errors if the matrix isn't square
+    pub fn determinant(&self) -> Result<T, &'static str> {
+        if self.matrix.rows() != self.matrix.columns() {
+            return Err("The matrix was not square");
+        }
+        if self.pivots.len() < self.matrix.rows() {
+            return Ok(T::zero());
+        }
+
+        Ok(self.swap_sign * self.pivot_product)
+    }
+
+    /// Solves Ax = b for the original matrix A by applying the recorded row swaps and multipliers to b and back
+    /// substituting through the echelon matrix, so each new b costs quadratic rather than cubic time. Free variables
+    /// are set to zero, like Matrix::solve_general. Errors if the system is inconsistent, which is judged with the same
+    /// tolerance that chose the pivots, or if b doesn't have an entry for each row
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        let rows: usize = self.matrix.rows();
+        if b.len() != rows {
+            return Err(self.matrix.mismatch("solve", b.len(), 1));
+        }
+
+        // Forward substitution through L on the permuted b
+        let mut reduced_b: Vec<T> = self.permutation.iter().map(|row| b[*row]).collect();
+        for row in 0..rows {
+            for column in 0..row {
+                let subtraction: T = self.lower[row][column] * reduced_b[column];
+                reduced_b[row] -= subtraction;
+            }
+        }
+
+        if reduced_b[self.pivots.len()..]
+            .iter()
+            .any(|value| !(self.negligible)(*value, self.tolerance))
+        {
+            return Err(MatrixError::Inconsistent);
+        }
+
+        let mut x: Vec<T> = vec![T::zero(); self.matrix.columns()];
+        for (row, pivot_column) in self.pivots.iter().rev() {
+            let mut value: T = reduced_b[*row];
+            for (entry, solved) in self.matrix[*row].iter().zip(&x).skip(pivot_column + 1) {
+                value -= *entry * *solved;
+            }
+            x[*pivot_column] = value / self.matrix[*row][*pivot_column];
+        }
+
+        Ok(x)
     }
 }
 
-impl<T> cmp::PartialEq for Matrix<T>
+/// A solution to Ax = b along with measures of how much it can be trusted
+#[derive(Debug, Clone)]
+pub struct SolveReport<T>
 where
     T: MatrixCompatible,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.equals(other, T::zero())
-    }
+    solution: Vec<T>,
+    residual_norm: T,
+    condition_estimate: T,
+    smallest_pivot: T,
+    pivot_growth: T,
+    ill_conditioned: bool,
 }
 
-impl<T> ops::Index<usize> for Matrix<T>
+impl<T> SolveReport<T>
 where
     T: MatrixCompatible,
 {
-    type Output = Vec<T>;
+    /// Returns the solution x
+    pub fn solution(&self) -> &[T] {
+        &self.solution
+    }
 
-    /// Grabs the indicated row of the matrix. Can then index that row to get a value, ie Matrix\[row\]\[column\]
-    fn index(&self, index: usize) -> &Self::Output {
-        return self.matrix[index].as_ref();
+    /// Returns the Euclidean norm of b - Ax, computed with the original matrix
+    pub fn residual_norm(&self) -> T {
+        self.residual_norm
+    }
+
+    /// Returns the estimate of the 1-norm condition number. The relative error in x can be up to this times the
+    /// relative error in A or b
+    pub fn condition_estimate(&self) -> T {
+        self.condition_estimate
+    }
+
+    /// Returns the smallest pivot magnitude found during elimination
+    pub fn smallest_pivot(&self) -> T {
+        self.smallest_pivot
+    }
+
+    /// Returns the largest entry of the echelon matrix divided by the largest entry of the original matrix. Large
+    /// growth means rounding errors were amplified during elimination
+    pub fn pivot_growth(&self) -> T {
+        self.pivot_growth
+    }
+
+    /// Returns true if the condition estimate is so large that about half the digits of the solution may be wrong
+    pub fn is_ill_conditioned(&self) -> bool {
+        self.ill_conditioned
     }
 }
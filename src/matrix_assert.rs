@@ -0,0 +1,116 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains assertion macros for comparing matrices in tests, along with the report they print on failure
+use crate::matrix::{Matrix, MatrixCompatible};
+use std::fmt;
+
+/// The most mismatched elements listed in a diff report
+const MAX_REPORTED_MISMATCHES: usize = 5;
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + fmt::Display + PartialOrd,
+{
+    /// Builds a report of the differences between two matrices, or None if every pair of entries is within tolerance.
+    /// The report lists both shapes, the number of mismatched elements, and the largest few mismatches
+    pub fn diff_report(&self, other: &Self, tolerance: T) -> Option<String> {
+        self.diff_report_by(other, |_, _| tolerance)
+    }
+
+    /// Same as diff_report, except that each pair of entries may differ by tolerance times the larger of their magnitudes
+    pub fn relative_diff_report(&self, other: &Self, tolerance: T) -> Option<String> {
+        self.diff_report_by(other, |left, right| {
+            let magnitude: T = if left.abs() > right.abs() {
+                left.abs()
+            } else {
+                right.abs()
+            };
+            magnitude * tolerance
+        })
+    }
+
+    /// Builds a diff report where the allowed difference of each pair of entries is given by allowed_difference
+    fn diff_report_by<F>(&self, other: &Self, allowed_difference: F) -> Option<String>
+    where
+        F: Fn(T, T) -> T,
+    {
+        if self.rows() != other.rows() || self.columns() != other.columns() {
+            return Some(format!(
+                "matrix shapes differ: left is {}x{}, right is {}x{}",
+                self.rows(),
+                self.columns(),
+                other.rows(),
+                other.columns()
+            ));
+        }
+
+        let mut mismatches: Vec<(usize, usize, T)> = Vec::new();
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                let left: T = self[row][column];
+                let right: T = other[row][column];
+                let difference: T = (left - right).abs();
+                // NaN differences are incomparable, so they count as mismatches too
+                let within_tolerance: bool = matches!(
+                    difference.partial_cmp(&allowed_difference(left, right)),
+                    Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+                );
+                if !within_tolerance {
+                    mismatches.push((row, column, difference));
+                }
+            }
+        }
+
+        if mismatches.is_empty() {
+            return None;
+        }
+
+        mismatches.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut report: String = format!(
+            "matrices differ: both are {}x{}, {} mismatched element(s)",
+            self.rows(),
+            self.columns(),
+            mismatches.len()
+        );
+        for (row, column, difference) in mismatches.iter().take(MAX_REPORTED_MISMATCHES) {
+            report += &format!(
+                "\n  ({}, {}): left={}, right={}, |diff|={}",
+                row, column, self[*row][*column], other[*row][*column], difference
+            );
+        }
+
+        Some(report)
+    }
+}
+
+/// Asserts that two matrices (or references to matrices) are exactly equal, printing a diff report if they aren't
+#[macro_export]
+macro_rules! assert_matrix_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(report) =
+            $crate::matrix::Matrix::diff_report(&$left, &$right, $crate::num_traits::Zero::zero())
+        {
+            panic!("assertion `left == right` failed\n{}", report);
+        }
+    };
+}
+
+/// Asserts that two matrices (or references to matrices) are equal within a tolerance, printing a diff report if they aren't.
+/// Use `tol = x` for an absolute tolerance, or `rel = x` for a tolerance relative to the magnitude of the entries
+#[macro_export]
+macro_rules! assert_matrix_approx_eq {
+    ($left:expr, $right:expr, tol = $tolerance:expr $(,)?) => {
+        if let Some(report) = $crate::matrix::Matrix::diff_report(&$left, &$right, $tolerance) {
+            panic!("assertion `left ≈ right` failed\n{}", report);
+        }
+    };
+    ($left:expr, $right:expr, rel = $tolerance:expr $(,)?) => {
+        if let Some(report) =
+            $crate::matrix::Matrix::relative_diff_report(&$left, &$right, $tolerance)
+        {
+            panic!("assertion `left ≈ right` failed\n{}", report);
+        }
+    };
+}
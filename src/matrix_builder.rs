@@ -0,0 +1,76 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains a builder for assembling a matrix one row at a time
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+
+/// Incrementally collects rows for a matrix whose row count isn't known up front
+#[derive(Debug, Default)]
+pub struct MatrixBuilder<T>
+where
+    T: MatrixCompatible,
+{
+    matrix: Vec<Vec<T>>,
+    columns: Option<usize>,
+}
+
+impl<T> MatrixBuilder<T>
+where
+    T: MatrixCompatible,
+{
+    /// Creates a new builder with no rows
+    pub fn new() -> Self {
+        Self {
+            matrix: Vec::new(),
+            columns: None,
+        }
+    }
+
+    /// Appends a row to the builder. The first row fixes the column count, and any later row of a different length is
+    /// rejected without being added. The error carries the index the row would have had along with both lengths
+    pub fn push_row(&mut self, row: Vec<T>) -> Result<(), MatrixError> {
+        match self.columns {
+            None => self.columns = Some(row.len()),
+            Some(columns) => {
+                if columns != row.len() {
+                    return Err(MatrixError::RowLengthMismatch {
+                        row: self.matrix.len(),
+                        expected: columns,
+                        actual: row.len(),
+                    });
+                }
+            }
+        }
+
+        self.matrix.push(row);
+        Ok(())
+    }
+
+    /// Appends every row of the given iterator, stopping at the first row that doesn't match the column count
+    pub fn push_rows<I>(&mut self, rows: I) -> Result<(), MatrixError>
+    where
+        I: IntoIterator<Item = Vec<T>>,
+    {
+        for row in rows {
+            self.push_row(row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of rows pushed so far
+    pub fn row_count(&self) -> usize {
+        self.matrix.len()
+    }
+
+    /// Builds the matrix from the pushed rows. Errors if no rows were pushed, since the column count is only fixed by
+    /// the first row. Use Matrix::new for a matrix with zero rows
+    pub fn build(self) -> Result<Matrix<T>, MatrixError> {
+        if self.matrix.is_empty() {
+            return Err(MatrixError::NoRows);
+        }
+
+        Ok(Matrix::from_vector(&self.matrix))
+    }
+}
@@ -0,0 +1,175 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains options for printing a matrix, including truncating large matrices
+use crate::matrix::{Matrix, MatrixCompatible};
+use std::fmt;
+
+const ELLIPSIS: &str = "...";
+
+/// Options controlling how a matrix is printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DisplayOptions {
+    precision: Option<usize>,
+    max_rows: Option<usize>,
+    max_columns: Option<usize>,
+}
+
+impl DisplayOptions {
+    /// Creates options that print every entry in full
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of digits printed after the decimal point
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the most rows that will be printed. Larger matrices show their first and last rows around an ellipsis row
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Sets the most columns that will be printed. Larger matrices show their first and last columns around an ellipsis column
+    pub fn max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = Some(max_columns);
+        self
+    }
+
+    /// Returns the indices to print out of length indices, where None marks the position of the ellipsis.
+    /// When truncating, the first half (rounded up) and the last half of the allowed indices are kept
+    fn kept_indices(length: usize, max: Option<usize>) -> Vec<Option<usize>> {
+        match max {
+            Some(max) if length > max => {
+                let head: usize = max.div_ceil(2);
+                let tail: usize = max - head;
+
+                let mut indices: Vec<Option<usize>> = (0..head).map(Some).collect();
+                indices.push(None);
+                indices.extend((length - tail..length).map(Some));
+                indices
+            }
+            _ => (0..length).map(Some).collect(),
+        }
+    }
+}
+
+/// A matrix paired with the options used to print it
+pub struct MatrixDisplay<'a, T>
+where
+    T: MatrixCompatible,
+{
+    matrix: &'a Matrix<T>,
+    options: DisplayOptions,
+}
+
+impl<'a, T> MatrixDisplay<'a, T>
+where
+    T: MatrixCompatible,
+{
+    /// Sets the number of digits printed after the decimal point
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.options = self.options.precision(precision);
+        self
+    }
+
+    /// Sets the most rows that will be printed
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.options = self.options.max_rows(max_rows);
+        self
+    }
+
+    /// Sets the most columns that will be printed
+    pub fn max_columns(mut self, max_columns: usize) -> Self {
+        self.options = self.options.max_columns(max_columns);
+        self
+    }
+}
+
+impl<'a, T> fmt::Display for MatrixDisplay<'a, T>
+where
+    T: MatrixCompatible + fmt::Display,
+{
+    /// Prints each row on its own line with the columns padded to line up
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<Option<usize>> =
+            DisplayOptions::kept_indices(self.matrix.rows(), self.options.max_rows);
+        let columns: Vec<Option<usize>> =
+            DisplayOptions::kept_indices(self.matrix.columns(), self.options.max_columns);
+
+        // Format every printed entry first so the column widths are known
+        let mut cells: Vec<Vec<String>> = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut cell_row: Vec<String> = Vec::with_capacity(columns.len());
+            for column in &columns {
+                let cell: String = match (row, column) {
+                    (Some(row), Some(column)) => match self.options.precision {
+                        Some(precision) => format!("{:.*}", precision, self.matrix[*row][*column]),
+                        None => format!("{}", self.matrix[*row][*column]),
+                    },
+                    _ => ELLIPSIS.to_string(),
+                };
+                cell_row.push(cell);
+            }
+            cells.push(cell_row);
+        }
+
+        let mut widths: Vec<usize> = vec![0; columns.len()];
+        for cell_row in &cells {
+            for (width, cell) in widths.iter_mut().zip(cell_row) {
+                *width = usize::max(*width, cell.chars().count());
+            }
+        }
+
+        for (index, cell_row) in cells.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (column, (width, cell)) in widths.iter().zip(cell_row).enumerate() {
+                if column > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{:>width$}", cell, width = width)?;
+            }
+            write!(f, "]")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + fmt::Display,
+{
+    /// Returns a printable view of this matrix that can be configured, ie. matrix.display().precision(3).max_rows(8)
+    pub fn display(&self) -> MatrixDisplay<'_, T> {
+        MatrixDisplay {
+            matrix: self,
+            options: DisplayOptions::new(),
+        }
+    }
+
+    /// Prints this matrix to a string using the given options
+    pub fn to_string_with(&self, options: &DisplayOptions) -> String {
+        MatrixDisplay {
+            matrix: self,
+            options: *options,
+        }
+        .to_string()
+    }
+}
+
+impl<T> fmt::Display for Matrix<T>
+where
+    T: MatrixCompatible + fmt::Display,
+{
+    /// Prints every entry of this matrix, one row per line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
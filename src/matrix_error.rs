@@ -0,0 +1,194 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains the structured error returned by the checked matrix operations
+use std::fmt;
+
+/// An error from a checked matrix operation, carrying the shapes involved so the caller can tell what went wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The operands of op had incompatible shapes, given as (rows, columns). A vector operand is a single column
+    DimensionMismatch {
+        op: &'static str,
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    /// The entry at position in a list of indices along axis was index, which isn't below bound
+    IndexOutOfRange {
+        axis: &'static str,
+        position: usize,
+        index: usize,
+        bound: usize,
+    },
+    /// The row at index row had actual entries where the rows before it had expected
+    RowLengthMismatch {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The operation needs a square matrix, but this one has the given rows and columns
+    NotSquare { rows: usize, columns: usize },
+    /// The operation needs a matrix of the expected shape, given as (rows, columns), but this one has the actual shape
+    UnexpectedShape {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    /// The entry at (row, column) is on the side of the diagonal that should be zero, and exceeds the tolerance
+    NotTriangular { row: usize, column: usize },
+    /// The entry at (row, column) is off the diagonal but isn't zero
+    NotDiagonal { row: usize, column: usize },
+    /// The diagonal entry at (index, index) is within the tolerance of zero, so the matrix is singular
+    SingularDiagonal { index: usize },
+    /// An iterative method still hadn't met its tolerance after the given number of iterations
+    NotConverged { iterations: usize },
+    /// An intermediate value of op's exact integer arithmetic overflowed
+    Overflow { op: &'static str },
+    /// A matrix was to be built from rows, but there were none to fix its column count
+    NoRows,
+    /// The argument named by name, which may be the matrix itself, doesn't meet the requirement, which reads as a
+    /// sentence after the name
+    InvalidArgument {
+        name: &'static str,
+        requirement: &'static str,
+    },
+    /// The determinant shares a factor with the modulus, so the matrix has no inverse modulo it
+    NotInvertibleModulo,
+    /// The matrix is singular, so the operation has no unique answer
+    Singular,
+    /// The operation needs a symmetric matrix, but this one isn't symmetric within the tolerance
+    NotSymmetric,
+    /// The operation needs a positive semi-definite matrix, but this one has a significantly negative eigenvalue
+    NotPositiveSemiDefinite,
+    /// A list of indices that should hold each of 0..length exactly once doesn't
+    NotPermutation { length: usize },
+    /// A projective transform sent a point to infinity, where its homogeneous coordinate is zero
+    PointAtInfinity,
+    /// The operation needs a 3x3 rotation matrix, but this one isn't orthogonal with determinant 1
+    NotRotation,
+    /// The row at index row doesn't have exactly one entry equal to one and zeros elsewhere
+    NotOneHot { row: usize },
+    /// The entry at (row, column) is negative, but the operation needs non-negative entries
+    NegativeEntry { row: usize, column: usize },
+    /// The row at index row of a transition matrix doesn't sum to one within the tolerance
+    NotStochastic { row: usize },
+    /// A state listed as absorbing doesn't return to itself with probability 1
+    NotAbsorbing { state: usize },
+    /// Some transient state of an absorbing chain can never reach an absorbing state
+    NeverAbsorbed,
+    /// The graph has an edge from vertex to itself, which the operation doesn't allow
+    SelfLoop { vertex: usize },
+    /// The graph has a cycle of negative total weight through vertex, so its shortest paths aren't defined
+    NegativeCycle { vertex: usize },
+    /// The linear system is inconsistent, so it has no solution
+    Inconsistent,
+    /// The entry at (row, column) is NaN or infinite
+    NonFinite { row: usize, column: usize },
+    /// The pivot at (index, index) is zero but the column below it isn't, so the matrix can't be factored without
+    /// pivoting
+    ZeroPivot { index: usize },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { op, left, right } => write!(
+                f,
+                "Dimension mismatch in {}: the left is {}x{} but the right is {}x{}",
+                op, left.0, left.1, right.0, right.1
+            ),
+            MatrixError::IndexOutOfRange {
+                axis,
+                position,
+                index,
+                bound,
+            } => write!(
+                f,
+                "The {} index {} at position {} is out of range, since it must be below {}",
+                axis, index, position, bound
+            ),
+            MatrixError::RowLengthMismatch {
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Row {} has {} entries but the previous rows have {}",
+                row, actual, expected
+            ),
+            MatrixError::NotSquare { rows, columns } => {
+                write!(f, "The matrix is {}x{} but must be square", rows, columns)
+            }
+            MatrixError::UnexpectedShape { expected, actual } => write!(
+                f,
+                "The matrix is {}x{} but must be {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            MatrixError::NotTriangular { row, column } => write!(
+                f,
+                "The entry at ({}, {}) breaks the triangular structure",
+                row, column
+            ),
+            MatrixError::NotDiagonal { row, column } => write!(
+                f,
+                "The entry at ({}, {}) is off the diagonal but isn't zero",
+                row, column
+            ),
+            MatrixError::SingularDiagonal { index } => write!(
+                f,
+                "The diagonal entry at ({}, {}) is within the tolerance of zero, so the matrix is singular",
+                index, index
+            ),
+            MatrixError::NotConverged { iterations } => {
+                write!(f, "The iteration did not converge within {} iterations", iterations)
+            }
+            MatrixError::Overflow { op } => {
+                write!(f, "An intermediate value overflowed in {}", op)
+            }
+            MatrixError::NoRows => write!(f, "There were no rows to take the column count from"),
+            MatrixError::InvalidArgument { name, requirement } => {
+                write!(f, "The {} {}", name, requirement)
+            }
+            MatrixError::NotInvertibleModulo => write!(
+                f,
+                "The determinant shares a factor with the modulus, so there is no inverse"
+            ),
+            MatrixError::Singular => write!(f, "The matrix is singular"),
+            MatrixError::NotSymmetric => write!(f, "The matrix is not symmetric"),
+            MatrixError::NotPositiveSemiDefinite => {
+                write!(f, "The matrix has a significantly negative eigenvalue")
+            }
+            MatrixError::NotPermutation { length } => write!(
+                f,
+                "The list doesn't hold each index below {} exactly once",
+                length
+            ),
+            MatrixError::PointAtInfinity => write!(f, "The point was transformed to infinity"),
+            MatrixError::NotRotation => write!(f, "The matrix is not a 3x3 rotation matrix"),
+            MatrixError::NotOneHot { row } => write!(f, "Row {} is not one-hot", row),
+            MatrixError::NegativeEntry { row, column } => {
+                write!(f, "The entry at ({}, {}) is negative", row, column)
+            }
+            MatrixError::NotStochastic { row } => write!(f, "Row {} doesn't sum to one", row),
+            MatrixError::NotAbsorbing { state } => write!(f, "State {} is not absorbing", state),
+            MatrixError::NeverAbsorbed => write!(
+                f,
+                "Some transient state can never reach an absorbing state"
+            ),
+            MatrixError::SelfLoop { vertex } => write!(f, "Vertex {} has a self-loop", vertex),
+            MatrixError::NegativeCycle { vertex } => {
+                write!(f, "A negative cycle runs through vertex {}", vertex)
+            }
+            MatrixError::Inconsistent => write!(f, "The system is inconsistent, so it has no solution"),
+            MatrixError::NonFinite { row, column } => {
+                write!(f, "The entry at ({}, {}) is not finite", row, column)
+            }
+            MatrixError::ZeroPivot { index } => write!(
+                f,
+                "The pivot at ({}, {}) is zero but the column below it isn't, so the matrix can't be factored without pivoting",
+                index, index
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
@@ -0,0 +1,133 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains the diagonally pivoted Cholesky factorization of positive semi-definite matrices
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+use std::cmp;
+
+/// Holds a diagonally pivoted Cholesky factorization P A P^T ≈ L L^T of a symmetric positive semi-definite matrix,
+/// where L is lower trapezoidal with one column for each pivot taken. The number of columns is the numerical rank
+#[derive(Debug, Clone)]
+pub struct PivotedCholesky<T>
+where
+    T: MatrixCompatible,
+{
+    lower: Matrix<T>,
+    permutation: Vec<usize>,
+}
+
+impl<T> PivotedCholesky<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// Factors the given matrix, taking the largest remaining diagonal entry as the pivot at each step and stopping once
+    /// every remaining diagonal entry is at most the tolerance. Errors if an entry is NaN or infinite, if the matrix
+    /// isn't symmetric, or if a remaining diagonal entry falls below -tolerance, which means the matrix is indefinite
+    pub fn new(matrix: &Matrix<T>, tolerance: T) -> Result<Self, MatrixError> {
+        for row in 0..matrix.rows() {
+            if let Some(column) = matrix[row].iter().position(|value| !value.is_finite()) {
+                return Err(MatrixError::NonFinite { row, column });
+            }
+        }
+        if !matrix.is_symmetric(T::epsilon() * matrix.frobenius_norm()) {
+            return Err(MatrixError::NotSymmetric);
+        }
+
+        let size: usize = matrix.rows();
+        let mut permutation: Vec<usize> = (0..size).collect();
+        let mut diagonal: Vec<T> = (0..size).map(|index| matrix[index][index]).collect();
+        let mut lower: Matrix<T> = Matrix::square_matrix(size);
+
+        let mut rank: usize = 0;
+        while rank < size {
+            let pivot: usize = (rank..size)
+                .max_by(|a, b| {
+                    diagonal[*a]
+                        .partial_cmp(&diagonal[*b])
+                        .unwrap_or(cmp::Ordering::Equal)
+                })
+                .unwrap();
+            if diagonal[pivot] <= tolerance {
+                break;
+            }
+
+            permutation.swap(rank, pivot);
+            diagonal.swap(rank, pivot);
+            for column in 0..rank {
+                let above: T = lower[rank][column];
+                lower.set_value(rank, column, lower[pivot][column]);
+                lower.set_value(pivot, column, above);
+            }
+
+            let pivot_root: T = diagonal[rank].sqrt();
+            lower.set_value(rank, rank, pivot_root);
+            for row in rank + 1..size {
+                let mut value: T = matrix[permutation[row]][permutation[rank]];
+                for k in 0..rank {
+                    value -= lower[row][k] * lower[rank][k];
+                }
+                let value: T = value / pivot_root;
+                lower.set_value(row, rank, value);
+                diagonal[row] -= value * value;
+            }
+            rank += 1;
+        }
+
+        if diagonal[rank..].iter().any(|value| *value < -tolerance) {
+            return Err(MatrixError::NotPositiveSemiDefinite);
+        }
+
+        // The first rank columns are always inside lower, so this can't fail
+        Ok(Self {
+            lower: lower.submatrix(0, 0, size, rank).unwrap(),
+            permutation,
+        })
+    }
+
+    /// Returns the numerical rank, which is the number of pivots taken
+    pub fn rank(&self) -> usize {
+        self.lower.columns()
+    }
+
+    /// Returns the lower trapezoidal factor L, with one column for each pivot
+    pub fn lower(&self) -> &Matrix<T> {
+        &self.lower
+    }
+
+    /// Returns the symmetric permutation applied by the pivoting, where row i of L belongs to row permutation\[i\] of
+    /// the original matrix
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Returns the factor G = P^T L with its rows put back in the original order, so that A ≈ G G^T with G having only
+    /// rank columns
+    pub fn low_rank_factor(&self) -> Matrix<T> {
+        let mut output: Matrix<T> = Matrix::new(self.lower.rows(), self.lower.columns());
+        for (row, original) in self.permutation.iter().enumerate() {
+            for column in 0..self.lower.columns() {
+                output.set_value(*original, column, self.lower[row][column]);
+            }
+        }
+
+        output
+    }
+
+    /// Returns P^T L L^T P, the approximation of the original matrix
+    pub fn reconstruct(&self) -> Matrix<T> {
+        let factor: Matrix<T> = self.low_rank_factor();
+        factor.try_mul(&factor.transpose()).unwrap()
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// Calculates the diagonally pivoted Cholesky factorization of this positive semi-definite matrix, stopping once
+    /// the remaining diagonal is at most the tolerance
+    pub fn pivoted_cholesky(&self, tolerance: T) -> Result<PivotedCholesky<T>, MatrixError> {
+        PivotedCholesky::new(self, tolerance)
+    }
+}
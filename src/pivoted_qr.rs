@@ -0,0 +1,209 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains the rank-revealing QR factorization with column pivoting
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+use std::cmp;
+
+/// Holds a Householder QR factorization with column pivoting A P = Q R of an m x n matrix. With k = min(m, n), Q is
+/// m x k with orthonormal columns and R is k x n upper trapezoidal, with the magnitudes of its diagonal non-increasing
+#[derive(Debug, Clone)]
+pub struct PivotedQr<T>
+where
+    T: MatrixCompatible,
+{
+    q: Matrix<T>,
+    r: Matrix<T>,
+    permutation: Vec<usize>,
+    rank: usize,
+}
+
+impl<T> PivotedQr<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// Factors the given matrix, swapping the remaining column with the largest norm into place before each Householder
+    /// reflection. Stops once every remaining column norm is at most the tolerance, treating what's left as zero, so
+    /// A P matches Q R to within the tolerance. Errors if the tolerance is negative or if an entry is NaN or infinite
+    pub fn new(matrix: &Matrix<T>, tolerance: T) -> Result<Self, MatrixError> {
+        if tolerance < T::zero() {
+            return Err(MatrixError::InvalidArgument {
+                name: "tolerance",
+                requirement: "must not be negative",
+            });
+        }
+        for row in 0..matrix.rows() {
+            if let Some(column) = matrix[row].iter().position(|value| !value.is_finite()) {
+                return Err(MatrixError::NonFinite { row, column });
+            }
+        }
+
+        let rows: usize = matrix.rows();
+        let columns: usize = matrix.columns();
+        let two: T = T::one() + T::one();
+        let mut work: Matrix<T> = matrix.clone();
+        let mut q: Matrix<T> = Matrix::identity_matrix(rows);
+        let mut permutation: Vec<usize> = (0..columns).collect();
+
+        let mut rank: usize = 0;
+        while rank < rows.min(columns) {
+            let norms: Vec<T> = (rank..columns)
+                .map(|column| {
+                    (rank..rows)
+                        .fold(T::zero(), |sum, row| {
+                            sum + work[row][column] * work[row][column]
+                        })
+                        .sqrt()
+                })
+                .collect();
+            let (offset, norm) = norms
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(cmp::Ordering::Equal))
+                .unwrap();
+            if *norm <= tolerance {
+                break;
+            }
+            let (pivot, norm) = (rank + offset, *norm);
+
+            permutation.swap(rank, pivot);
+            for row in 0..rows {
+                let value: T = work[row][rank];
+                work.set_value(row, rank, work[row][pivot]);
+                work.set_value(row, pivot, value);
+            }
+
+            // The reflection I - 2 v v^T / (v^T v) sends the column below the diagonal to alpha e_1, with alpha's sign
+            // opposite the leading entry so the subtraction forming v doesn't cancel
+            let alpha: T = if work[rank][rank] < T::zero() {
+                norm
+            } else {
+                -norm
+            };
+            let mut reflector: Vec<T> = (rank..rows).map(|row| work[row][rank]).collect();
+            reflector[0] -= alpha;
+            let scale: T = two
+                / reflector
+                    .iter()
+                    .fold(T::zero(), |sum, value| sum + *value * *value);
+
+            for column in rank + 1..columns {
+                let dot: T = (rank..rows)
+                    .zip(&reflector)
+                    .fold(T::zero(), |sum, (row, value)| {
+                        sum + work[row][column] * *value
+                    });
+                for (row, value) in (rank..rows).zip(&reflector) {
+                    work.set_value(row, column, work[row][column] - scale * dot * *value);
+                }
+            }
+            work.set_value(rank, rank, alpha);
+            for row in rank + 1..rows {
+                work.set_value(row, rank, T::zero());
+            }
+
+            // Q accumulates the reflections from the right, so A P = Q R
+            for row in 0..rows {
+                let dot: T = (rank..rows)
+                    .zip(&reflector)
+                    .fold(T::zero(), |sum, (column, value)| {
+                        sum + q[row][column] * *value
+                    });
+                for (column, value) in (rank..rows).zip(&reflector) {
+                    q.set_value(row, column, q[row][column] - scale * dot * *value);
+                }
+            }
+            rank += 1;
+        }
+
+        let size: usize = rows.min(columns);
+        let mut r: Matrix<T> = Matrix::new(size, columns);
+        for row in 0..rank {
+            for column in row..columns {
+                r.set_value(row, column, work[row][column]);
+            }
+        }
+
+        // The first size columns are always inside q, so this can't fail
+        Ok(Self {
+            q: q.submatrix(0, 0, rows, size).unwrap(),
+            r,
+            permutation,
+            rank,
+        })
+    }
+
+    /// Returns Q, whose columns are orthonormal
+    pub fn q(&self) -> &Matrix<T> {
+        &self.q
+    }
+
+    /// Returns the upper trapezoidal R
+    pub fn r(&self) -> &Matrix<T> {
+        &self.r
+    }
+
+    /// Returns the column permutation, where column j of A P is column permutation\[j\] of the original matrix
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Returns the numerical rank at the given tolerance, which is the number of diagonal entries of R whose magnitude
+    /// is above it
+    pub fn rank(&self, tolerance: T) -> usize {
+        (0..self.r.rows())
+            .take_while(|index| self.r[*index][*index].abs() > tolerance)
+            .count()
+    }
+
+    /// Returns the original indices of the columns that were pivoted in before the factorization stopped, in the order
+    /// they were chosen. These span the column space to within the tolerance given to new, so there are as many of them
+    /// as rank(tolerance) with that same tolerance, while rank with another tolerance can count more or fewer
+    pub fn selected_columns(&self) -> &[usize] {
+        &self.permutation[..self.rank]
+    }
+
+    /// Returns a least squares solution of Ax = b that only uses the selected columns, with the components for every
+    /// other column set to zero. This is the basic solution, which minimizes the residual but for a rank deficient
+    /// matrix is generally not the solution of minimum norm. Like selected_columns, it uses the tolerance given to new
+    pub fn least_squares_solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        if b.len() != self.q.rows() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "least_squares_solve",
+                left: (self.q.rows(), self.r.columns()),
+                right: (b.len(), 1),
+            });
+        }
+
+        let mut reduced: Vec<T> = (0..self.rank)
+            .map(|column| {
+                (0..b.len()).fold(T::zero(), |sum, row| sum + self.q[row][column] * b[row])
+            })
+            .collect();
+        for row in (0..self.rank).rev() {
+            for column in row + 1..self.rank {
+                reduced[row] = reduced[row] - self.r[row][column] * reduced[column];
+            }
+            reduced[row] /= self.r[row][row];
+        }
+
+        let mut solution: Vec<T> = vec![T::zero(); self.r.columns()];
+        for (column, value) in self.selected_columns().iter().zip(reduced) {
+            solution[*column] = value;
+        }
+
+        Ok(solution)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible + num_traits::Float,
+{
+    /// Calculates the rank-revealing QR factorization with column pivoting, treating remaining columns with norms at
+    /// most the tolerance as zero
+    pub fn qr_column_pivoted(&self, tolerance: T) -> Result<PivotedQr<T>, MatrixError> {
+        PivotedQr::new(self, tolerance)
+    }
+}
@@ -0,0 +1,237 @@
+/// Brayden Jonsson, 2023
+/// https://github.com/BraydenJonsson/rust-matrix
+///
+/// Contains matrix products over semirings other than the usual (+, *) arithmetic
+use crate::matrix::{Matrix, MatrixCompatible};
+use crate::matrix_error::MatrixError;
+
+/// The addition and multiplication that a matrix product is built from, along with their identities. Entry (i, j) of
+/// the product is the semiring sum over k of the semiring product of a\[i\]\[k\] and b\[k\]\[j\].
+///
+/// ```
+/// use matrix::matrix::Matrix;
+/// use matrix::semiring::Semiring;
+///
+/// /// The (max, min) semiring, where entry (i, j) of the product is the widest bottleneck along any two-step path
+/// struct Bottleneck;
+///
+/// impl Semiring<i64> for Bottleneck {
+///     fn zero() -> i64 {
+///         i64::MIN
+///     }
+///
+///     fn one() -> i64 {
+///         i64::MAX
+///     }
+///
+///     fn add(a: i64, b: i64) -> i64 {
+///         a.max(b)
+///     }
+///
+///     fn mul(a: i64, b: i64) -> i64 {
+///         a.min(b)
+///     }
+/// }
+///
+/// let capacities: Matrix<i64> = Matrix::square_matrix_from_list(&[0, 5, 2, 0, 0, 3, 0, 0, 0]);
+/// let widest: Matrix<i64> = capacities.product_with::<Bottleneck>(&capacities).unwrap();
+///
+/// assert_eq!(widest[0][2], 3);
+/// ```
+pub trait Semiring<T> {
+    /// The identity for add, which mul absorbs
+    fn zero() -> T;
+
+    /// The identity for mul
+    fn one() -> T;
+
+    /// The semiring addition, used to accumulate over k
+    fn add(a: T, b: T) -> T;
+
+    /// The semiring multiplication, used to combine a\[i\]\[k\] with b\[k\]\[j\]
+    fn mul(a: T, b: T) -> T;
+}
+
+/// The usual (+, *) arithmetic, which gives the ordinary matrix product
+pub struct StandardSemiring;
+
+impl<T> Semiring<T> for StandardSemiring
+where
+    T: MatrixCompatible,
+{
+    fn zero() -> T {
+        T::zero()
+    }
+
+    fn one() -> T {
+        T::one()
+    }
+
+    fn add(a: T, b: T) -> T {
+        a + b
+    }
+
+    fn mul(a: T, b: T) -> T {
+        a * b
+    }
+}
+
+/// The (or, and) semiring on zero and non-zero values, with results of 0 and 1. Powers of an adjacency matrix count
+/// reachability rather than paths
+pub struct BooleanSemiring;
+
+impl<T> Semiring<T> for BooleanSemiring
+where
+    T: MatrixCompatible,
+{
+    fn zero() -> T {
+        T::zero()
+    }
+
+    fn one() -> T {
+        T::one()
+    }
+
+    fn add(a: T, b: T) -> T {
+        if a.is_zero() && b.is_zero() {
+            T::zero()
+        } else {
+            T::one()
+        }
+    }
+
+    fn mul(a: T, b: T) -> T {
+        if a.is_zero() || b.is_zero() {
+            T::zero()
+        } else {
+            T::one()
+        }
+    }
+}
+
+/// The (min, +) semiring, with T::max_value() standing for infinity. Products give shortest path lengths
+pub struct MinPlus;
+
+impl<T> Semiring<T> for MinPlus
+where
+    T: MatrixCompatible + PartialOrd + num_traits::Bounded,
+{
+    fn zero() -> T {
+        T::max_value()
+    }
+
+    fn one() -> T {
+        T::zero()
+    }
+
+    fn add(a: T, b: T) -> T {
+        if b < a {
+            b
+        } else {
+            a
+        }
+    }
+
+    fn mul(a: T, b: T) -> T {
+        if a == T::max_value() || b == T::max_value() {
+            T::max_value()
+        } else {
+            a + b
+        }
+    }
+}
+
+/// The (max, +) semiring, with T::min_value() standing for negative infinity. Products give longest path lengths
+pub struct MaxPlus;
+
+impl<T> Semiring<T> for MaxPlus
+where
+    T: MatrixCompatible + PartialOrd + num_traits::Bounded,
+{
+    fn zero() -> T {
+        T::min_value()
+    }
+
+    fn one() -> T {
+        T::zero()
+    }
+
+    fn add(a: T, b: T) -> T {
+        if b > a {
+            b
+        } else {
+            a
+        }
+    }
+
+    fn mul(a: T, b: T) -> T {
+        if a == T::min_value() || b == T::min_value() {
+            T::min_value()
+        } else {
+            a + b
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: MatrixCompatible,
+{
+    /// Calculates the matrix product using the addition and multiplication of the semiring S. Errors if the inner
+    /// dimensions don't match
+    pub fn product_with<S: Semiring<T>>(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        if self.columns() != rhs.rows() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "product_with",
+                left: (self.rows(), self.columns()),
+                right: (rhs.rows(), rhs.columns()),
+            });
+        }
+
+        let mut output: Self = Self::new(self.rows(), rhs.columns());
+        for row in 0..self.rows() {
+            for column in 0..rhs.columns() {
+                let mut sum: T = S::zero();
+                for k in 0..self.columns() {
+                    sum = S::add(sum, S::mul(self[row][k], rhs[k][column]));
+                }
+                output.set_value(row, column, sum);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Raises this matrix to the given power in the semiring S by repeated squaring. The zeroth power is the semiring
+    /// identity matrix, with S::one() on the diagonal and S::zero() elsewhere. Errors if the matrix isn't square
+    pub fn pow_with<S: Semiring<T>>(&self, exponent: u32) -> Result<Self, MatrixError> {
+        if self.rows() != self.columns() {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows(),
+                columns: self.columns(),
+            });
+        }
+
+        let mut result: Self = Self::new(self.rows(), self.columns());
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                let value: T = if row == column { S::one() } else { S::zero() };
+                result.set_value(row, column, value);
+            }
+        }
+
+        let mut base: Self = self.clone();
+        let mut remaining: u32 = exponent;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result.product_with::<S>(&base)?;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = base.product_with::<S>(&base)?;
+            }
+        }
+
+        Ok(result)
+    }
+}